@@ -0,0 +1,80 @@
+use cdtext::{BlockBuilder, CDText, CDTextBuilder, CDTextPackType, Language, StreamingParser};
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+
+/// Builds a dump of 8 blocks in different languages, with 60 tracks each,
+/// with both ASCII and accented Latin-1 text.
+fn multi_block_dump() -> Vec<u8> {
+    let languages = [
+        Language::English,
+        Language::German,
+        Language::French,
+        Language::Spanish,
+        Language::Italian,
+        Language::Dutch,
+        Language::Swedish,
+        Language::Finnish,
+    ];
+
+    let blocks = languages.into_iter().map(|language| {
+        let mut block = BlockBuilder::new(language)
+            .with_text(CDTextPackType::Title, 0, format!("Album ({language:?})"))
+            .with_text(CDTextPackType::Performers, 0, "Various Artists");
+
+        for track in 1..=60u8 {
+            block = block
+                .with_text(
+                    CDTextPackType::Title,
+                    track,
+                    format!("Chanson numéro {track}"),
+                )
+                .with_text(CDTextPackType::Performers, track, format!("Artist {track}"));
+        }
+
+        block
+    });
+
+    CDTextBuilder::from_blocks(blocks)
+        .with_header(true)
+        .build()
+        .expect("the text fits")
+}
+
+fn parsing(c: &mut Criterion) {
+    let data = multi_block_dump();
+    let cdtext = CDText::new(&data);
+
+    let mut group = c.benchmark_group("parsing");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+
+    group.bench_function("parse", |b| b.iter(|| cdtext.parse().unwrap()));
+    group.bench_function("parse_borrowed", |b| {
+        b.iter(|| cdtext.parse_borrowed().unwrap())
+    });
+    group.bench_function("iter_entries", |b| b.iter(|| cdtext.iter_entries().count()));
+    group.bench_function("streaming", |b| {
+        b.iter(|| {
+            let mut parser = StreamingParser::new();
+            let mut entries = Vec::new();
+
+            for chunk in data.chunks(2048) {
+                parser.push(chunk, &mut entries).unwrap();
+            }
+
+            parser.finish(&mut entries).unwrap();
+            entries
+        })
+    });
+    group.bench_function("disc", |b| b.iter(|| cdtext.disc().unwrap()));
+
+    group.finish();
+}
+
+fn building(c: &mut Criterion) {
+    let data = multi_block_dump();
+    let builder = CDText::new(&data).to_builder().unwrap();
+
+    c.bench_function("build", |b| b.iter(|| builder.build().unwrap()));
+}
+
+criterion_group!(benches, parsing, building);
+criterion_main!(benches);
@@ -1,7 +1,7 @@
 use cdtext::CDText;
 
 fn main() {
-    let Some(filename) = std::env::args().skip(1).next() else {
+    let Some(filename) = std::env::args().nth(1) else {
         eprintln!("No filename provided!");
 
         std::process::exit(1);
@@ -16,20 +16,18 @@ fn main() {
         }
     };
 
-    let cdtext = CDText::from_data_with_length(&data);
+    let cdtext = CDText::new(&data);
 
-    let data: Vec<cdtext::CDTextEntry> = cdtext.parse();
+    let data: Vec<cdtext::CDTextEntry> = match cdtext.parse() {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("Failed to parse CD-Text: {e}");
 
-    for i in data {
-        let displayable_track = match i.track_number {
-            cdtext::CDTextTrackNumber::WholeAlbum => {
-                format!("Album")
-            },
-            cdtext::CDTextTrackNumber::Track(nr) => {
-                format!("Track #{nr}")
-            },
-        };
+            std::process::exit(1);
+        }
+    };
 
-        println!("{displayable_track}: {:?}: {:?}", i.entry_type, i.data);
+    for entry in data {
+        println!("{entry}");
     }
 }
@@ -0,0 +1,95 @@
+/*
+ * C API of the cdtext CD-Text parser.
+ *
+ * Strings are NUL-terminated UTF-8. Strings returned by the document stay valid
+ * until the document is freed; error messages are freed with cdtext_string_free().
+ */
+
+#ifndef CDTEXT_H
+#define CDTEXT_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Version of the API, increased on incompatible changes. */
+#define CDTEXT_API_VERSION 1
+
+/* Pack types. */
+#define CDTEXT_PACK_TITLE 0x80
+#define CDTEXT_PACK_PERFORMER 0x81
+#define CDTEXT_PACK_SONGWRITER 0x82
+#define CDTEXT_PACK_COMPOSER 0x83
+#define CDTEXT_PACK_ARRANGER 0x84
+#define CDTEXT_PACK_MESSAGE 0x85
+#define CDTEXT_PACK_DISC_ID 0x86
+#define CDTEXT_PACK_GENRE 0x87
+#define CDTEXT_PACK_TOC 0x88
+#define CDTEXT_PACK_TOC2 0x89
+#define CDTEXT_PACK_CLOSED_INFO 0x8d
+#define CDTEXT_PACK_CODE 0x8e
+#define CDTEXT_PACK_SIZE_INFO 0x8f
+
+/* Flags of cdtext_parse_with_options(), combined with bitwise OR. */
+/* Tolerate violations of the specification (bad CRC, unknown pack type, truncated pack),
+ * recovering as much text as possible. */
+#define CDTEXT_PARSE_LENIENT (1u << 0)
+/* Replace invalid sequences in text with U+FFFD REPLACEMENT CHARACTER. */
+#define CDTEXT_PARSE_LOSSY_TEXT (1u << 1)
+/* Keep undecodable text as entries without text instead of failing. */
+#define CDTEXT_PARSE_RAW_TEXT (1u << 2)
+/* Keep TAB ("same as previous track") strings as they are instead of expanding them. */
+#define CDTEXT_PARSE_KEEP_TABS (1u << 3)
+
+/* Parsed CD-Text data. */
+typedef struct CdtextDocument CdtextDocument;
+
+typedef struct CdtextEntry {
+    uint8_t pack_type;
+    /* Track number, 0 for the whole album. */
+    uint8_t track;
+    uint8_t block;
+    /* Text of the entry: strings, UPC/EAN and ISRC codes, supplementary genre text.
+     * NULL if the entry holds no text. */
+    const char *text;
+} CdtextEntry;
+
+/* Returns the version of the API the library implements. */
+uint32_t cdtext_api_version(void);
+
+/* Parses the data, with or without the 4-byte header. The data is not used after the call.
+ * Returns NULL on failure, storing the error message into `error` if it's not NULL. */
+CdtextDocument *cdtext_parse(const uint8_t *data, size_t length, char **error);
+
+/* Parses the data like cdtext_parse(), with options given by CDTEXT_PARSE_* flags.
+ * 0 is the same as cdtext_parse(). Fails on unknown flags, and if both
+ * CDTEXT_PARSE_LOSSY_TEXT and CDTEXT_PARSE_RAW_TEXT are set. */
+CdtextDocument *cdtext_parse_with_options(const uint8_t *data, size_t length, uint32_t flags,
+                                          char **error);
+
+/* Frees the document. Does nothing if it's NULL. */
+void cdtext_free(CdtextDocument *document);
+
+/* Returns the number of entries in the document. */
+size_t cdtext_entry_count(const CdtextDocument *document);
+
+/* Stores the entry with the index into `entry`, in the order of the data.
+ * Returns false if there's no such entry. */
+bool cdtext_entry(const CdtextDocument *document, size_t index, CdtextEntry *entry);
+
+/* Returns the first non-empty text of the pack type for the track in the first block,
+ * or NULL if there's none. */
+const char *cdtext_get(const CdtextDocument *document, uint8_t pack_type, uint8_t track);
+
+/* Frees a string returned by the library. Does nothing if it's NULL. */
+void cdtext_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CDTEXT_H */
@@ -0,0 +1,5 @@
+//! C API of the cdtext crate, built as shared and static libraries.
+//! The functions are declared in `include/cdtext.h`.
+
+// The functions live in the crate behind the `ffi` feature, linking it in exports them.
+pub use cdtext;
@@ -0,0 +1,13 @@
+#![no_main]
+
+use cdtext::{CDText, ParserOptions};
+use libfuzzer_sys::fuzz_target;
+
+// Any data, with or without the service header, must be parsed without panics.
+fuzz_target!(|data: &[u8]| {
+    for options in [ParserOptions::strict(), ParserOptions::lenient()] {
+        let cdtext = CDText::new(data).with_options(options);
+
+        let _ = cdtext.parse_with_diagnostics();
+    }
+});
@@ -0,0 +1,34 @@
+#![no_main]
+
+use cdtext::{BorrowedEntry, CDText, PackStream, ParserOptions, StreamingParser};
+use libfuzzer_sys::fuzz_target;
+
+// All the ways of parsing give the same entries for damaged, but plausible streams.
+fuzz_target!(|stream: PackStream| {
+    let data = stream.to_bytes();
+
+    for options in [ParserOptions::strict(), ParserOptions::lenient()] {
+        let cdtext = CDText::from_data(&data).with_options(options.clone());
+        let expected = format!("{:?}", cdtext.parse());
+
+        let borrowed = cdtext.parse_borrowed().map(|entries| {
+            entries
+                .into_iter()
+                .map(BorrowedEntry::into_owned)
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(format!("{borrowed:?}"), expected);
+
+        let iterated: Result<Vec<_>, _> = cdtext.iter_entries().collect();
+        assert_eq!(format!("{iterated:?}"), expected);
+
+        let mut parser = StreamingParser::with_options(options);
+        let mut streamed = Vec::new();
+        let result = data
+            .chunks(7)
+            .try_for_each(|chunk| parser.push(chunk, &mut streamed))
+            .and_then(|()| parser.finish(&mut streamed))
+            .map(|_| streamed);
+        assert_eq!(format!("{result:?}"), expected);
+    }
+});
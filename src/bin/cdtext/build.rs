@@ -0,0 +1,267 @@
+use cdtext::{
+    BlockBuilder, CDTextBuilder, Charset, Disc, Genre, GenreCode, Isrc, Language, TrackInfo, Upc,
+};
+use serde::Deserialize;
+
+use crate::{Arguments, read_file};
+
+/// Track in the album description.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Track {
+    /// Defaults to the number after the previous track.
+    number: Option<u8>,
+    title: Option<String>,
+    performer: Option<String>,
+    songwriter: Option<String>,
+    composer: Option<String>,
+    arranger: Option<String>,
+    message: Option<String>,
+    isrc: Option<Isrc>,
+}
+
+/// Album description, i.e. the text of one block. The top-level one may hold the blocks
+/// in other languages.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Album {
+    language: Option<String>,
+    charset: Option<String>,
+    title: Option<String>,
+    performer: Option<String>,
+    songwriter: Option<String>,
+    composer: Option<String>,
+    arranger: Option<String>,
+    message: Option<String>,
+    disc_id: Option<String>,
+    upc: Option<Upc>,
+    /// Name of the standard genre, e.g. `Jazz`.
+    genre: Option<String>,
+    /// Free-form text of the genre, e.g. `Smooth Jazz`.
+    genre_text: Option<String>,
+    #[serde(default)]
+    tracks: Vec<Track>,
+    #[serde(default)]
+    blocks: Vec<Album>,
+}
+
+/// Reads an album description in TOML or YAML (by the extension of the file, or by `--format`)
+/// and writes its CD-Text to the file given by `--output`. With `--header` the data starts
+/// with the 4-byte header, as cdrecord expects for `textfile=`.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let arguments = Arguments::parse(args, &["--output", "--format"], &["--header"])?;
+
+    let [path] = arguments.positional.as_slice() else {
+        return Err(String::from("expected a single album description"));
+    };
+
+    let output = arguments
+        .option("--output")
+        .ok_or("the output file is not given with --output")?;
+
+    let format = match arguments.option("--format") {
+        Some(format) => format,
+        None if path.ends_with(".yaml") || path.ends_with(".yml") => "yaml",
+        None => "toml",
+    };
+
+    let text =
+        String::from_utf8(read_file(path)?).map_err(|_| format!("{path} is not valid UTF-8"))?;
+
+    let album: Album = match format {
+        "toml" => toml::from_str(&text).map_err(|error| error.to_string()),
+        "yaml" => serde_norway::from_str(&text).map_err(|error| error.to_string()),
+        format => return Err(format!("unknown format {format:?}")),
+    }
+    .map_err(|error| format!("cannot read {path}: {error}"))?;
+
+    let data = builder(album)?
+        .with_header(arguments.flag("--header"))
+        .build()
+        .map_err(|error| format!("cannot build CD-Text: {error}"))?;
+
+    std::fs::write(output, data).map_err(|error| format!("cannot write {output}: {error}"))
+}
+
+/// Creates the builder with the top-level block first, followed by the other blocks.
+fn builder(mut album: Album) -> Result<CDTextBuilder, String> {
+    let others = std::mem::take(&mut album.blocks);
+    let mut blocks = vec![block(album)?];
+
+    for other in others {
+        if !other.blocks.is_empty() {
+            return Err(String::from("blocks can't be nested"));
+        }
+
+        blocks.push(block(other)?);
+    }
+
+    Ok(CDTextBuilder::from_blocks(blocks))
+}
+
+/// Creates the block of the album, English and ISO 8859-1 unless specified otherwise.
+fn block(album: Album) -> Result<BlockBuilder, String> {
+    let language = match &album.language {
+        Some(name) => {
+            Language::from_name(name).ok_or_else(|| format!("unknown language {name:?}"))?
+        }
+        None => Language::English,
+    };
+
+    let charset = match album.charset.as_deref() {
+        None | Some("iso-8859-1") => Charset::Iso8859_1,
+        Some("ascii") => Charset::Ascii,
+        Some("ms-jis") => Charset::MsJis,
+        Some(charset) => {
+            return Err(format!(
+                "unknown charset {charset:?}, expected iso-8859-1, ascii or ms-jis"
+            ));
+        }
+    };
+
+    let genre = match (&album.genre, album.genre_text) {
+        (Some(name), text) => Some(Genre {
+            code: GenreCode::from_name(name).ok_or_else(|| format!("unknown genre {name:?}"))?,
+            text,
+        }),
+        (None, Some(text)) => Some(Genre {
+            code: GenreCode::NotDefined,
+            text: Some(text),
+        }),
+        (None, None) => None,
+    };
+
+    let mut tracks: Vec<TrackInfo> = Vec::new();
+
+    for track in album.tracks {
+        let number = match track.number {
+            Some(number) => number,
+            None => tracks.last().map_or(1, |previous| previous.number + 1),
+        };
+
+        if !(1..=99).contains(&number) {
+            return Err(format!("track number {number} is not in 1..=99"));
+        }
+
+        tracks.push(TrackInfo {
+            number,
+            title: track.title,
+            performer: track.performer,
+            songwriter: track.songwriter,
+            composer: track.composer,
+            arranger: track.arranger,
+            message: track.message,
+            isrc: track.isrc,
+        });
+    }
+
+    let disc = Disc {
+        title: album.title,
+        performer: album.performer,
+        songwriter: album.songwriter,
+        composer: album.composer,
+        arranger: album.arranger,
+        message: album.message,
+        disc_id: album.disc_id,
+        upc: album.upc,
+        genre,
+        tracks,
+    };
+
+    Ok(BlockBuilder::from_disc(&disc, language).with_charset(charset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cdtext::CDTextPackType;
+
+    #[test]
+    fn album() {
+        let album: Album = toml::from_str(
+            r#"
+            title = "Album"
+            performer = "Band"
+            genre = "Jazz"
+            genre_text = "Smooth Jazz"
+
+            [[tracks]]
+            title = "First"
+
+            [[tracks]]
+            title = "Second"
+
+            [[tracks]]
+            number = 5
+            title = "Fifth"
+
+            [[blocks]]
+            language = "German"
+            title = "Titel"
+            "#,
+        )
+        .unwrap();
+
+        let cdtext = builder(album).unwrap();
+        let [english, german] = cdtext.blocks() else {
+            panic!("expected two blocks");
+        };
+
+        assert_eq!(english.language(), Language::English);
+        assert_eq!(english.charset(), Charset::Iso8859_1);
+        assert_eq!(english.text(CDTextPackType::Title, 0), Some("Album"));
+        assert_eq!(english.text(CDTextPackType::Performers, 0), Some("Band"));
+        assert_eq!(english.text(CDTextPackType::Title, 1), Some("First"));
+        assert_eq!(english.text(CDTextPackType::Title, 2), Some("Second"));
+        assert_eq!(english.text(CDTextPackType::Title, 3), None);
+        assert_eq!(english.text(CDTextPackType::Title, 5), Some("Fifth"));
+        assert_eq!(
+            english.genre(),
+            Some(&Genre {
+                code: GenreCode::Jazz,
+                text: Some(String::from("Smooth Jazz")),
+            })
+        );
+
+        assert_eq!(german.language(), Language::German);
+        assert_eq!(german.text(CDTextPackType::Title, 0), Some("Titel"));
+
+        let album: Album = serde_norway::from_str("title: Album\ncharset: ascii\n").unwrap();
+        let cdtext = builder(album).unwrap();
+
+        assert_eq!(cdtext.blocks()[0].charset(), Charset::Ascii);
+        assert_eq!(
+            cdtext.blocks()[0].text(CDTextPackType::Title, 0),
+            Some("Album")
+        );
+    }
+
+    #[test]
+    fn album_errors() {
+        let error = |text: &str| builder(toml::from_str(text).unwrap()).err();
+
+        assert_eq!(
+            error("[[blocks]]\n[[blocks.blocks]]\n"),
+            Some(String::from("blocks can't be nested"))
+        );
+        assert_eq!(
+            error("genre = \"Polka\""),
+            Some(String::from("unknown genre \"Polka\""))
+        );
+        assert_eq!(
+            error("language = \"Klingon\""),
+            Some(String::from("unknown language \"Klingon\""))
+        );
+        assert_eq!(
+            error("charset = \"utf-8\""),
+            Some(String::from(
+                "unknown charset \"utf-8\", expected iso-8859-1, ascii or ms-jis"
+            ))
+        );
+        assert_eq!(
+            error("[[tracks]]\nnumber = 99\n[[tracks]]\n"),
+            Some(String::from("track number 100 is not in 1..=99"))
+        );
+        assert!(toml::from_str::<Album>("year = 1999").is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use std::path::Path;
+
+use cdtext::{BlockBuilder, CDText, CDTextBuilder, CdrdaoText, Disc};
+
+use crate::{Arguments, read_file};
+
+/// Formats known to `convert`, with the extensions they are detected by.
+const FORMATS: [(&str, &[&str]); 5] = [
+    ("raw", &["cdt", "dat", "bin", "raw"]),
+    ("v07t", &["v07t"]),
+    ("cue", &["cue"]),
+    ("toc", &["toc"]),
+    ("json", &["json"]),
+];
+
+/// Finds the format given by the option, or by the extension of the file.
+fn format<'a>(option: Option<&'a str>, path: &str) -> Result<&'a str, String> {
+    if let Some(format) = option {
+        return FORMATS
+            .iter()
+            .find(|(name, _)| *name == format)
+            .map(|(name, _)| *name)
+            .ok_or_else(|| format!("unknown format {format:?}"));
+    }
+
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    FORMATS
+        .iter()
+        .find(|(_, extensions)| extensions.contains(&extension.as_str()))
+        .map(|(name, _)| *name)
+        .ok_or_else(|| format!("cannot tell the format of {path}, set it with --from or --to"))
+}
+
+/// Converts CD-Text between binary packs, Sony Input Sheets (v07t), CUE sheets,
+/// cdrdao toc-files and JSON. The formats are detected by the extensions of the files,
+/// or given by `--from` and `--to`.
+///
+/// The binary packs are the common ground: other formats are built into packs first,
+/// so everything the target format can hold is kept. Each block of the input sheets is
+/// given as a separate file, and each block is written to a separate file, numbered
+/// from the second one on (`disc.v07t`, `disc.1.v07t`, ...).
+pub fn run(args: &[String]) -> Result<(), String> {
+    let arguments = Arguments::parse(args, &["--output", "--from", "--to"], &["--header"])?;
+
+    let (Some(first), Some(output)) = (arguments.positional.first(), arguments.option("--output"))
+    else {
+        return Err(String::from("expected the input files and --output"));
+    };
+
+    let from = format(arguments.option("--from"), first)?;
+    let to = format(arguments.option("--to"), output)?;
+
+    if from != "v07t" && arguments.positional.len() > 1 {
+        return Err(String::from(
+            "only input sheets (v07t) can be given as several files",
+        ));
+    }
+
+    let data = match from {
+        "raw" => read_file(first)?,
+        _ => builder(from, &arguments.positional)?
+            .with_header(arguments.flag("--header"))
+            .build()
+            .map_err(|error| format!("cannot build CD-Text: {error}"))?,
+    };
+
+    let cdtext = CDText::new(&data);
+    let parse_error = |error: cdtext::CDTextError| format!("cannot parse {first}: {error}");
+
+    let outputs: Vec<(String, Vec<u8>)> = match to {
+        "raw" => vec![(String::from(output), data.clone())],
+        "v07t" => cdtext
+            .to_v07t()
+            .map_err(parse_error)?
+            .into_iter()
+            .enumerate()
+            .map(|(block_number, sheet)| (numbered(output, block_number), sheet))
+            .collect(),
+        "cue" => vec![(
+            String::from(output),
+            cdtext.to_cue().map_err(parse_error)?.into_bytes(),
+        )],
+        "toc" => vec![(
+            String::from(output),
+            toc_file(&cdtext.to_cdrdao().map_err(parse_error)?).into_bytes(),
+        )],
+        _ => vec![(
+            String::from(output),
+            (cdtext.disc().map_err(parse_error)?.to_json() + "\n").into_bytes(),
+        )],
+    };
+
+    for (path, content) in outputs {
+        std::fs::write(&path, content).map_err(|error| format!("cannot write {path}: {error}"))?;
+    }
+
+    Ok(())
+}
+
+/// Creates the builder from the input files in a text format.
+fn builder(format: &str, paths: &[String]) -> Result<CDTextBuilder, String> {
+    let read_text = |path: &String| {
+        String::from_utf8(read_file(path)?).map_err(|_| format!("{path} is not valid UTF-8"))
+    };
+
+    let path = &paths[0];
+
+    match format {
+        "v07t" => {
+            let mut blocks = Vec::new();
+
+            for path in paths {
+                let block = BlockBuilder::from_v07t(&read_file(path)?)
+                    .map_err(|error| format!("cannot read {path}: {error}"))?;
+
+                blocks.push(block);
+            }
+
+            Ok(CDTextBuilder::from_blocks(blocks))
+        }
+        // CD-Text file referenced by the sheet is taken as well.
+        "cue" => CDTextBuilder::from_cue_file(path).map_err(|error| format!("{path}: {error}")),
+        "toc" => CDTextBuilder::from_cdrdao(&read_text(path)?)
+            .map_err(|error| format!("cannot read {path}: {error}")),
+        _ => Disc::from_json(&read_text(path)?)
+            .map(|disc| CDTextBuilder::from_disc(&disc))
+            .map_err(|error| format!("cannot read {path}: {error}")),
+    }
+}
+
+/// Inserts the block number before the extension of the path, except for the first block.
+fn numbered(path: &str, block_number: usize) -> String {
+    if block_number == 0 {
+        return String::from(path);
+    }
+
+    match path.rsplit_once('.') {
+        Some((stem, extension)) if !extension.contains('/') => {
+            format!("{stem}.{block_number}.{extension}")
+        }
+        _ => format!("{path}.{block_number}"),
+    }
+}
+
+/// Creates an audio toc-file with the CD-Text sections, with a `TRACK` definition
+/// for every track up to the last one with text. Audio files of the tracks are to be added.
+fn toc_file(text: &CdrdaoText) -> String {
+    let mut toc = String::from("CD_DA\n\n") + &text.disc;
+    let last = text.tracks.last().map_or(0, |(number, _)| *number);
+
+    for number in 1..=last {
+        toc += "\nTRACK AUDIO\n";
+
+        if let Some((_, section)) = text.tracks.iter().find(|(track, _)| *track == number) {
+            toc += section;
+        }
+    }
+
+    toc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cdtext::{CDTextPackType, Language};
+
+    #[test]
+    fn formats() {
+        assert_eq!(format(None, "disc.CDT"), Ok("raw"));
+        assert_eq!(format(None, "dir.v07t/disc.toc"), Ok("toc"));
+        assert_eq!(format(Some("cue"), "disc.json"), Ok("cue"));
+        assert_eq!(
+            format(Some("xml"), "disc.cdt"),
+            Err(String::from("unknown format \"xml\""))
+        );
+        assert_eq!(
+            format(None, "disc"),
+            Err(String::from(
+                "cannot tell the format of disc, set it with --from or --to"
+            ))
+        );
+    }
+
+    #[test]
+    fn numbered_paths() {
+        assert_eq!(numbered("disc.v07t", 0), "disc.v07t");
+        assert_eq!(numbered("disc.v07t", 2), "disc.2.v07t");
+        assert_eq!(numbered("out.d/disc", 1), "out.d/disc.1");
+    }
+
+    #[test]
+    fn toc() {
+        let text = CdrdaoText {
+            disc: String::from("CD_TEXT {\n}\n"),
+            tracks: vec![(2, String::from("CD_TEXT {\n}\n"))],
+        };
+
+        assert_eq!(
+            toc_file(&text),
+            "CD_DA\n\nCD_TEXT {\n}\n\nTRACK AUDIO\n\nTRACK AUDIO\nCD_TEXT {\n}\n"
+        );
+    }
+
+    #[test]
+    fn conversion() {
+        let input = crate::temp_path("convert.cdt");
+        let output = crate::temp_path("convert.v07t");
+        let second = numbered(&output, 1);
+
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .with_block(BlockBuilder::new(Language::German).with_text(
+                CDTextPackType::Title,
+                0,
+                "Titel",
+            ))
+            .build()
+            .unwrap();
+        std::fs::write(&input, &data).unwrap();
+
+        let args = [input.clone(), String::from("--output"), output.clone()];
+        let result = run(&args);
+        let sheets = [read_file(&output), read_file(&second)];
+        let back = builder("v07t", &[output.clone(), second.clone()]);
+
+        for path in [&input, &output, &second] {
+            let _ = std::fs::remove_file(path);
+        }
+
+        assert_eq!(result, Ok(()));
+        assert!(sheets.iter().all(Result::is_ok));
+        assert_eq!(back.unwrap().build().unwrap(), data);
+    }
+}
@@ -0,0 +1,48 @@
+use cdtext::{CDText, ParserOptions, Recovery, ReportFormat};
+
+use crate::{Arguments, read_file};
+
+/// Prints the parsed CD-Text in the format given by `--format`: a table (the default),
+/// a Markdown report, JSON or a CUE sheet.
+///
+/// With `--lenient`, damaged data is parsed as far as possible, and the problems
+/// the parser has recovered from are printed to stderr.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let arguments = Arguments::parse(args, &["--format"], &["--lenient"])?;
+
+    let [path] = arguments.positional.as_slice() else {
+        return Err(String::from("expected a single file"));
+    };
+
+    let data = read_file(path)?;
+    let mut cdtext = CDText::new(&data);
+
+    if arguments.flag("--lenient") {
+        cdtext = cdtext.with_options(ParserOptions::lenient());
+
+        for diagnostic in cdtext.parse_with_diagnostics().1 {
+            let recovery = match diagnostic.recovery {
+                Recovery::PackSkipped => "pack skipped",
+                Recovery::DataUsed => "data used as is",
+                Recovery::Resynchronized => "resynchronized",
+                Recovery::TextReplaced => "text replaced",
+                Recovery::TextKeptAsData => "text kept as data",
+                Recovery::ParsingStopped => "parsing stopped",
+            };
+
+            eprintln!("{path}: warning: {} ({recovery})", diagnostic.problem);
+        }
+    }
+
+    let output = match arguments.option("--format").unwrap_or("table") {
+        "table" => cdtext.report(ReportFormat::PlainText),
+        "markdown" => cdtext.report(ReportFormat::Markdown),
+        "json" => cdtext.disc().map(|disc| disc.to_json() + "\n"),
+        "cue" => cdtext.to_cue(),
+        format => return Err(format!("unknown format {format:?}")),
+    }
+    .map_err(|error| format!("cannot parse {path}: {error}"))?;
+
+    print!("{output}");
+    Ok(())
+}
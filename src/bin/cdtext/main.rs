@@ -0,0 +1,170 @@
+//! Command line tool for CD-Text data.
+
+mod build;
+mod convert;
+mod dump;
+mod validate;
+
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: cdtext <command> [options]
+
+Commands:
+  dump [--format table|markdown|json|cue] [--lenient] <file>
+                                           Print the parsed CD-Text of a binary file,
+                                           leniently reporting problems to stderr
+  build --output <file> [--format toml|yaml] [--header] <album>
+                                           Write the CD-Text of an album description
+  convert --output <file> [--from <format>] [--to <format>] [--header] <file>...
+                                           Convert between raw, v07t, cue, toc and json
+  validate [--repair <file>] <file>        Check a binary file, optionally write a repaired copy
+";
+
+/// Arguments of a command: options with values, flags and positional arguments.
+pub struct Arguments {
+    options: Vec<(String, String)>,
+    flags: Vec<String>,
+    pub positional: Vec<String>,
+}
+
+impl Arguments {
+    /// Splits the arguments into the options, which take a value, the flags and
+    /// the positional arguments. Fails on unknown options.
+    pub fn parse(args: &[String], options: &[&str], flags: &[&str]) -> Result<Self, String> {
+        let mut arguments = Self {
+            options: Vec::new(),
+            flags: Vec::new(),
+            positional: Vec::new(),
+        };
+
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            if flags.contains(&arg.as_str()) {
+                arguments.flags.push(arg.clone());
+            } else if options.contains(&arg.as_str()) {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("option {arg} needs a value"))?;
+
+                arguments.options.push((arg.clone(), value.clone()));
+            } else if arg.starts_with('-') && arg.len() > 1 {
+                return Err(format!("unknown option {arg}"));
+            } else {
+                arguments.positional.push(arg.clone());
+            }
+        }
+
+        Ok(arguments)
+    }
+
+    /// Returns the value of the option, the last one if it's given several times.
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(option, _)| option == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the flag is given.
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+}
+
+/// Reads the file, with the error message naming it.
+pub fn read_file(path: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|error| format!("cannot read {path}: {error}"))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("build") => build::run(&args[1..]),
+        Some("convert") => convert::run(&args[1..]),
+        Some("dump") => dump::run(&args[1..]),
+        Some("validate") => validate::run(&args[1..]),
+        Some("-h" | "--help" | "help") | None => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Some(command) => Err(format!("unknown command {command:?}\n\n{USAGE}")),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("cdtext: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Returns a path in the temporary directory, unique to the test process.
+#[cfg(test)]
+fn temp_path(name: &str) -> String {
+    let name = format!("cdtext-{}-{name}", std::process::id());
+    std::env::temp_dir().join(name).display().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| String::from(*arg)).collect()
+    }
+
+    #[test]
+    fn arguments() {
+        let arguments = Arguments::parse(
+            &args(&[
+                "--format",
+                "json",
+                "a.cdt",
+                "--lenient",
+                "-",
+                "--format",
+                "cue",
+            ]),
+            &["--format"],
+            &["--lenient"],
+        )
+        .unwrap();
+
+        assert_eq!(arguments.option("--format"), Some("cue"));
+        assert_eq!(arguments.option("--output"), None);
+        assert!(arguments.flag("--lenient"));
+        assert_eq!(arguments.positional, ["a.cdt", "-"]);
+
+        assert_eq!(
+            Arguments::parse(&args(&["--verbose"]), &[], &[]).err(),
+            Some(String::from("unknown option --verbose"))
+        );
+        assert_eq!(
+            Arguments::parse(&args(&["a.cdt", "--format"]), &["--format"], &[]).err(),
+            Some(String::from("option --format needs a value"))
+        );
+    }
+
+    #[test]
+    fn dump_errors() {
+        let path = temp_path("dump.cdt");
+        let data = cdtext::CDTextBuilder::new()
+            .with_text(cdtext::CDTextPackType::Title, 0, "Album")
+            .build()
+            .unwrap();
+        std::fs::write(&path, data).unwrap();
+
+        let result = dump::run(&args(&["--format", "xml", &path]));
+        let missing = dump::run(&args(&["--format", "json"]));
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(result, Err(String::from("unknown format \"xml\"")));
+        assert_eq!(missing, Err(String::from("expected a single file")));
+        assert!(read_file(&path).unwrap_err().starts_with("cannot read "));
+    }
+}
@@ -0,0 +1,152 @@
+use cdtext::{CDText, CDTextTrackNumber, IssueKind, Severity, ValidationIssue};
+
+use crate::{Arguments, read_file};
+
+/// Validates the data and prints the issues found. With `--repair`, writes a copy of the data
+/// with sequence numbers, Block Size Info counters and CRCs fixed, and validates it as well.
+///
+/// Fails if there are errors in the data, or in the repaired copy if one is written.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let arguments = Arguments::parse(args, &["--repair"], &[])?;
+
+    let [path] = arguments.positional.as_slice() else {
+        return Err(String::from("expected a single file"));
+    };
+
+    let data = read_file(path)?;
+    let cdtext = CDText::new(&data);
+
+    let mut errors = report(path, &cdtext.validate().issues);
+
+    if let Some(output) = arguments.option("--repair") {
+        let repaired = cdtext.repair();
+
+        std::fs::write(output, &repaired)
+            .map_err(|error| format!("cannot write {output}: {error}"))?;
+
+        println!();
+        errors = report(output, &CDText::new(&repaired).validate().issues);
+    }
+
+    match errors {
+        0 => Ok(()),
+        1 => Err(String::from("1 error found")),
+        errors => Err(format!("{errors} errors found")),
+    }
+}
+
+/// Prints the issues of the file. Returns the number of errors.
+fn report(path: &str, issues: &[ValidationIssue]) -> usize {
+    for issue in issues {
+        let severity = match issue.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+
+        println!("{path}: {severity}: {}", describe(&issue.kind));
+    }
+
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .count();
+
+    println!(
+        "{path}: {errors} errors, {} warnings",
+        issues.len() - errors
+    );
+
+    errors
+}
+
+/// Describes the issue in a single line.
+fn describe(kind: &IssueKind) -> String {
+    match kind {
+        IssueKind::Pack(problem) => problem.to_string(),
+        IssueKind::MissingBlockSizeInfo { block_number } => {
+            format!("block {block_number} has no complete Block Size Info")
+        }
+        IssueKind::PackCountMismatch {
+            block_number,
+            pack_type,
+            declared,
+            found,
+        } => format!(
+            "block {block_number} has {found} packs of type {pack_type:?}, Block Size Info declares {declared}"
+        ),
+        IssueKind::LastSequenceNumberMismatch {
+            block_number,
+            declared,
+            found,
+        } => format!(
+            "last pack of block {block_number} has sequence number {found}, Block Size Info declares {declared}"
+        ),
+        IssueKind::MissingTrack {
+            block_number,
+            pack_type,
+            track_number,
+        } => match track_number {
+            CDTextTrackNumber::WholeAlbum => {
+                format!("block {block_number} has no {pack_type:?} for the album")
+            }
+            CDTextTrackNumber::Track(number) => {
+                format!("block {block_number} has no {pack_type:?} for track {number}")
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cdtext::{CDTextBuilder, CDTextPackType};
+
+    #[test]
+    fn descriptions() {
+        assert_eq!(
+            describe(&IssueKind::MissingBlockSizeInfo { block_number: 1 }),
+            "block 1 has no complete Block Size Info"
+        );
+        assert_eq!(
+            describe(&IssueKind::PackCountMismatch {
+                block_number: 0,
+                pack_type: CDTextPackType::Title,
+                declared: 2,
+                found: 3,
+            }),
+            "block 0 has 3 packs of type Title, Block Size Info declares 2"
+        );
+        assert_eq!(
+            describe(&IssueKind::MissingTrack {
+                block_number: 0,
+                pack_type: CDTextPackType::Title,
+                track_number: CDTextTrackNumber::Track(2),
+            }),
+            "block 0 has no Title for track 2"
+        );
+    }
+
+    #[test]
+    fn repair() {
+        let path = crate::temp_path("validate.cdt");
+        let output = crate::temp_path("validate.repaired.cdt");
+
+        let mut data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .build()
+            .unwrap();
+        data[17] ^= 0xff;
+        std::fs::write(&path, &data).unwrap();
+
+        let damaged = run(std::slice::from_ref(&path));
+        let repaired = run(&[path.clone(), String::from("--repair"), output.clone()]);
+        let fixed = run(std::slice::from_ref(&output));
+
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&output).unwrap();
+
+        assert!(damaged.is_err());
+        assert_eq!(repaired, Ok(()));
+        assert_eq!(fixed, Ok(()));
+    }
+}
@@ -0,0 +1,181 @@
+use core::borrow::Borrow;
+
+use crate::{CDText, CDTextPack, CDTextPackType};
+#[cfg(feature = "alloc")]
+use crate::{CDTextEntry, CDTextEntryDataType};
+
+/// Contents of three Block Size Info packs (0x8F) of a block.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockSizeInfo {
+    /// Character code of the text in the block, see [`crate::Charset`].
+    pub character_code: u8,
+    pub first_track: u8,
+    pub last_track: u8,
+    /// Copy protection flags of the block: 0x00 if the text is not copyrighted,
+    /// 0x03 if it is, see [`BlockSizeInfo::is_copy_protected`].
+    pub copyright_flags: u8,
+    /// Number of packs of each type 0x80-0x8F in the block.
+    pub pack_counts: [u8; 16],
+    /// Sequence number of the last pack of each block.
+    pub last_sequence_numbers: [u8; 8],
+    /// Language code of each block, see [`crate::Language`].
+    pub language_codes: [u8; 8],
+}
+
+impl BlockSizeInfo {
+    /// Internal function. Finds Size Info of the block among the parsed entries.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn from_entries(entries: &[CDTextEntry], block_number: u8) -> Option<Self> {
+        entries.iter().find_map(|entry| match entry.data {
+            CDTextEntryDataType::BlockSizeInfo(size_info) if entry.block_number == block_number => {
+                Some(size_info)
+            }
+            _ => None,
+        })
+    }
+
+    /// Collects Size Info packs of the block. Returns `None` if any of the three packs is missing.
+    pub(crate) fn from_packs(
+        block_number: u8,
+        packs: impl IntoIterator<Item = impl Borrow<CDTextPack>>,
+    ) -> Option<Self> {
+        let mut data = [0u8; 36];
+        let mut present = [false; 3];
+
+        for pack in packs {
+            let pack = pack.borrow();
+
+            if pack.pack_type != CDTextPackType::BlockSizeInfo || pack.block_number != block_number
+            {
+                continue;
+            }
+
+            // Track number field holds the index of the Size Info pack instead.
+            let index = u8::from(pack.track_number) as usize;
+
+            if index < 3 {
+                data[index * 12..][..12].copy_from_slice(&pack.payload);
+                present[index] = true;
+            }
+        }
+
+        if present.contains(&false) {
+            return None;
+        }
+
+        Some(Self {
+            character_code: data[0],
+            first_track: data[1],
+            last_track: data[2],
+            copyright_flags: data[3],
+            pack_counts: data[4..20].try_into().ok()?,
+            last_sequence_numbers: data[20..28].try_into().ok()?,
+            language_codes: data[28..36].try_into().ok()?,
+        })
+    }
+
+    /// Internal method. Returns the contents of the three packs.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn to_bytes(self) -> [u8; 36] {
+        let mut data = [0u8; 36];
+
+        data[0] = self.character_code;
+        data[1] = self.first_track;
+        data[2] = self.last_track;
+        data[3] = self.copyright_flags;
+        data[4..20].copy_from_slice(&self.pack_counts);
+        data[20..28].copy_from_slice(&self.last_sequence_numbers);
+        data[28..36].copy_from_slice(&self.language_codes);
+
+        data
+    }
+
+    /// Whether the text of the block is marked as copyrighted. Any nonzero flags count.
+    pub fn is_copy_protected(&self) -> bool {
+        self.copyright_flags != 0
+    }
+
+    /// Returns the declared number of packs of the type in the block.
+    pub fn pack_count(&self, pack_type: CDTextPackType) -> u8 {
+        match u8::from(pack_type) {
+            n @ 0x80..=0x8f => self.pack_counts[(n - 0x80) as usize],
+            _ => 0,
+        }
+    }
+}
+
+impl CDText<'_> {
+    /// Returns Block Size Info of the block, if all three of its packs are present.
+    pub fn block_size_info(&self, block_number: u8) -> Option<BlockSizeInfo> {
+        BlockSizeInfo::from_packs(block_number, self.iter_pack_chunks())
+    }
+
+    /// Whether the text of any block is marked as copyrighted by its Block Size Info.
+    pub fn is_copy_protected(&self) -> bool {
+        (0..8).any(|block_number| {
+            self.block_size_info(block_number)
+                .is_some_and(|size_info| size_info.is_copy_protected())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finalize_pack;
+
+    /// Internal function. Builds the three Size Info packs of block 0 from their contents.
+    fn size_info_packs(data: &[u8; 36]) -> [u8; 54] {
+        let mut packs = [0u8; 54];
+
+        for (index, pack) in packs.chunks_exact_mut(18).enumerate() {
+            pack[..3].copy_from_slice(&[0x8f, index as u8, index as u8]);
+            pack[4..16].copy_from_slice(&data[index * 12..][..12]);
+            finalize_pack(pack.try_into().unwrap());
+        }
+
+        packs
+    }
+
+    #[test]
+    fn size_info() {
+        let mut data = [0u8; 36];
+        // ASCII, tracks 1-12, copyrighted.
+        data[..4].copy_from_slice(&[0x01, 1, 12, 0x03]);
+        // 4 Title packs, 2 Performers packs and 3 Size Info packs.
+        data[4] = 4;
+        data[5] = 2;
+        data[19] = 3;
+        // Sequence number of the last pack and the language of block 0.
+        data[20] = 8;
+        data[28] = 0x09;
+
+        let packs = size_info_packs(&data);
+        let cdtext = CDText::from_data(&packs);
+        let size_info = cdtext.block_size_info(0).unwrap();
+
+        assert_eq!(size_info.character_code, 0x01);
+        assert_eq!((size_info.first_track, size_info.last_track), (1, 12));
+        assert!(size_info.is_copy_protected());
+        assert!(cdtext.is_copy_protected());
+        assert_eq!(size_info.pack_count(CDTextPackType::Title), 4);
+        assert_eq!(size_info.pack_count(CDTextPackType::Performers), 2);
+        assert_eq!(size_info.pack_count(CDTextPackType::BlockSizeInfo), 3);
+        assert_eq!(size_info.pack_count(CDTextPackType::Unknown(0x20)), 0);
+        assert_eq!(size_info.last_sequence_numbers[0], 8);
+        assert_eq!(size_info.language_codes[0], 0x09);
+
+        // Size Info of other blocks is not there.
+        assert_eq!(cdtext.block_size_info(1), None);
+    }
+
+    #[test]
+    fn incomplete_size_info() {
+        let packs = size_info_packs(&[0; 36]);
+        let cdtext = CDText::from_data(&packs[..36]);
+
+        assert_eq!(cdtext.block_size_info(0), None);
+        assert!(!cdtext.is_copy_protected());
+    }
+}
@@ -0,0 +1,185 @@
+use alloc::{borrow::Cow, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+    CDText, CDTextEntry, CDTextEntryDataType, CDTextError, CDTextPack, CDTextPackType,
+    CDTextTrackNumber, parser::ParseState, visitor::CDTextVisitor,
+};
+
+/// Data of a [`BorrowedEntry`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum BorrowedData<'data> {
+    /// Text of the entry. Borrowed from the input if the string is stored within one pack
+    /// and needs no conversion to UTF-8, e.g. it's ASCII.
+    Text(Cow<'data, str>),
+    /// Any other data. Never holds [`CDTextEntryDataType::String`].
+    Other(CDTextEntryDataType),
+}
+
+impl From<CDTextEntryDataType> for BorrowedData<'_> {
+    fn from(value: CDTextEntryDataType) -> Self {
+        match value {
+            CDTextEntryDataType::String(text) => Self::Text(Cow::Owned(text)),
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl BorrowedData<'_> {
+    /// Returns the text of the entry, if it's text.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Text(text) => Some(text),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Converts the data into owned data, copying the text if it's borrowed.
+    pub fn into_owned(self) -> CDTextEntryDataType {
+        match self {
+            Self::Text(text) => CDTextEntryDataType::String(text.into_owned()),
+            Self::Other(other) => other,
+        }
+    }
+}
+
+/// The processed entry, with text borrowed from the input data where possible.
+/// Returned by [`CDText::parse_borrowed`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BorrowedEntry<'data> {
+    pub track_number: CDTextTrackNumber,
+    pub block_number: u8,
+    pub entry_type: CDTextPackType,
+    pub data: BorrowedData<'data>,
+    /// Indices of the packs the entry is assembled from, see [`CDTextEntry::packs`].
+    pub packs: Range<usize>,
+}
+
+impl BorrowedEntry<'_> {
+    /// Converts the entry into [`CDTextEntry`], copying the text if it's borrowed.
+    pub fn into_owned(self) -> CDTextEntry {
+        CDTextEntry {
+            track_number: self.track_number,
+            block_number: self.block_number,
+            entry_type: self.entry_type,
+            data: self.data.into_owned(),
+            packs: self.packs,
+        }
+    }
+}
+
+impl From<BorrowedEntry<'_>> for CDTextEntry {
+    fn from(value: BorrowedEntry<'_>) -> Self {
+        value.into_owned()
+    }
+}
+
+/// Internal trait. Receives entries from the parser as soon as they are assembled.
+pub(crate) trait EntrySink<'data> {
+    fn entry(&mut self, entry: BorrowedEntry<'data>);
+
+    fn binary_pack(&mut self, _pack_index: usize, _pack: &CDTextPack) {}
+}
+
+impl<'data> EntrySink<'data> for Vec<BorrowedEntry<'data>> {
+    fn entry(&mut self, entry: BorrowedEntry<'data>) {
+        self.push(entry);
+    }
+}
+
+impl<V: CDTextVisitor> EntrySink<'_> for V {
+    fn entry(&mut self, entry: BorrowedEntry<'_>) {
+        match entry.track_number {
+            CDTextTrackNumber::WholeAlbum => self.on_album_field(entry),
+            CDTextTrackNumber::Track(track) => self.on_track_field(track, entry),
+        }
+    }
+
+    fn binary_pack(&mut self, pack_index: usize, pack: &CDTextPack) {
+        self.on_binary_pack(pack_index, pack);
+    }
+}
+
+impl<'data> CDText<'data> {
+    /// Parses all the entries like [`CDText::parse`], but without copying text
+    /// that is stored within one pack and needs no conversion, which is most of
+    /// short ASCII titles and names.
+    pub fn parse_borrowed(&self) -> Result<Vec<BorrowedEntry<'data>>, CDTextError> {
+        let mut entries = Vec::new();
+        self.parse_entries(
+            &mut ParseState::new(self, self.options.mode()),
+            &mut entries,
+        )?;
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CDTextBuilder;
+
+    #[test]
+    fn borrows_single_pack_text() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "A title spanning two packs")
+            .with_text(CDTextPackType::Performers, 0, "Caf\u{e9}")
+            .build()
+            .unwrap();
+
+        let entries = {
+            // The entries borrow the data, not the `CDText`.
+            let cdtext = CDText::from_data(&data);
+            cdtext.parse_borrowed().unwrap()
+        };
+        let text = |pack_type, track: u8| {
+            let entry = entries
+                .iter()
+                .find(|entry| {
+                    entry.entry_type == pack_type && u8::from(entry.track_number) == track
+                })
+                .unwrap();
+
+            match &entry.data {
+                BorrowedData::Text(text) => text,
+                BorrowedData::Other(other) => panic!("not text: {other:?}"),
+            }
+        };
+
+        let album = text(CDTextPackType::Title, 0);
+        assert!(matches!(album, Cow::Borrowed("Album")));
+        assert!(data.as_ptr_range().contains(&album.as_ptr()));
+
+        assert!(matches!(
+            text(CDTextPackType::Title, 1),
+            Cow::Owned(text) if text == "A title spanning two packs"
+        ));
+        assert!(matches!(
+            text(CDTextPackType::Performers, 0),
+            Cow::Owned(text) if text == "Caf\u{e9}"
+        ));
+    }
+
+    #[test]
+    fn same_as_parse() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "A title spanning two packs")
+            .with_text(CDTextPackType::Code, 1, "USRC17607839")
+            .build()
+            .unwrap();
+        let cdtext = CDText::from_data(&data);
+
+        let entries: Vec<CDTextEntry> = cdtext
+            .parse_borrowed()
+            .unwrap()
+            .into_iter()
+            .map(CDTextEntry::from)
+            .collect();
+        assert_eq!(entries, cdtext.parse().unwrap());
+    }
+}
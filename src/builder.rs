@@ -0,0 +1,1032 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    BlockSizeInfo, BuildError, CDText, CDTextEntry, CDTextEntryDataType, CDTextError,
+    CDTextPackType, CapacityHint, Charset, Disc, Genre, Language, crc,
+};
+
+/// Pack types that hold text, in the order they are written.
+pub(crate) const TEXT_TYPES: [CDTextPackType; 10] = [
+    CDTextPackType::Title,
+    CDTextPackType::Performers,
+    CDTextPackType::Songwriters,
+    CDTextPackType::Composers,
+    CDTextPackType::Arrangers,
+    CDTextPackType::Message,
+    CDTextPackType::DiscID,
+    CDTextPackType::Genre,
+    CDTextPackType::ClosedInfo,
+    CDTextPackType::Code,
+];
+
+/// Maximum number of packs in a block, including Block Size Info.
+pub const MAX_BLOCK_PACKS: usize = 256;
+
+/// Space the content takes in the lead-in.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SizeEstimate {
+    /// Number of packs of each block, including Block Size Info.
+    pub block_packs: Vec<usize>,
+}
+
+impl SizeEstimate {
+    /// Total number of packs.
+    pub fn total_packs(&self) -> usize {
+        self.block_packs.iter().sum()
+    }
+
+    /// Total size of the packs in bytes, not counting the header.
+    pub fn bytes(&self) -> usize {
+        self.total_packs() * 18
+    }
+
+    /// Whether every block fits into [`MAX_BLOCK_PACKS`].
+    pub fn fits(&self) -> bool {
+        self.block_packs
+            .iter()
+            .all(|&packs| packs <= MAX_BLOCK_PACKS)
+    }
+}
+
+/// What to do with characters that cannot be encoded in the charset of the block.
+/// NUL characters cannot be encoded in any charset.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Unmappable {
+    /// Fail with [`BuildError::UnencodableText`].
+    #[default]
+    Error,
+    /// Replace each such character with `?`.
+    Replace,
+    /// Leave such characters out.
+    Skip,
+}
+
+/// A text that has been changed to be encodable, see [`Unmappable`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DegradedText {
+    pub block_number: u8,
+    pub pack_type: CDTextPackType,
+    pub track: u8,
+}
+
+/// Texts of one block, in one language.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockBuilder {
+    /// Texts (pack type, track, text) in the order they were set.
+    texts: Vec<(CDTextPackType, u8, String)>,
+    genre: Option<Genre>,
+    language: Language,
+    charset: Charset,
+}
+
+impl BlockBuilder {
+    /// Creates a block without any text. The text is encoded in ISO 8859-1.
+    pub fn new(language: Language) -> Self {
+        Self {
+            texts: Vec::new(),
+            genre: None,
+            language,
+            charset: Charset::Iso8859_1,
+        }
+    }
+
+    /// Creates a block from the parsed entries of the block with given number, taking its
+    /// language and charset from Block Size Info. Without Block Size Info the language is English
+    /// and the text is encoded in ISO 8859-1.
+    ///
+    /// Only the text, the genre, and UPC/ISRC codes are taken; TOC and undecodable entries aren't.
+    pub fn from_entries(entries: &[CDTextEntry], block_number: u8) -> Self {
+        let mut block = Self::new(Language::English);
+
+        for entry in entries.iter().filter(|e| e.block_number == block_number) {
+            let track = u8::from(entry.track_number);
+
+            match &entry.data {
+                CDTextEntryDataType::String(text)
+                    if !text.is_empty()
+                        && TEXT_TYPES.contains(&entry.entry_type)
+                        && entry.entry_type != CDTextPackType::Genre =>
+                {
+                    block.set_text(entry.entry_type, track, text.as_str());
+                }
+                CDTextEntryDataType::Genre(genre) => block.genre = Some(genre.clone()),
+                CDTextEntryDataType::Upc(upc) => {
+                    block.set_text(CDTextPackType::Code, track, upc.as_str());
+                }
+                CDTextEntryDataType::Isrc(isrc) => {
+                    block.set_text(CDTextPackType::Code, track, isrc.as_str());
+                }
+                CDTextEntryDataType::BlockSizeInfo(size_info) => {
+                    block.charset = size_info.charset();
+
+                    if let Some(language) = size_info.language(block_number) {
+                        block.language = language;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        block
+    }
+
+    /// Creates a block in the language from the disc model, e.g. one edited by hand
+    /// or imported from JSON. The text is encoded in ISO 8859-1.
+    pub fn from_disc(disc: &Disc, language: Language) -> Self {
+        let mut block = Self::new(language);
+
+        let album = [
+            (CDTextPackType::Title, &disc.title),
+            (CDTextPackType::Performers, &disc.performer),
+            (CDTextPackType::Songwriters, &disc.songwriter),
+            (CDTextPackType::Composers, &disc.composer),
+            (CDTextPackType::Arrangers, &disc.arranger),
+            (CDTextPackType::Message, &disc.message),
+            (CDTextPackType::DiscID, &disc.disc_id),
+        ];
+
+        for (pack_type, text) in album {
+            if let Some(text) = text {
+                block.set_text(pack_type, 0, text.as_str());
+            }
+        }
+
+        if let Some(upc) = &disc.upc {
+            block.set_text(CDTextPackType::Code, 0, upc.as_str());
+        }
+
+        block.genre.clone_from(&disc.genre);
+
+        for track in &disc.tracks {
+            let fields = [
+                (CDTextPackType::Title, &track.title),
+                (CDTextPackType::Performers, &track.performer),
+                (CDTextPackType::Songwriters, &track.songwriter),
+                (CDTextPackType::Composers, &track.composer),
+                (CDTextPackType::Arrangers, &track.arranger),
+                (CDTextPackType::Message, &track.message),
+            ];
+
+            for (pack_type, text) in fields {
+                if let Some(text) = text {
+                    block.set_text(pack_type, track.number, text.as_str());
+                }
+            }
+
+            if let Some(isrc) = &track.isrc {
+                block.set_text(CDTextPackType::Code, track.number, isrc.as_str());
+            }
+        }
+
+        block
+    }
+
+    /// Sets the language of the block.
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Sets the charset the text is encoded in. ISO 8859-1, ASCII and,
+    /// with the `encoding` feature, double-byte MS-JIS are supported.
+    pub fn with_charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Sets the text of the type for the track, replacing the one set before.
+    /// Track 0 is the whole album.
+    pub fn with_text(
+        mut self,
+        pack_type: CDTextPackType,
+        track: u8,
+        text: impl Into<String>,
+    ) -> Self {
+        self.set_text(pack_type, track, text);
+        self
+    }
+
+    /// Sets the genre of the album.
+    pub fn with_genre(mut self, genre: Genre) -> Self {
+        self.genre = Some(genre);
+        self
+    }
+
+    /// Returns the language of the block.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Returns the charset the text is encoded in.
+    pub fn charset(&self) -> Charset {
+        self.charset
+    }
+
+    /// Returns the text of the type for the track, if it's set.
+    pub fn text(&self, pack_type: CDTextPackType, track: u8) -> Option<&str> {
+        self.texts
+            .iter()
+            .find(|(t, n, _)| *t == pack_type && *n == track)
+            .map(|(_, _, text)| text.as_str())
+    }
+
+    /// Returns the genre of the album, if it's set.
+    pub fn genre(&self) -> Option<&Genre> {
+        self.genre.as_ref()
+    }
+
+    /// Sets the text of the type for the track in place, see [`BlockBuilder::with_text`].
+    pub fn set_text(&mut self, pack_type: CDTextPackType, track: u8, text: impl Into<String>) {
+        let text = text.into();
+
+        match self
+            .texts
+            .iter_mut()
+            .find(|(t, n, _)| *t == pack_type && *n == track)
+        {
+            Some(item) => item.2 = text,
+            None => self.texts.push((pack_type, track, text)),
+        }
+    }
+
+    /// Adds the texts and the genre of the other block that aren't set in this one.
+    pub fn merge(&mut self, other: &BlockBuilder) {
+        for (pack_type, track, text) in &other.texts {
+            if self.text(*pack_type, *track).is_none() {
+                self.texts.push((*pack_type, *track, text.clone()));
+            }
+        }
+
+        if self.genre.is_none() {
+            self.genre.clone_from(&other.genre);
+        }
+    }
+
+    /// Internal method. Returns the last track that has any text.
+    fn last_track(&self) -> u8 {
+        self.texts
+            .iter()
+            .map(|&(_, track, _)| track)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Internal method. Checks that all the texts can be written.
+    fn check(&self) -> Result<(), BuildError> {
+        if !self.charset.is_encodable() {
+            return Err(BuildError::UnsupportedCharset {
+                charset: self.charset,
+            });
+        }
+
+        for &(pack_type, track, _) in &self.texts {
+            if !TEXT_TYPES.contains(&pack_type) || pack_type == CDTextPackType::Genre {
+                return Err(BuildError::NotTextType { pack_type });
+            }
+
+            if track > 99 {
+                return Err(BuildError::InvalidTrackNumber { track });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Internal method. Writes text packs of the block.
+    fn write(
+        &self,
+        writer: &mut PackWriter,
+        block_number: u8,
+        settings: &WriteSettings,
+    ) -> Result<(), BuildError> {
+        let mut encode = |pack_type: CDTextPackType, track: u8, text: &str| {
+            let (bytes, degraded) = self
+                .charset
+                .encode(text, settings.unmappable)
+                .ok_or(BuildError::UnencodableText { pack_type, track })?;
+
+            if degraded {
+                writer.degraded.push(DegradedText {
+                    block_number,
+                    pack_type,
+                    track,
+                });
+            }
+
+            Ok(bytes)
+        };
+
+        let width = if self.charset.is_double_byte() { 2 } else { 1 };
+        let mut streams: Vec<(CDTextPackType, Vec<Vec<u8>>)> = Vec::new();
+
+        for pack_type in TEXT_TYPES {
+            // Genre text is preceded by the 2-byte genre code and describes the whole album only.
+            let strings = if pack_type == CDTextPackType::Genre {
+                let Some(genre) = &self.genre else {
+                    continue;
+                };
+
+                let mut string = u16::from(genre.code).to_be_bytes().to_vec();
+                string.extend(encode(
+                    pack_type,
+                    0,
+                    genre.text.as_deref().unwrap_or_default(),
+                )?);
+
+                Vec::from([string])
+            } else {
+                if !self.texts.iter().any(|&(t, _, _)| t == pack_type) {
+                    continue;
+                }
+
+                let mut strings = (0..=settings.last_track)
+                    .map(|track| {
+                        let text = self
+                            .texts
+                            .iter()
+                            .find(|(t, n, _)| *t == pack_type && *n == track)
+                            .map_or("", |(_, _, text)| text.as_str());
+
+                        encode(pack_type, track, text)
+                    })
+                    .collect::<Result<Vec<Vec<u8>>, BuildError>>()?;
+
+                // A single TAB means the string is the same as for the previous track.
+                if settings.compress_tabs {
+                    let tab = Vec::from(&[0x09; 2][..width]);
+
+                    // Track 1 follows the album, not a track, so it's always written in full.
+                    for index in (2..strings.len()).rev() {
+                        if strings[index].len() > width && strings[index] == strings[index - 1] {
+                            strings[index] = tab.clone();
+                        }
+                    }
+                }
+
+                strings
+            };
+
+            streams.push((pack_type, strings));
+        }
+
+        for (pack_type, strings) in streams {
+            writer.write_text(pack_type, block_number, width, &strings);
+        }
+
+        Ok(())
+    }
+}
+
+/// Builds CD-Text binary data (a stream of 18-byte packs) from texts of the album and its tracks.
+///
+/// Texts can be given in up to 8 languages, each one written to a separate block.
+/// Block Size Info is generated automatically from the content.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CDTextBuilder {
+    /// Blocks in the order they are written. There's always at least one.
+    blocks: Vec<BlockBuilder>,
+    compress_tabs: bool,
+    unmappable: Unmappable,
+    header: bool,
+}
+
+impl Default for CDTextBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CDTextBuilder {
+    /// Creates a builder without any text. The language of the first block is English.
+    pub fn new() -> Self {
+        Self {
+            blocks: Vec::from([BlockBuilder::new(Language::English)]),
+            compress_tabs: false,
+            unmappable: Unmappable::Error,
+            header: false,
+        }
+    }
+
+    /// Creates a builder from the blocks, written in the given order.
+    /// Without any blocks, it's the same as [`CDTextBuilder::new`].
+    pub fn from_blocks(blocks: impl IntoIterator<Item = BlockBuilder>) -> Self {
+        let blocks: Vec<BlockBuilder> = blocks.into_iter().collect();
+
+        if blocks.is_empty() {
+            return Self::new();
+        }
+
+        Self {
+            blocks,
+            ..Self::new()
+        }
+    }
+
+    /// Creates a builder from the parsed entries, so the text can be edited and written again.
+    /// Each block becomes a [`BlockBuilder`], see [`BlockBuilder::from_entries`].
+    /// Blocks are renumbered to be consecutive if some of them are missing.
+    pub fn from_entries(entries: &[CDTextEntry]) -> Self {
+        let mut block_numbers: Vec<u8> = entries.iter().map(|e| e.block_number).collect();
+        block_numbers.sort_unstable();
+        block_numbers.dedup();
+
+        Self::from_blocks(
+            block_numbers
+                .into_iter()
+                .map(|block_number| BlockBuilder::from_entries(entries, block_number)),
+        )
+    }
+
+    /// Creates a builder with a single English block from the disc model,
+    /// see [`BlockBuilder::from_disc`].
+    pub fn from_disc(disc: &Disc) -> Self {
+        Self::from_blocks([BlockBuilder::from_disc(disc, Language::English)])
+    }
+
+    /// Sets the charset of the first block, see [`BlockBuilder::with_charset`].
+    pub fn with_charset(mut self, charset: Charset) -> Self {
+        self.blocks[0].charset = charset;
+        self
+    }
+
+    /// Sets the language of the first block.
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.blocks[0].language = language;
+        self
+    }
+
+    /// Sets the text of the type for the track in the first block, replacing the one set before.
+    /// Track 0 is the whole album.
+    pub fn with_text(
+        mut self,
+        pack_type: CDTextPackType,
+        track: u8,
+        text: impl Into<String>,
+    ) -> Self {
+        self.blocks[0].set_text(pack_type, track, text);
+        self
+    }
+
+    /// Sets the genre of the album in the first block.
+    pub fn with_genre(mut self, genre: Genre) -> Self {
+        self.blocks[0].genre = Some(genre);
+        self
+    }
+
+    /// Adds a block with texts in another language.
+    pub fn with_block(mut self, block: BlockBuilder) -> Self {
+        self.blocks.push(block);
+        self
+    }
+
+    /// Combines the blocks of the other builder with these ones, e.g. to take the English block
+    /// from one dump and the Japanese block from another.
+    ///
+    /// Blocks in a language that is already present are merged into the existing block,
+    /// which takes precedence, see [`BlockBuilder::merge`]. Blocks in other languages are added
+    /// after the existing ones. Block numbers and Block Size Info follow from the resulting order.
+    pub fn merge(mut self, other: CDTextBuilder) -> Self {
+        for block in other.blocks {
+            match self
+                .blocks
+                .iter_mut()
+                .find(|b| b.language == block.language)
+            {
+                Some(existing) => existing.merge(&block),
+                None => self.blocks.push(block),
+            }
+        }
+
+        self
+    }
+
+    /// Returns the blocks in the order they are written.
+    pub fn blocks(&self) -> &[BlockBuilder] {
+        &self.blocks
+    }
+
+    /// Returns the blocks for editing in place.
+    pub fn blocks_mut(&mut self) -> &mut [BlockBuilder] {
+        &mut self.blocks
+    }
+
+    /// Sets whether a string that is the same as for the previous track is replaced with
+    /// a single TAB (0x09) character, saving space in the lead-in. Disabled by default.
+    ///
+    /// The string of track 1 is never replaced, even if it's the same as the one of the album.
+    pub fn with_tab_compression(mut self, compress_tabs: bool) -> Self {
+        self.compress_tabs = compress_tabs;
+        self
+    }
+
+    /// Sets what to do with characters that cannot be encoded in the charset of their block.
+    pub fn with_unmappable(mut self, unmappable: Unmappable) -> Self {
+        self.unmappable = unmappable;
+        self
+    }
+
+    /// Sets whether the packs are preceded by the 4-byte header, as returned by READ TOC/PMA/ATIP
+    /// command: 2-byte length of the rest of the data followed by 2 reserved bytes.
+    /// Some tools expect it, e.g. cdrecord `textfile=` option. Disabled by default.
+    pub fn with_header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Builds the packs.
+    ///
+    /// Each text type present in a block is written as one stream of strings, for the album
+    /// and every track from 1 to the last one that has any text. Missing strings are written empty.
+    /// Each block is followed by three Block Size Info packs describing it.
+    pub fn build(&self) -> Result<Vec<u8>, BuildError> {
+        Ok(self.build_with_report()?.0)
+    }
+
+    /// Builds the packs like [`CDTextBuilder::build`].
+    /// Also returns the texts that have been degraded according to [`Unmappable`] policy.
+    pub fn build_with_report(&self) -> Result<(Vec<u8>, Vec<DegradedText>), BuildError> {
+        let output = self.write(self.compress_tabs)?;
+
+        let overflow = output
+            .estimate
+            .block_packs
+            .iter()
+            .enumerate()
+            .find(|&(_, &packs)| packs > MAX_BLOCK_PACKS);
+
+        if let Some((block_number, &packs)) = overflow {
+            let hint = if !self.compress_tabs && self.write(true)?.estimate.fits() {
+                CapacityHint::EnableTabCompression
+            } else {
+                CapacityHint::ShortenText
+            };
+
+            return Err(BuildError::CapacityExceeded {
+                block_number: block_number as u8,
+                packs,
+                limit: MAX_BLOCK_PACKS,
+                hint,
+            });
+        }
+
+        if !self.header {
+            return Ok((output.data, output.degraded));
+        }
+
+        // The length covers the reserved bytes too. 8 blocks of 256 packs fit in it.
+        let length = (output.data.len() + 2) as u16;
+
+        let mut data = Vec::with_capacity(output.data.len() + 4);
+        data.extend_from_slice(&length.to_be_bytes());
+        data.extend_from_slice(&[0, 0]);
+        data.extend(output.data);
+
+        Ok((data, output.degraded))
+    }
+
+    /// Returns how much space the content will take, without checking the limits.
+    pub fn estimate(&self) -> Result<SizeEstimate, BuildError> {
+        Ok(self.write(self.compress_tabs)?.estimate)
+    }
+
+    /// Internal method. Writes the packs of all the blocks.
+    fn write(&self, compress_tabs: bool) -> Result<Output, BuildError> {
+        if self.blocks.len() > 8 {
+            return Err(BuildError::TooManyBlocks {
+                count: self.blocks.len(),
+            });
+        }
+
+        for block in &self.blocks {
+            block.check()?;
+        }
+
+        // All blocks describe the same tracks. A disc has at least one track.
+        let last_track = self
+            .blocks
+            .iter()
+            .map(BlockBuilder::last_track)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let settings = WriteSettings {
+            last_track,
+            compress_tabs,
+            unmappable: self.unmappable,
+        };
+
+        let mut writer = PackWriter::default();
+        let mut estimate = SizeEstimate::default();
+
+        for (block_number, block) in self.blocks.iter().enumerate() {
+            let block_number = block_number as u8;
+            let start = writer.data.len();
+
+            block.write(&mut writer, block_number, &settings)?;
+
+            writer.write_size_info(block_number, |size_info| {
+                size_info.character_code = u8::from(block.charset);
+                size_info.first_track = 1;
+                size_info.last_track = last_track;
+            });
+
+            estimate.block_packs.push((writer.data.len() - start) / 18);
+        }
+
+        let mut language_codes = [0u8; 8];
+
+        for (code, block) in language_codes.iter_mut().zip(&self.blocks) {
+            *code = u8::from(block.language);
+        }
+
+        let degraded = core::mem::take(&mut writer.degraded);
+
+        Ok(Output {
+            data: writer.finish(language_codes),
+            estimate,
+            degraded,
+        })
+    }
+}
+
+impl CDText<'_> {
+    /// Parses the data into a builder, so the text can be edited and written again,
+    /// see [`CDTextBuilder::from_entries`].
+    pub fn to_builder(&self) -> Result<CDTextBuilder, CDTextError> {
+        Ok(CDTextBuilder::from_entries(&self.parse()?))
+    }
+}
+
+/// Internal structure. Settings shared by all the blocks.
+struct WriteSettings {
+    last_track: u8,
+    compress_tabs: bool,
+    unmappable: Unmappable,
+}
+
+/// Internal structure. Result of writing all the blocks.
+struct Output {
+    data: Vec<u8>,
+    estimate: SizeEstimate,
+    degraded: Vec<DegradedText>,
+}
+
+/// Internal structure. Writes packs, numbering them sequentially.
+#[derive(Default)]
+struct PackWriter {
+    data: Vec<u8>,
+    sequence: u8,
+    /// Number of packs of each type written to the current block.
+    pack_counts: [u8; 16],
+    last_sequence_numbers: [u8; 8],
+    /// Size Info of the blocks written so far with offsets of their packs,
+    /// to be completed once all the blocks are written.
+    size_infos: Vec<(usize, BlockSizeInfo)>,
+    degraded: Vec<DegradedText>,
+}
+
+impl PackWriter {
+    /// Writes a pack with the header fields, computing its CRC.
+    fn write_pack(
+        &mut self,
+        pack_type: CDTextPackType,
+        track: u8,
+        block_number: u8,
+        double_byte: bool,
+        character_position: u8,
+        payload: &[u8; 12],
+    ) {
+        let mut pack = [0u8; 18];
+
+        pack[0] = u8::from(pack_type);
+        pack[1] = track;
+        pack[2] = self.sequence;
+        pack[3] =
+            (double_byte as u8) << 7 | (block_number & 0x07) << 4 | character_position.min(15);
+        pack[4..16].copy_from_slice(payload);
+
+        crc::finalize_pack(&mut pack);
+
+        self.data.extend_from_slice(&pack);
+        self.sequence = self.sequence.wrapping_add(1);
+
+        if let n @ 0x80..=0x8f = u8::from(pack_type) {
+            let count = &mut self.pack_counts[(n - 0x80) as usize];
+            *count = count.saturating_add(1);
+        }
+    }
+
+    /// Finishes the block with three Block Size Info packs, counting the packs written to it.
+    /// The rest of the fields are filled by the closure, except for the tables describing
+    /// all the blocks, which are filled by [`PackWriter::finish`].
+    fn write_size_info(&mut self, block_number: u8, fill: impl FnOnce(&mut BlockSizeInfo)) {
+        let mut pack_counts = self.pack_counts;
+        pack_counts[0x0f] = 3;
+
+        // The third Size Info pack is the last one of the block.
+        self.last_sequence_numbers[block_number as usize] = self.sequence.wrapping_add(2);
+
+        let mut size_info = BlockSizeInfo {
+            character_code: 0,
+            first_track: 0,
+            last_track: 0,
+            copyright_flags: 0,
+            pack_counts,
+            last_sequence_numbers: [0; 8],
+            language_codes: [0; 8],
+        };
+
+        fill(&mut size_info);
+
+        let data = size_info.to_bytes();
+        self.size_infos.push((self.data.len(), size_info));
+
+        // Track number field holds the index of the Size Info pack instead.
+        for (index, payload) in data.as_chunks::<12>().0.iter().enumerate() {
+            self.write_pack(
+                CDTextPackType::BlockSizeInfo,
+                index as u8,
+                block_number,
+                false,
+                0,
+                payload,
+            );
+        }
+
+        self.pack_counts = [0; 16];
+    }
+
+    /// Fills the tables describing all the blocks into the Size Info packs and returns the data.
+    fn finish(mut self, language_codes: [u8; 8]) -> Vec<u8> {
+        for (offset, mut size_info) in core::mem::take(&mut self.size_infos) {
+            size_info.last_sequence_numbers = self.last_sequence_numbers;
+            size_info.language_codes = language_codes;
+
+            let data = size_info.to_bytes();
+
+            for (pack, payload) in self.data[offset..]
+                .as_chunks_mut::<18>()
+                .0
+                .iter_mut()
+                .zip(data.as_chunks::<12>().0)
+            {
+                pack[4..16].copy_from_slice(payload);
+                crc::finalize_pack(pack);
+            }
+        }
+
+        self.data
+    }
+
+    /// Writes NUL-terminated strings of consecutive tracks starting with the album,
+    /// splitting them over as many packs as needed.
+    ///
+    /// Each pack is marked with the track of its first character and the number of characters
+    /// of that string stored in the preceding packs. Characters and terminators of double-byte
+    /// text take two bytes; since strings are of even length then, no character is split between packs.
+    fn write_text(
+        &mut self,
+        pack_type: CDTextPackType,
+        block_number: u8,
+        character_width: usize,
+        strings: &[Vec<u8>],
+    ) {
+        let double_byte = character_width == 2;
+        let mut payload = [0u8; 12];
+        let mut length = 0;
+        let mut header = (0, 0);
+
+        for (track, string) in strings.iter().enumerate() {
+            let terminator = &[0; 2][..character_width];
+
+            for (offset, &byte) in string.iter().chain(terminator).enumerate() {
+                if length == 0 {
+                    header = (track as u8, (offset / character_width).min(15) as u8);
+                }
+
+                payload[length] = byte;
+                length += 1;
+
+                if length == payload.len() {
+                    self.write_pack(
+                        pack_type,
+                        header.0,
+                        block_number,
+                        double_byte,
+                        header.1,
+                        &payload,
+                    );
+                    length = 0;
+                }
+            }
+        }
+
+        // The rest of the last pack is padded with NULs.
+        if length > 0 {
+            payload[length..].fill(0);
+            self.write_pack(
+                pack_type,
+                header.0,
+                block_number,
+                double_byte,
+                header.1,
+                &payload,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GenreCode;
+
+    #[test]
+    fn round_trip() {
+        let builder = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Performers, 0, "Band")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .with_text(CDTextPackType::Title, 2, "Second")
+            .with_text(CDTextPackType::Performers, 1, "Band")
+            .with_text(CDTextPackType::Performers, 2, "Guest")
+            .with_genre(Genre {
+                code: GenreCode::Jazz,
+                text: Some(String::from("Bebop")),
+            })
+            .with_block(BlockBuilder::new(Language::German).with_text(
+                CDTextPackType::Title,
+                0,
+                "Titel",
+            ));
+        let data = builder.build().unwrap();
+        let cdtext = CDText::from_data(&data);
+
+        let entries = cdtext.parse().unwrap();
+        let text = |block_number, pack_type, track| {
+            entries
+                .iter()
+                .find(|entry| {
+                    entry.block_number == block_number
+                        && entry.entry_type == pack_type
+                        && u8::from(entry.track_number) == track
+                })
+                .map(|entry| &entry.data)
+        };
+
+        let string = |text: &str| Some(CDTextEntryDataType::String(String::from(text)));
+        assert_eq!(text(0, CDTextPackType::Title, 0).cloned(), string("Album"));
+        assert_eq!(text(0, CDTextPackType::Title, 2).cloned(), string("Second"));
+        assert_eq!(
+            text(0, CDTextPackType::Performers, 1).cloned(),
+            string("Band")
+        );
+        assert_eq!(
+            text(0, CDTextPackType::Performers, 2).cloned(),
+            string("Guest")
+        );
+        assert_eq!(text(1, CDTextPackType::Title, 0).cloned(), string("Titel"));
+        assert_eq!(
+            text(0, CDTextPackType::Genre, 0).cloned(),
+            Some(CDTextEntryDataType::Genre(Genre {
+                code: GenreCode::Jazz,
+                text: Some(String::from("Bebop")),
+            }))
+        );
+
+        assert!(cdtext.validate().is_valid());
+        assert_eq!(cdtext.to_builder().unwrap().build().unwrap(), data);
+    }
+
+    #[test]
+    fn tab_compression_starts_at_track_2() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Same")
+            .with_text(CDTextPackType::Title, 1, "Same")
+            .with_text(CDTextPackType::Title, 2, "Same")
+            .with_tab_compression(true)
+            .build()
+            .unwrap();
+
+        assert!(data.starts_with(b"\x80\x00\x00\x00Same\x00Same\x00\x09\x00"));
+    }
+
+    #[test]
+    fn long_text_round_trip() {
+        let title = "A title that takes more than three packs to store";
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, title)
+            .with_text(CDTextPackType::Title, 1, "Track")
+            .build()
+            .unwrap();
+        let cdtext = CDText::from_data(&data);
+
+        let positions: Vec<u8> = cdtext
+            .iter_pack_chunks()
+            .filter(|pack| pack.pack_type == CDTextPackType::Title)
+            .map(|pack| pack.character_position)
+            .collect();
+        assert_eq!(positions, [0, 12, 15, 15, 15]);
+
+        let entries = cdtext.parse().unwrap();
+        assert_eq!(
+            entries[0].data,
+            CDTextEntryDataType::String(String::from(title))
+        );
+        assert_eq!(
+            entries[1].data,
+            CDTextEntryDataType::String(String::from("Track"))
+        );
+
+        for pack in cdtext.iter_pack_chunks() {
+            assert!(pack.verify_crc());
+        }
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn double_byte() {
+        let builder = CDTextBuilder::from_blocks([BlockBuilder::new(Language::Japanese)
+            .with_charset(Charset::MsJis)
+            .with_text(CDTextPackType::Title, 0, "\u{65e5}\u{672c}\u{8a9e}")
+            .with_text(CDTextPackType::Title, 1, "A 1")]);
+        let data = builder.build().unwrap();
+
+        // ASCII is written in its full-width form, and each string ends with two NULs.
+        assert!(
+            data.starts_with(b"\x80\x00\x00\x80\x93\xfa\x96\x7b\x8c\xea\x00\x00\x82\x60\x81\x40")
+        );
+
+        let cdtext = CDText::from_data(&data);
+        assert_eq!(cdtext.block_size_info(0).unwrap().charset(), Charset::MsJis);
+        assert!(
+            cdtext
+                .iter_pack_chunks()
+                .filter(|pack| pack.pack_type == CDTextPackType::Title)
+                .all(|pack| pack.is_double_byte_characters)
+        );
+
+        let entries = cdtext.parse().unwrap();
+        let string = |text: &str| CDTextEntryDataType::String(String::from(text));
+        assert_eq!(entries[0].data, string("\u{65e5}\u{672c}\u{8a9e}"));
+        assert_eq!(entries[1].data, string("\u{ff21}\u{3000}\u{ff11}"));
+
+        let unencodable = CDTextBuilder::from_blocks([BlockBuilder::new(Language::Japanese)
+            .with_charset(Charset::MsJis)
+            .with_text(CDTextPackType::Title, 0, "\u{e9}")]);
+        assert_eq!(
+            unencodable.build(),
+            Err(BuildError::UnencodableText {
+                pack_type: CDTextPackType::Title,
+                track: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn merge() {
+        let first = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "First");
+        let second = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Other album")
+            .with_text(CDTextPackType::Performers, 0, "Band")
+            .with_genre(Genre {
+                code: GenreCode::Jazz,
+                text: None,
+            })
+            .with_block(BlockBuilder::new(Language::Japanese).with_text(
+                CDTextPackType::Title,
+                0,
+                "Arubamu",
+            ));
+
+        let merged = first.merge(second);
+        let blocks = merged.blocks();
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].text(CDTextPackType::Title, 0), Some("Album"));
+        assert_eq!(blocks[0].text(CDTextPackType::Title, 1), Some("First"));
+        assert_eq!(blocks[0].text(CDTextPackType::Performers, 0), Some("Band"));
+        assert_eq!(
+            blocks[0].genre().map(|genre| genre.code),
+            Some(GenreCode::Jazz)
+        );
+        assert_eq!(blocks[1].language(), Language::Japanese);
+        assert_eq!(blocks[1].text(CDTextPackType::Title, 0), Some("Arubamu"));
+
+        let data = merged.build().unwrap();
+        assert_eq!(
+            CDText::from_data(&data)
+                .block_size_info(1)
+                .and_then(|size_info| size_info.language(1)),
+            Some(Language::Japanese)
+        );
+    }
+}
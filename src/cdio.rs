@@ -0,0 +1,200 @@
+use alloc::string::String;
+
+use crate::{CDText, CDTextEntry, CDTextEntryDataType, CDTextPackType, CDTextTrackNumber};
+
+/// A CD-Text field under libcdio's `cdtext_field_t` naming and numbering,
+/// for code ported from libcdio.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CdioField {
+    Title,
+    Performer,
+    Songwriter,
+    Composer,
+    Message,
+    Arranger,
+    Isrc,
+    UpcEan,
+    Genre,
+    DiscId,
+}
+
+impl CdioField {
+    /// All the fields, in libcdio's order.
+    pub const ALL: [Self; 10] = [
+        Self::Title,
+        Self::Performer,
+        Self::Songwriter,
+        Self::Composer,
+        Self::Message,
+        Self::Arranger,
+        Self::Isrc,
+        Self::UpcEan,
+        Self::Genre,
+        Self::DiscId,
+    ];
+
+    /// Returns the name of the field, as returned by libcdio's `cdtext_field2str`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Title => "TITLE",
+            Self::Performer => "PERFORMER",
+            Self::Songwriter => "SONGWRITER",
+            Self::Composer => "COMPOSER",
+            Self::Message => "MESSAGE",
+            Self::Arranger => "ARRANGER",
+            Self::Isrc => "ISRC",
+            Self::UpcEan => "UPC_EAN",
+            Self::Genre => "GENRE",
+            Self::DiscId => "DISC_ID",
+        }
+    }
+
+    /// Returns the pack type the field is stored in.
+    pub fn pack_type(&self) -> CDTextPackType {
+        match self {
+            Self::Title => CDTextPackType::Title,
+            Self::Performer => CDTextPackType::Performers,
+            Self::Songwriter => CDTextPackType::Songwriters,
+            Self::Composer => CDTextPackType::Composers,
+            Self::Message => CDTextPackType::Message,
+            Self::Arranger => CDTextPackType::Arrangers,
+            Self::Isrc | Self::UpcEan => CDTextPackType::Code,
+            Self::Genre => CDTextPackType::Genre,
+            Self::DiscId => CDTextPackType::DiscID,
+        }
+    }
+}
+
+impl From<CdioField> for u8 {
+    /// Returns the value of the field in `cdtext_field_t`.
+    fn from(value: CdioField) -> Self {
+        value as u8
+    }
+}
+
+impl TryFrom<u8> for CdioField {
+    type Error = u8;
+
+    /// Converts a value of `cdtext_field_t`. `CDTEXT_FIELD_INVALID` and other unknown values
+    /// are returned back as the error.
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::ALL.get(usize::from(value)).copied().ok_or(value)
+    }
+}
+
+impl CDTextEntry {
+    /// Returns the libcdio field of the entry, or `None` if libcdio has no such field,
+    /// e.g. for TOC or Block Size Info. Code packs are UPC/EAN for the album and ISRC for tracks.
+    pub fn cdio_field(&self) -> Option<CdioField> {
+        Some(match self.entry_type {
+            CDTextPackType::Title => CdioField::Title,
+            CDTextPackType::Performers => CdioField::Performer,
+            CDTextPackType::Songwriters => CdioField::Songwriter,
+            CDTextPackType::Composers => CdioField::Composer,
+            CDTextPackType::Message => CdioField::Message,
+            CDTextPackType::Arrangers => CdioField::Arranger,
+            CDTextPackType::Code => match self.track_number {
+                CDTextTrackNumber::WholeAlbum => CdioField::UpcEan,
+                CDTextTrackNumber::Track(_) => CdioField::Isrc,
+            },
+            CDTextPackType::Genre => CdioField::Genre,
+            CDTextPackType::DiscID => CdioField::DiscId,
+            _ => return None,
+        })
+    }
+}
+
+impl CDText<'_> {
+    /// Returns the field of the track as text, like libcdio's `cdtext_get_const`.
+    /// Track 0 is the whole album.
+    ///
+    /// As in libcdio, the first block is used, codes are returned as written, and the genre
+    /// is its supplementary text. Returns `None` if the field is not set or the data
+    /// cannot be parsed.
+    pub fn cdio_get(&self, field: CdioField, track: u8) -> Option<String> {
+        let entries = self.parse().ok()?;
+        let block_number = entries.iter().map(|entry| entry.block_number).min()?;
+        let track_number = CDTextTrackNumber::from(track);
+
+        entries
+            .into_iter()
+            .filter(|entry| {
+                entry.block_number == block_number
+                    && entry.track_number == track_number
+                    && entry.cdio_field() == Some(field)
+            })
+            .find_map(|entry| match entry.data {
+                CDTextEntryDataType::String(text) => Some(text),
+                CDTextEntryDataType::Upc(upc) => Some(String::from(upc.as_str())),
+                CDTextEntryDataType::Isrc(isrc) => Some(String::from(isrc.as_str())),
+                CDTextEntryDataType::Genre(genre) => genre.text,
+                _ => None,
+            })
+            .filter(|text| !text.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CDTextBuilder, Genre, GenreCode};
+
+    #[test]
+    fn field_values() {
+        for (value, field) in CdioField::ALL.into_iter().enumerate() {
+            assert_eq!(usize::from(u8::from(field)), value);
+            assert_eq!(CdioField::try_from(value as u8), Ok(field));
+        }
+
+        assert_eq!(u8::from(CdioField::UpcEan), 7);
+        assert_eq!(CdioField::DiscId.as_str(), "DISC_ID");
+        assert_eq!(CdioField::try_from(10), Err(10));
+    }
+
+    #[test]
+    fn get() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Code, 0, "036000291452")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .with_text(CDTextPackType::Code, 1, "USRC17607839")
+            .with_genre(Genre {
+                code: GenreCode::Jazz,
+                text: Some(String::from("Bebop")),
+            })
+            .build()
+            .unwrap();
+        let cdtext = CDText::from_data(&data);
+
+        assert_eq!(
+            cdtext.cdio_get(CdioField::Title, 0).as_deref(),
+            Some("Album")
+        );
+        assert_eq!(
+            cdtext.cdio_get(CdioField::Title, 1).as_deref(),
+            Some("First")
+        );
+        assert_eq!(
+            cdtext.cdio_get(CdioField::UpcEan, 0).as_deref(),
+            Some("036000291452")
+        );
+        assert_eq!(
+            cdtext.cdio_get(CdioField::Isrc, 1).as_deref(),
+            Some("USRC17607839")
+        );
+        assert_eq!(
+            cdtext.cdio_get(CdioField::Genre, 0).as_deref(),
+            Some("Bebop")
+        );
+        assert_eq!(cdtext.cdio_get(CdioField::Isrc, 0), None);
+        assert_eq!(cdtext.cdio_get(CdioField::Performer, 1), None);
+
+        let entries = cdtext.parse().unwrap();
+        let size_info = entries
+            .iter()
+            .find(|entry| entry.entry_type == CDTextPackType::BlockSizeInfo)
+            .unwrap();
+        assert_eq!(size_info.cdio_field(), None);
+    }
+}
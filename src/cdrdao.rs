@@ -0,0 +1,688 @@
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+    BlockBuilder, BlockSizeInfo, CDText, CDTextBuilder, CDTextEntry, CDTextError, CDTextPackType,
+    Disc, Genre, GenreCode, Language, TocFileError, TrackInfo,
+};
+
+/// CD-Text sections of a cdrdao toc-file.
+///
+/// The disc section belongs to the header of the toc-file, before the first `TRACK`,
+/// and each track section belongs to the definition of its track.
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CdrdaoText {
+    pub disc: String,
+    /// Sections of the tracks, sorted by number.
+    pub tracks: Vec<(u8, String)>,
+}
+
+/// Internal function. Quotes the string, escaping Latin-1 characters the way cdrdao reads them.
+fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for character in text.chars() {
+        match character {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(character);
+            }
+            ' '..='~' => quoted.push(character),
+            '\u{0}'..='\u{ff}' => {
+                let _ = write!(quoted, "\\{:03o}", character as u32);
+            }
+            _ => quoted.push(character),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+/// Internal function. Returns the text fields of a track, by cdrdao keywords.
+fn track_fields(track: &TrackInfo) -> [(&'static str, Option<&str>); 6] {
+    [
+        ("TITLE", track.title.as_deref()),
+        ("PERFORMER", track.performer.as_deref()),
+        ("SONGWRITER", track.songwriter.as_deref()),
+        ("COMPOSER", track.composer.as_deref()),
+        ("ARRANGER", track.arranger.as_deref()),
+        ("MESSAGE", track.message.as_deref()),
+    ]
+}
+
+/// Internal function. Returns the text fields of the album, by cdrdao keywords.
+fn disc_fields(disc: &Disc) -> [(&'static str, Option<&str>); 6] {
+    [
+        ("TITLE", disc.title.as_deref()),
+        ("PERFORMER", disc.performer.as_deref()),
+        ("SONGWRITER", disc.songwriter.as_deref()),
+        ("COMPOSER", disc.composer.as_deref()),
+        ("ARRANGER", disc.arranger.as_deref()),
+        ("MESSAGE", disc.message.as_deref()),
+    ]
+}
+
+impl CdrdaoText {
+    /// Creates the sections from the parsed entries, one `LANGUAGE` block per CD-Text block.
+    ///
+    /// cdrdao requires a text item that is present for some tracks to be present for the album
+    /// and all the tracks, so missing ones are written empty.
+    pub fn from_entries(entries: &[CDTextEntry]) -> Self {
+        let mut block_numbers: Vec<u8> = entries.iter().map(|e| e.block_number).collect();
+        block_numbers.sort_unstable();
+        block_numbers.dedup();
+
+        let blocks: Vec<(u8, Disc)> = block_numbers
+            .iter()
+            .map(|&block_number| (block_number, Disc::from_block(entries, block_number)))
+            .collect();
+
+        let mut track_numbers: Vec<u8> = blocks
+            .iter()
+            .flat_map(|(_, disc)| disc.tracks.iter().map(|track| track.number))
+            .collect();
+        track_numbers.sort_unstable();
+        track_numbers.dedup();
+
+        let mut text = Self::default();
+
+        text.disc.push_str("CD_TEXT {\n  LANGUAGE_MAP {\n");
+
+        for &block_number in &block_numbers {
+            let language = BlockSizeInfo::from_entries(entries, block_number)
+                .and_then(|size_info| size_info.language(block_number));
+
+            let language = match language.unwrap_or(Language::English) {
+                Language::English => String::from("EN"),
+                language => format!("{}", u8::from(language)),
+            };
+
+            let _ = writeln!(text.disc, "    {block_number} : {language}");
+        }
+
+        text.disc.push_str("  }\n");
+
+        for &number in &track_numbers {
+            text.tracks.push((number, String::from("CD_TEXT {\n")));
+        }
+
+        for (block_number, disc) in &blocks {
+            let present = |index: usize| {
+                disc_fields(disc)[index].1.is_some()
+                    || disc
+                        .tracks
+                        .iter()
+                        .any(|track| track_fields(track)[index].1.is_some())
+            };
+
+            let _ = writeln!(text.disc, "  LANGUAGE {block_number} {{");
+
+            for (index, (keyword, value)) in disc_fields(disc).into_iter().enumerate() {
+                if present(index) {
+                    let _ = writeln!(text.disc, "    {keyword} {}", quote(value.unwrap_or("")));
+                }
+            }
+
+            if let Some(disc_id) = &disc.disc_id {
+                let _ = writeln!(text.disc, "    DISC_ID {}", quote(disc_id));
+            }
+
+            if let Some(upc) = &disc.upc {
+                let _ = writeln!(text.disc, "    UPC_EAN {}", quote(upc.as_str()));
+            }
+
+            // Genre is binary: 2-byte code followed by NUL-terminated supplementary text.
+            if let Some(genre) = &disc.genre {
+                let mut bytes = u16::from(genre.code).to_be_bytes().to_vec();
+                bytes.extend(
+                    genre
+                        .text
+                        .as_deref()
+                        .unwrap_or_default()
+                        .chars()
+                        .map(|c| u8::try_from(c).unwrap_or(b'?')),
+                );
+                bytes.push(0);
+
+                let bytes: Vec<String> = bytes.iter().map(|byte| format!("{byte}")).collect();
+                let _ = writeln!(text.disc, "    GENRE {{ {} }}", bytes.join(", "));
+            }
+
+            text.disc.push_str("  }\n");
+
+            for (number, section) in &mut text.tracks {
+                let track = disc.track(*number);
+                let _ = writeln!(section, "  LANGUAGE {block_number} {{");
+
+                for index in 0..6 {
+                    if present(index) {
+                        let (keyword, value) = match track {
+                            Some(track) => track_fields(track)[index],
+                            None => (disc_fields(disc)[index].0, None),
+                        };
+
+                        let _ = writeln!(section, "    {keyword} {}", quote(value.unwrap_or("")));
+                    }
+                }
+
+                if let Some(isrc) = track.and_then(|track| track.isrc) {
+                    let _ = writeln!(section, "    ISRC {}", quote(isrc.as_str()));
+                }
+
+                section.push_str("  }\n");
+            }
+        }
+
+        text.disc.push_str("}\n");
+
+        for (_, section) in &mut text.tracks {
+            section.push_str("}\n");
+        }
+
+        text
+    }
+}
+
+impl CDText<'_> {
+    /// Parses the data into CD-Text sections of a cdrdao toc-file, see [`CdrdaoText::from_entries`].
+    pub fn to_cdrdao(&self) -> Result<CdrdaoText, CDTextError> {
+        Ok(CdrdaoText::from_entries(&self.parse()?))
+    }
+}
+
+/// Internal structure. Token of a toc-file.
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Word(&'a str),
+    String(String),
+    Open,
+    Close,
+    Colon,
+    Comma,
+}
+
+/// Internal function. Splits the toc-file into tokens along with their line numbers,
+/// leaving out `//` comments. Escape sequences of strings are decoded, see [`quote`].
+fn tokenize(toc: &str) -> Result<Vec<(usize, Token<'_>)>, TocFileError> {
+    let mut tokens = Vec::new();
+
+    for (index, text) in toc.lines().enumerate() {
+        let line = index + 1;
+        let mut characters = text.char_indices().peekable();
+
+        while let Some((start, character)) = characters.next() {
+            let token = match character {
+                '/' if text[start..].starts_with("//") => break,
+                '{' => Token::Open,
+                '}' => Token::Close,
+                ':' => Token::Colon,
+                ',' => Token::Comma,
+                '"' => {
+                    let mut string = String::new();
+
+                    loop {
+                        match characters.next() {
+                            Some((_, '"')) => break,
+                            Some((_, '\\')) => match characters.next() {
+                                Some((_, digit @ '0'..='7')) => {
+                                    let mut code = digit as u32 - '0' as u32;
+
+                                    for _ in 0..2 {
+                                        match characters.peek() {
+                                            Some(&(_, digit @ '0'..='7')) => {
+                                                code = code * 8 + (digit as u32 - '0' as u32);
+                                                characters.next();
+                                            }
+                                            _ => break,
+                                        }
+                                    }
+
+                                    string.push(char::from_u32(code).unwrap_or('?'));
+                                }
+                                Some((_, 'n')) => string.push('\n'),
+                                Some((_, escaped)) => string.push(escaped),
+                                None => return Err(TocFileError::UnterminatedString { line }),
+                            },
+                            Some((_, character)) => string.push(character),
+                            None => return Err(TocFileError::UnterminatedString { line }),
+                        }
+                    }
+
+                    Token::String(string)
+                }
+                _ if character.is_whitespace() => continue,
+                _ => {
+                    let mut end = start + character.len_utf8();
+
+                    while let Some(&(index, next)) = characters.peek() {
+                        if next.is_whitespace() || "{}:,\"".contains(next) {
+                            break;
+                        }
+
+                        end = index + next.len_utf8();
+                        characters.next();
+                    }
+
+                    Token::Word(&text[start..end])
+                }
+            };
+
+            tokens.push((line, token));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Internal structure. State of reading `CD_TEXT` sections of a toc-file.
+#[derive(Default)]
+struct TocFileReader<'a> {
+    tokens: Vec<(usize, Token<'a>)>,
+    position: usize,
+    languages: BTreeMap<u8, Language>,
+    /// Texts (pack type, track, text) of the blocks.
+    texts: BTreeMap<u8, Vec<(CDTextPackType, u8, String)>>,
+    genres: BTreeMap<u8, Genre>,
+}
+
+impl<'a> TocFileReader<'a> {
+    /// Internal method. Returns the next token, failing at the end of the file.
+    fn next(&mut self) -> Result<(usize, &Token<'a>), TocFileError> {
+        let line = self.tokens.last().map_or(1, |(line, _)| *line);
+        let (line, token) = self
+            .tokens
+            .get(self.position)
+            .map(|(line, token)| (*line, token))
+            .ok_or(TocFileError::Syntax { line })?;
+
+        self.position += 1;
+        Ok((line, token))
+    }
+
+    /// Internal method. Skips the token, failing if it's not the expected one.
+    fn expect(&mut self, expected: &Token<'_>) -> Result<(), TocFileError> {
+        match self.next()? {
+            (_, token) if token == expected => Ok(()),
+            (line, _) => Err(TocFileError::Syntax { line }),
+        }
+    }
+
+    /// Internal method. Reads a block number of `LANGUAGE_MAP` or `LANGUAGE`.
+    fn block_number(&mut self) -> Result<u8, TocFileError> {
+        match self.next()? {
+            (line, Token::Word(word)) => match word.parse::<u8>() {
+                Ok(number @ 0..=7) => Ok(number),
+                _ => Err(TocFileError::InvalidBlockNumber { line }),
+            },
+            (line, _) => Err(TocFileError::Syntax { line }),
+        }
+    }
+
+    /// Internal method. Reads a `CD_TEXT` section of the track, after the keyword.
+    /// Track 0 is the disc section.
+    fn cd_text(&mut self, track: u8) -> Result<(), TocFileError> {
+        self.expect(&Token::Open)?;
+
+        loop {
+            match self.next()? {
+                (_, Token::Close) => return Ok(()),
+                (_, Token::Word("LANGUAGE_MAP")) => self.language_map()?,
+                (_, Token::Word("LANGUAGE")) => {
+                    let block_number = self.block_number()?;
+                    self.language(block_number, track)?;
+                }
+                (line, _) => return Err(TocFileError::Syntax { line }),
+            }
+        }
+    }
+
+    /// Internal method. Reads `LANGUAGE_MAP`, after the keyword.
+    fn language_map(&mut self) -> Result<(), TocFileError> {
+        self.expect(&Token::Open)?;
+
+        loop {
+            let position = self.position;
+
+            if let (_, Token::Close) = self.next()? {
+                return Ok(());
+            }
+
+            self.position = position;
+
+            let block_number = self.block_number()?;
+            self.expect(&Token::Colon)?;
+
+            let language = match self.next()? {
+                (_, Token::Word("EN")) => Language::English,
+                (line, Token::Word(code)) => code
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|code| *code <= 0x7f)
+                    .map(Language::from)
+                    .ok_or(TocFileError::UnknownLanguage { line })?,
+                (line, _) => return Err(TocFileError::Syntax { line }),
+            };
+
+            self.languages.insert(block_number, language);
+
+            let position = self.position;
+
+            if !matches!(self.next()?, (_, Token::Comma)) {
+                self.position = position;
+            }
+        }
+    }
+
+    /// Internal method. Reads a value of an item: a string, or binary data in braces.
+    /// Binary data of text items is Latin-1 text terminated by NUL.
+    fn value(&mut self) -> Result<Value, TocFileError> {
+        match self.next()? {
+            (_, Token::String(string)) => Ok(Value::Text(string.clone())),
+            (_, Token::Open) => {
+                let mut bytes = Vec::new();
+
+                loop {
+                    match self.next()? {
+                        (_, Token::Close) => return Ok(Value::Binary(bytes)),
+                        (_, Token::Comma) => {}
+                        (line, Token::Word(word)) => {
+                            bytes.push(word.parse().map_err(|_| TocFileError::Syntax { line })?)
+                        }
+                        (line, _) => return Err(TocFileError::Syntax { line }),
+                    }
+                }
+            }
+            (line, _) => Err(TocFileError::Syntax { line }),
+        }
+    }
+
+    /// Internal method. Reads items of a `LANGUAGE` block, after its number.
+    fn language(&mut self, block_number: u8, track: u8) -> Result<(), TocFileError> {
+        self.expect(&Token::Open)?;
+
+        loop {
+            let keyword = match self.next()? {
+                (_, Token::Close) => return Ok(()),
+                (_, Token::Word(keyword)) => *keyword,
+                (line, _) => return Err(TocFileError::Syntax { line }),
+            };
+
+            let pack_type = match keyword {
+                "TITLE" => Some(CDTextPackType::Title),
+                "PERFORMER" => Some(CDTextPackType::Performers),
+                "SONGWRITER" => Some(CDTextPackType::Songwriters),
+                "COMPOSER" => Some(CDTextPackType::Composers),
+                "ARRANGER" => Some(CDTextPackType::Arrangers),
+                "MESSAGE" => Some(CDTextPackType::Message),
+                "DISC_ID" => Some(CDTextPackType::DiscID),
+                "UPC_EAN" | "ISRC" => Some(CDTextPackType::Code),
+                _ => None,
+            };
+
+            let value = self.value()?;
+
+            if let Some(pack_type) = pack_type {
+                let text = value.text();
+
+                if !text.is_empty() {
+                    self.texts
+                        .entry(block_number)
+                        .or_default()
+                        .push((pack_type, track, text));
+                }
+            } else if keyword == "GENRE" {
+                let genre = match value {
+                    Value::Binary(bytes) if bytes.len() >= 2 => Genre {
+                        code: GenreCode::from(u16::from_be_bytes([bytes[0], bytes[1]])),
+                        text: Some(Value::Binary(bytes[2..].to_vec()).text())
+                            .filter(|text| !text.is_empty()),
+                    },
+                    value => Genre {
+                        code: GenreCode::NotDefined,
+                        text: Some(value.text()).filter(|text| !text.is_empty()),
+                    },
+                };
+
+                self.genres.insert(block_number, genre);
+            }
+
+            // Other items (TOC_INFO1, SIZE_INFO, ...) are derived from the content by the builder,
+            // but the block is kept even if it has no text.
+            self.texts.entry(block_number).or_default();
+        }
+    }
+}
+
+/// Internal structure. Value of an item of a `LANGUAGE` block.
+enum Value {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl Value {
+    /// Internal method. Returns the value as text.
+    fn text(self) -> String {
+        match self {
+            Self::Text(text) => text,
+            Self::Binary(bytes) => bytes
+                .iter()
+                .take_while(|&&byte| byte != 0)
+                .map(|&byte| byte as char)
+                .collect(),
+        }
+    }
+}
+
+impl CDTextBuilder {
+    /// Creates a builder from `CD_TEXT` sections of a cdrdao toc-file, see [`CdrdaoText`].
+    ///
+    /// Each `LANGUAGE` block becomes a block of the builder, in the language given by
+    /// `LANGUAGE_MAP` (English if not given). Tracks are numbered by their `TRACK` definitions,
+    /// starting from 1. Binary items, except for `GENRE`, are not taken, since the builder
+    /// derives them from the content.
+    pub fn from_cdrdao(toc: &str) -> Result<Self, TocFileError> {
+        let mut reader = TocFileReader {
+            tokens: tokenize(toc)?,
+            ..TocFileReader::default()
+        };
+
+        let mut track: u8 = 0;
+
+        while reader.position < reader.tokens.len() {
+            match reader.next()? {
+                (line, Token::Word("TRACK")) => {
+                    track += 1;
+
+                    if track > 99 {
+                        return Err(TocFileError::TooManyTracks { line });
+                    }
+                }
+                (_, Token::Word("CD_TEXT")) => reader.cd_text(track)?,
+                _ => {}
+            }
+        }
+
+        let blocks = core::mem::take(&mut reader.texts)
+            .into_iter()
+            .map(|(block_number, texts)| {
+                let language = reader
+                    .languages
+                    .get(&block_number)
+                    .copied()
+                    .unwrap_or(Language::English);
+
+                let mut block = BlockBuilder::new(language);
+
+                for (pack_type, track, text) in texts {
+                    block.set_text(pack_type, track, text);
+                }
+
+                match reader.genres.remove(&block_number) {
+                    Some(genre) => block.with_genre(genre),
+                    None => block,
+                }
+            });
+
+        Ok(Self::from_blocks(blocks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Caf\u{e9} \"Live\"")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .with_text(CDTextPackType::Performers, 2, "Guest")
+            .with_text(CDTextPackType::Code, 2, "USRC17607839")
+            .with_genre(Genre {
+                code: GenreCode::Jazz,
+                text: Some(String::from("Bebop")),
+            })
+            .build()
+            .unwrap();
+        let text = CDText::from_data(&data).to_cdrdao().unwrap();
+
+        assert_eq!(
+            text.disc,
+            "CD_TEXT {\n  LANGUAGE_MAP {\n    0 : EN\n  }\n  LANGUAGE 0 {\n\
+             \x20   TITLE \"Caf\\351 \\\"Live\\\"\"\n    PERFORMER \"\"\n\
+             \x20   GENRE { 0, 14, 66, 101, 98, 111, 112, 0 }\n  }\n}\n"
+        );
+        assert_eq!(
+            text.tracks,
+            [
+                (
+                    1,
+                    String::from(
+                        "CD_TEXT {\n  LANGUAGE 0 {\n    TITLE \"First\"\n    PERFORMER \"\"\n  }\n}\n"
+                    )
+                ),
+                (
+                    2,
+                    String::from(
+                        "CD_TEXT {\n  LANGUAGE 0 {\n    TITLE \"\"\n    PERFORMER \"Guest\"\n\
+                         \x20   ISRC \"USRC17607839\"\n  }\n}\n"
+                    )
+                ),
+            ]
+        );
+    }
+
+    /// Internal function. Creates a toc-file with a `TRACK` definition for every track with text.
+    fn toc_file(text: &CdrdaoText) -> String {
+        let mut toc = String::from("CD_DA\n\n") + &text.disc;
+
+        for (_, section) in &text.tracks {
+            toc += "\nTRACK AUDIO\n";
+            toc += section;
+        }
+
+        toc
+    }
+
+    #[test]
+    fn import() {
+        let toc = "CD_DA\n// Album\nCD_TEXT {\n  LANGUAGE_MAP { 0 : EN, 1 : 8 }\n\
+                   \x20 LANGUAGE 0 {\n    TITLE \"Caf\\351\"\n    UPC_EAN \"036000291452\"\n\
+                   \x20   GENRE { 0, 14, 66, 101, 98, 111, 112, 0 }\n    SIZE_INFO { 0, 1, 2 }\n  }\n\
+                   \x20 LANGUAGE 1 { TITLE \"Titel\" }\n}\n\
+                   TRACK AUDIO\nFILE \"one.wav\" 0\n\
+                   TRACK AUDIO\nCD_TEXT { LANGUAGE 0 { TITLE \"Second\" ISRC \"USRC17607839\" } }\n";
+
+        let builder = CDTextBuilder::from_cdrdao(toc).unwrap();
+        let blocks = builder.blocks();
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].language(), Language::English);
+        assert_eq!(blocks[0].text(CDTextPackType::Title, 0), Some("Caf\u{e9}"));
+        assert_eq!(
+            blocks[0].text(CDTextPackType::Code, 0),
+            Some("036000291452")
+        );
+        assert_eq!(blocks[0].text(CDTextPackType::Title, 1), None);
+        assert_eq!(blocks[0].text(CDTextPackType::Title, 2), Some("Second"));
+        assert_eq!(
+            blocks[0].text(CDTextPackType::Code, 2),
+            Some("USRC17607839")
+        );
+        assert_eq!(
+            blocks[0].genre(),
+            Some(&Genre {
+                code: GenreCode::Jazz,
+                text: Some(String::from("Bebop")),
+            })
+        );
+        assert_eq!(blocks[1].language(), Language::German);
+        assert_eq!(blocks[1].text(CDTextPackType::Title, 0), Some("Titel"));
+    }
+
+    #[test]
+    fn import_errors() {
+        assert_eq!(
+            CDTextBuilder::from_cdrdao("CD_TEXT {\n  LANGUAGE 0 { TITLE \"Album }\n}").err(),
+            Some(TocFileError::UnterminatedString { line: 2 })
+        );
+        assert_eq!(
+            CDTextBuilder::from_cdrdao("CD_TEXT {\n  LANGUAGE 8 { TITLE \"Album\" }\n}").err(),
+            Some(TocFileError::InvalidBlockNumber { line: 2 })
+        );
+        assert_eq!(
+            CDTextBuilder::from_cdrdao("CD_TEXT {\n  LANGUAGE_MAP { 0 : FR }\n}").err(),
+            Some(TocFileError::UnknownLanguage { line: 2 })
+        );
+        assert_eq!(
+            CDTextBuilder::from_cdrdao("CD_TEXT {\n  LANGUAGE 0 { TITLE }\n}").err(),
+            Some(TocFileError::Syntax { line: 2 })
+        );
+        assert_eq!(
+            CDTextBuilder::from_cdrdao("CD_TEXT {\n  LANGUAGE 0 {").err(),
+            Some(TocFileError::Syntax { line: 2 })
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Caf\u{e9} \"Live\" \\ Encore")
+            .with_text(CDTextPackType::Performers, 0, "Band")
+            .with_text(CDTextPackType::DiscID, 0, "XY-1234")
+            .with_text(CDTextPackType::Code, 0, "036000291452")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .with_text(CDTextPackType::Message, 2, "Bonus")
+            .with_text(CDTextPackType::Code, 2, "USRC17607839")
+            .with_genre(Genre {
+                code: GenreCode::Jazz,
+                text: None,
+            })
+            .with_block(BlockBuilder::new(Language::German).with_text(
+                CDTextPackType::Title,
+                0,
+                "Titel",
+            ))
+            .build()
+            .unwrap();
+
+        let toc = toc_file(&CDText::from_data(&data).to_cdrdao().unwrap());
+        let builder = CDTextBuilder::from_cdrdao(&toc).unwrap();
+        assert_eq!(builder.build().unwrap(), data);
+    }
+
+    #[cfg(feature = "synthetic")]
+    #[test]
+    fn synthetic_round_trip() {
+        let disc = crate::SyntheticDisc::new(12)
+            .with_languages([Language::English, Language::Japanese])
+            .with_tab_compression(false);
+        let data = disc.build().unwrap();
+
+        let toc = toc_file(&CDText::from_data(&data).to_cdrdao().unwrap());
+        let builder = CDTextBuilder::from_cdrdao(&toc).unwrap();
+        assert_eq!(builder.build().unwrap(), data);
+    }
+}
@@ -0,0 +1,362 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::BlockSizeInfo;
+#[cfg(feature = "alloc")]
+use crate::Unmappable;
+
+/// Character code of the text in a block, as declared by Block Size Info.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Charset {
+    /// ISO/IEC 8859-1 (Latin-1).
+    Iso8859_1,
+    /// ISO/IEC 646 (ASCII).
+    Ascii,
+    /// Double-byte MS-JIS (Shift-JIS).
+    MsJis,
+    /// Double-byte Korean (KS C 5601).
+    Korean,
+    /// Double-byte Mandarin Chinese (GB 2312).
+    Mandarin,
+    /// A code reserved by the specification. Holds the raw value.
+    Reserved(u8),
+}
+
+impl Charset {
+    /// Decodes the text. Returns `None` if it's invalid in the charset,
+    /// or if decoding of the charset is not supported.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn decode(self, bytes: &[u8]) -> Option<String> {
+        match self {
+            // Latin-1 code points are the same as the first 256 Unicode code points.
+            Self::Iso8859_1 => {
+                // Characters past ASCII take two bytes in UTF-8.
+                let extra = bytes.iter().filter(|x| !x.is_ascii()).count();
+                let mut text = String::with_capacity(bytes.len() + extra);
+                text.extend(bytes.iter().map(|&x| x as char));
+                Some(text)
+            }
+            Self::Ascii => bytes
+                .is_ascii()
+                .then(|| bytes.iter().map(|&x| x as char).collect()),
+            #[cfg(feature = "encoding")]
+            Self::MsJis | Self::Korean | Self::Mandarin => self
+                .encoding()?
+                .decode_without_bom_handling_and_without_replacement(bytes)
+                .map(|text| text.into_owned()),
+            _ => None,
+        }
+    }
+
+    /// Internal method. Returns the text without copying if its bytes are the same in UTF-8.
+    /// Returns `None` if the text has to be decoded with [`Charset::decode`].
+    #[cfg(feature = "alloc")]
+    pub(crate) fn decode_borrowed(self, bytes: &[u8]) -> Option<&str> {
+        match self {
+            // ASCII characters are encoded the same way in all the single-byte charsets.
+            Self::Iso8859_1 | Self::Ascii if bytes.is_ascii() => str::from_utf8(bytes).ok(),
+            _ => None,
+        }
+    }
+
+    /// Internal method. Returns the matching encoding of double-byte charsets.
+    #[cfg(feature = "encoding")]
+    fn encoding(self) -> Option<&'static encoding_rs::Encoding> {
+        match self {
+            Self::MsJis => Some(encoding_rs::SHIFT_JIS),
+            Self::Korean => Some(encoding_rs::EUC_KR),
+            Self::Mandarin => Some(encoding_rs::GBK),
+            _ => None,
+        }
+    }
+
+    /// Decodes the text, replacing invalid characters with U+FFFD REPLACEMENT CHARACTER.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn decode_lossy(self, bytes: &[u8]) -> String {
+        match self {
+            Self::Ascii => bytes
+                .iter()
+                .map(|&x| {
+                    if x.is_ascii() {
+                        x as char
+                    } else {
+                        char::REPLACEMENT_CHARACTER
+                    }
+                })
+                .collect(),
+            #[cfg(feature = "encoding")]
+            Self::MsJis | Self::Korean | Self::Mandarin => match self.encoding() {
+                Some(encoding) => encoding.decode_without_bom_handling(bytes).0.into_owned(),
+                None => String::new(),
+            },
+            _ => self.decode(bytes).unwrap_or_default(),
+        }
+    }
+
+    /// Internal method. Encodes the text, handling characters that cannot be encoded, including NULs,
+    /// according to the policy. Returns the bytes and whether the text has been degraded,
+    /// or `None` if it cannot be encoded under [`Unmappable::Error`].
+    #[cfg(feature = "alloc")]
+    pub(crate) fn encode(self, text: &str, unmappable: Unmappable) -> Option<(Vec<u8>, bool)> {
+        let mut bytes = Vec::with_capacity(text.len());
+        let mut degraded = false;
+
+        for character in text.chars() {
+            if character != '\0' && self.encode_character(character, &mut bytes) {
+                continue;
+            }
+
+            degraded = true;
+
+            match unmappable {
+                Unmappable::Error => return None,
+                Unmappable::Replace => {
+                    self.encode_character('?', &mut bytes);
+                }
+                Unmappable::Skip => {}
+            }
+        }
+
+        Some((bytes, degraded))
+    }
+
+    /// Internal method. Appends the encoded character to the bytes.
+    /// Returns `false` if the character cannot be encoded.
+    #[cfg(feature = "alloc")]
+    fn encode_character(self, character: char, bytes: &mut Vec<u8>) -> bool {
+        let byte = match self {
+            Self::Iso8859_1 => u8::try_from(character).ok(),
+            Self::Ascii => character.is_ascii().then_some(character as u8),
+            #[cfg(feature = "encoding")]
+            Self::MsJis => {
+                // Double-byte blocks can't hold single-byte characters,
+                // so ASCII is written in its full-width form.
+                let character = match character {
+                    ' ' => '\u{3000}',
+                    '!'..='~' => char::from_u32(character as u32 + 0xfee0).unwrap_or(character),
+                    _ => character,
+                };
+
+                let Some(encoding) = self.encoding() else {
+                    return false;
+                };
+
+                let mut buffer = [0; 4];
+                let (encoded, _, had_errors) = encoding.encode(character.encode_utf8(&mut buffer));
+
+                if had_errors || encoded.len() != 2 {
+                    return false;
+                }
+
+                bytes.extend_from_slice(&encoded);
+                return true;
+            }
+            _ => None,
+        };
+
+        byte.map(|byte| bytes.push(byte)).is_some()
+    }
+
+    /// Whether text can be encoded in the charset by [`crate::CDTextBuilder`].
+    /// MS-JIS needs the `encoding` feature.
+    pub fn is_encodable(self) -> bool {
+        matches!(self, Self::Iso8859_1 | Self::Ascii)
+            || (self == Self::MsJis && cfg!(feature = "encoding"))
+    }
+
+    /// Whether each character of the charset takes two bytes.
+    pub fn is_double_byte(self) -> bool {
+        matches!(self, Self::MsJis | Self::Korean | Self::Mandarin)
+    }
+
+    /// Whether the text in the charset can be decoded.
+    /// Double-byte charsets need the `encoding` feature.
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::Iso8859_1 | Self::Ascii => true,
+            Self::MsJis | Self::Korean | Self::Mandarin => cfg!(feature = "encoding"),
+            Self::Reserved(_) => false,
+        }
+    }
+}
+
+impl From<u8> for Charset {
+    fn from(value: u8) -> Self {
+        match value {
+            0x00 => Self::Iso8859_1,
+            0x01 => Self::Ascii,
+            0x80 => Self::MsJis,
+            0x81 => Self::Korean,
+            0x82 => Self::Mandarin,
+            n => Self::Reserved(n),
+        }
+    }
+}
+
+impl fmt::Display for Charset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Iso8859_1 => "ISO 8859-1",
+            Self::Ascii => "ASCII",
+            Self::MsJis => "MS-JIS",
+            Self::Korean => "Korean",
+            Self::Mandarin => "Mandarin",
+            Self::Reserved(value) => return write!(f, "Reserved ({value:#04x})"),
+        };
+
+        f.write_str(name)
+    }
+}
+
+impl From<Charset> for u8 {
+    fn from(value: Charset) -> Self {
+        match value {
+            Charset::Iso8859_1 => 0x00,
+            Charset::Ascii => 0x01,
+            Charset::MsJis => 0x80,
+            Charset::Korean => 0x81,
+            Charset::Mandarin => 0x82,
+            Charset::Reserved(n) => n,
+        }
+    }
+}
+
+impl BlockSizeInfo {
+    /// Returns the charset of the text in the block.
+    pub fn charset(&self) -> Charset {
+        Charset::from(self.character_code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::{
+        CDText, CDTextBuilder, CDTextEntryDataType, CDTextError, CDTextPackType, ParserOptions,
+    };
+
+    #[test]
+    fn codes() {
+        assert_eq!(Charset::from(0x00), Charset::Iso8859_1);
+        assert_eq!(Charset::from(0x01), Charset::Ascii);
+        assert_eq!(Charset::from(0x80), Charset::MsJis);
+        assert_eq!(Charset::from(0x42), Charset::Reserved(0x42));
+
+        for code in [0x00, 0x01, 0x80, 0x81, 0x82, 0x42] {
+            assert_eq!(u8::from(Charset::from(code)), code);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn decode() {
+        assert_eq!(Charset::Ascii.decode(b"Cafe").as_deref(), Some("Cafe"));
+        assert_eq!(Charset::Ascii.decode(b"Caf\xe9"), None);
+        assert_eq!(Charset::Ascii.decode_lossy(b"Caf\xe9"), "Caf\u{fffd}");
+        assert_eq!(Charset::Iso8859_1.decode_borrowed(b"Cafe"), Some("Cafe"));
+        assert_eq!(Charset::Iso8859_1.decode_borrowed(b"Caf\xe9"), None);
+        assert_eq!(Charset::Reserved(0x42).decode(b"Cafe"), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn latin_1() {
+        let bytes: Vec<u8> = (0x80..=0xff).collect();
+        let text = Charset::Iso8859_1.decode(&bytes).unwrap();
+
+        assert!(text.chars().map(u32::from).eq(0x80..=0xff));
+        assert_eq!(
+            Charset::Iso8859_1.encode(&text, Unmappable::Error),
+            Some((bytes, false))
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn declared_charset() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Café")
+            .build()
+            .unwrap();
+        assert!(data.starts_with(b"\x80\x00\x00\x00Caf\xe9\x00"));
+
+        let cdtext = CDText::from_data(&data);
+        assert_eq!(
+            cdtext.block_size_info(0).unwrap().charset(),
+            Charset::Iso8859_1
+        );
+        assert_eq!(
+            cdtext.parse().unwrap()[0].data,
+            CDTextEntryDataType::String(String::from("Café"))
+        );
+
+        let ascii = cdtext.with_options(ParserOptions::strict().with_charset(Charset::Ascii));
+        assert!(matches!(
+            ascii.parse(),
+            Err(CDTextError::InvalidText { pack_index: 0, .. })
+        ));
+    }
+
+    /// Title of the disc and of track 1 in a double-byte MS-JIS pack.
+    #[cfg(feature = "alloc")]
+    fn double_byte_pack() -> [u8; 18] {
+        let mut pack = [0; 18];
+        pack[..4].copy_from_slice(&[0x80, 0, 0, 0x80]);
+        pack[4..16].copy_from_slice(b"\x93\xfa\x96\x7b\x8c\xea\0\0\x82\x50\0\0");
+        crate::finalize_pack(&mut pack);
+        pack
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn double_byte() {
+        let title = "\u{65e5}\u{672c}\u{8a9e}";
+        assert_eq!(
+            Charset::MsJis
+                .decode(b"\x93\xfa\x96\x7b\x8c\xea")
+                .as_deref(),
+            Some(title)
+        );
+        assert_eq!(Charset::MsJis.decode(b"\x93"), None);
+        assert_eq!(
+            Charset::MsJis.decode_lossy(b"\x93\xfa\x93"),
+            "\u{65e5}\u{fffd}"
+        );
+        assert!(Charset::MsJis.is_supported());
+
+        let pack = double_byte_pack();
+        let cdtext = CDText::from_data(&pack)
+            .with_options(ParserOptions::strict().with_charset(Charset::MsJis));
+        let entries = cdtext.parse().unwrap();
+        assert_eq!(
+            entries[0].data,
+            CDTextEntryDataType::String(String::from(title))
+        );
+        assert_eq!(u8::from(entries[1].track_number), 1);
+        assert_eq!(
+            entries[1].data,
+            CDTextEntryDataType::String(String::from("\u{ff11}"))
+        );
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "encoding")))]
+    #[test]
+    fn double_byte_unsupported() {
+        let pack = double_byte_pack();
+        let cdtext = CDText::from_data(&pack)
+            .with_options(ParserOptions::strict().with_charset(Charset::MsJis));
+
+        assert!(!Charset::MsJis.is_supported());
+        assert!(matches!(
+            cdtext.parse(),
+            Err(CDTextError::UnsupportedCharset {
+                pack_index: 0,
+                character_code: 0x80,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+use core::fmt;
+
+/// UPC/EAN code of the disc. Holds 12 (UPC-A) or 13 (EAN-13) digits with a valid check digit.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Upc {
+    digits: [u8; 13],
+    length: u8,
+}
+
+impl Upc {
+    /// Parses and validates a UPC/EAN code.
+    pub fn parse(code: &str) -> Option<Self> {
+        let bytes = code.as_bytes();
+
+        if !(12..=13).contains(&bytes.len()) || !bytes.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+
+        let (data, check) = bytes.split_at(bytes.len() - 1);
+
+        // Digits are weighted 3, 1, 3, ... starting from the one next to the check digit.
+        let sum: u32 = data
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(index, digit)| (digit - b'0') as u32 * if index % 2 == 0 { 3 } else { 1 })
+            .sum();
+
+        if (10 - sum % 10) % 10 != (check[0] - b'0') as u32 {
+            return None;
+        }
+
+        let mut digits = [0u8; 13];
+        digits[..bytes.len()].copy_from_slice(bytes);
+
+        Some(Self {
+            digits,
+            length: bytes.len() as u8,
+        })
+    }
+
+    /// Returns the code as a string of digits.
+    pub fn as_str(&self) -> &str {
+        // Only ASCII digits are ever stored.
+        str::from_utf8(&self.digits[..self.length as usize]).unwrap_or_default()
+    }
+
+    /// Whether the code is a 13-digit EAN code.
+    pub fn is_ean13(&self) -> bool {
+        self.length == 13
+    }
+}
+
+impl fmt::Display for Upc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// International Standard Recording Code of a track, e.g. `USRC17607839`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Isrc {
+    code: [u8; 12],
+}
+
+impl Isrc {
+    /// Parses and validates an ISRC. Hyphens between the parts are allowed.
+    pub fn parse(code: &str) -> Option<Self> {
+        let mut bytes = code.bytes().filter(|&x| x != b'-');
+        let mut code = [0u8; 12];
+
+        for byte in code.iter_mut() {
+            *byte = bytes.next()?.to_ascii_uppercase();
+        }
+
+        if bytes.next().is_some() {
+            return None;
+        }
+
+        let valid = code[..2].iter().all(u8::is_ascii_uppercase)
+            && code[2..5].iter().all(u8::is_ascii_alphanumeric)
+            && code[5..].iter().all(u8::is_ascii_digit);
+
+        valid.then_some(Self { code })
+    }
+
+    /// Returns the code as a 12-character string without hyphens.
+    pub fn as_str(&self) -> &str {
+        // Only ASCII alphanumerics are ever stored.
+        str::from_utf8(&self.code).unwrap_or_default()
+    }
+
+    /// Two-letter country code.
+    pub fn country_code(&self) -> &str {
+        &self.as_str()[..2]
+    }
+
+    /// Three-character registrant code.
+    pub fn registrant_code(&self) -> &str {
+        &self.as_str()[2..5]
+    }
+
+    /// Last two digits of the reference year.
+    pub fn year(&self) -> &str {
+        &self.as_str()[5..7]
+    }
+
+    /// Five-digit designation code.
+    pub fn designation_code(&self) -> &str {
+        &self.as_str()[7..]
+    }
+}
+
+impl fmt::Display for Isrc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// UPC/EAN and ISRC codes are (de)serialized as strings, and validated on deserialization.
+#[cfg(feature = "serde")]
+macro_rules! impl_serde_as_str {
+    ($type:ty, $expected:literal) => {
+        impl serde::Serialize for $type {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $type {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let code = alloc::string::String::deserialize(deserializer)?;
+
+                Self::parse(&code).ok_or_else(|| {
+                    serde::de::Error::invalid_value(serde::de::Unexpected::Str(&code), &$expected)
+                })
+            }
+        }
+    };
+}
+
+#[cfg(feature = "serde")]
+impl_serde_as_str!(Upc, "a 12- or 13-digit UPC/EAN code");
+#[cfg(feature = "serde")]
+impl_serde_as_str!(Isrc, "a 12-character ISRC code");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upc() {
+        let upc = Upc::parse("036000291452").unwrap();
+        assert_eq!(upc.as_str(), "036000291452");
+        assert!(!upc.is_ean13());
+
+        assert!(Upc::parse("4006381333931").unwrap().is_ean13());
+
+        // Wrong check digit, wrong length and non-digits.
+        assert_eq!(Upc::parse("036000291453"), None);
+        assert_eq!(Upc::parse("03600029145"), None);
+        assert_eq!(Upc::parse("03600029145a"), None);
+    }
+
+    #[test]
+    fn isrc() {
+        let isrc = Isrc::parse("us-rc1-76-07839").unwrap();
+        assert_eq!(isrc.as_str(), "USRC17607839");
+        assert_eq!(isrc.country_code(), "US");
+        assert_eq!(isrc.registrant_code(), "RC1");
+        assert_eq!(isrc.year(), "76");
+        assert_eq!(isrc.designation_code(), "07839");
+
+        assert_eq!(Isrc::parse("USRC1760783"), None);
+        assert_eq!(Isrc::parse("USRC176078390"), None);
+        assert_eq!(Isrc::parse("1SRC17607839"), None);
+        assert_eq!(Isrc::parse("USRC1760783X"), None);
+    }
+}
@@ -0,0 +1,71 @@
+/// CRC of each byte value, so data is processed a byte at a time.
+const TABLE: [u16; 256] = table();
+
+/// Internal function. Computes [`TABLE`] bit by bit.
+const fn table() -> [u16; 256] {
+    let mut table = [0; 256];
+    let mut index = 0;
+
+    while index < 256 {
+        let mut crc = (index as u16) << 8;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+
+        table[index] = crc;
+        index += 1;
+    }
+
+    table
+}
+
+/// Computes the CRC of a CD-Text pack.
+///
+/// CD-Text uses the CRC-16 polynomial from ITU-T X.25 (x^16 + x^12 + x^5 + 1), processed MSB-first
+/// with zero initial value. The resulting value is inverted before being stored in a pack.
+pub(crate) fn crc16(data: &[u8]) -> u16 {
+    let crc = data.iter().fold(0u16, |crc, &byte| {
+        (crc << 8) ^ TABLE[((crc >> 8) as u8 ^ byte) as usize]
+    });
+
+    !crc
+}
+
+/// Computes the CRC of an 18-byte pack over its header and payload, ignoring the stored CRC.
+pub fn pack_crc(pack: &[u8; 18]) -> u16 {
+    crc16(&pack[..16])
+}
+
+/// Stores the correct CRC into the last two bytes of the pack, finalizing a hand-built pack.
+pub fn finalize_pack(pack: &mut [u8; 18]) {
+    let crc = pack_crc(pack);
+    pack[16..].copy_from_slice(&crc.to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_value() {
+        // Check value of CRC-16/GSM, the same CRC with the result inverted.
+        assert_eq!(crc16(b"123456789"), 0xce3c);
+    }
+
+    #[test]
+    fn known_pack() {
+        let mut pack = *b"\x80\x00\x00\x00My Album\x00Ope\x07\x6e";
+        assert_eq!(pack_crc(&pack), 0x076e);
+
+        pack[16..].fill(0);
+        finalize_pack(&mut pack);
+        assert_eq!(pack[16..], [0x07, 0x6e]);
+    }
+}
@@ -0,0 +1,423 @@
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+#[cfg(feature = "std")]
+use crate::CueFileError;
+use crate::{
+    CDText, CDTextBuilder, CDTextEntryDataType, CDTextError, CDTextPackType, CueError, Disc, Genre,
+    GenreCode, Toc,
+};
+
+/// Internal function. Splits the first word, or the quoted string, off the text.
+/// Returns `None` if the quoted string is not closed.
+fn split_value(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim_start();
+
+    if let Some(quoted) = text.strip_prefix('"') {
+        let end = quoted.find('"')?;
+        return Some((&quoted[..end], &quoted[end + 1..]));
+    }
+
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    Some((&text[..end], &text[end..]))
+}
+
+/// Internal function. Returns the value of a command: the quoted string, or the rest
+/// of the line if it's not quoted, like cdrdao and cuetools read it, e.g. `TITLE My Album`.
+/// Returns `None` if the quoted string is not closed.
+fn line_value(text: &str) -> Option<&str> {
+    let text = text.trim();
+
+    if text.starts_with('"') {
+        return split_value(text).map(|(value, _)| value);
+    }
+
+    Some(text)
+}
+
+/// Internal function. Adds the genre given by `REM GENRE` to the one read so far, if any:
+/// the name of a standard genre sets the code, any other name sets the supplementary text.
+fn genre_by_name(name: &str, previous: Option<&Genre>) -> Genre {
+    let mut genre = previous.cloned().unwrap_or(Genre {
+        code: GenreCode::NotDefined,
+        text: None,
+    });
+
+    match GenreCode::from_name(name) {
+        Some(code) => genre.code = code,
+        None => genre.text = Some(String::from(name)),
+    }
+
+    genre
+}
+
+/// Returns the name of the binary CD-Text file referenced by `CDTEXTFILE` command of the CUE sheet,
+/// if there's one. The name is relative to the directory of the sheet.
+pub fn cue_cdtext_file(sheet: &str) -> Result<Option<&str>, CueError> {
+    for (index, line) in sheet.lines().enumerate() {
+        let unterminated = CueError::UnterminatedString { line: index + 1 };
+        let (command, rest) = split_value(line).ok_or(unterminated.clone())?;
+
+        if command.eq_ignore_ascii_case("CDTEXTFILE") {
+            let name = line_value(rest).ok_or(unterminated)?;
+
+            if name.is_empty() {
+                return Err(CueError::MissingValue { line: index + 1 });
+            }
+
+            return Ok(Some(name));
+        }
+    }
+
+    Ok(None)
+}
+
+impl CDTextBuilder {
+    /// Creates a builder from the CD-Text fields of a CUE sheet.
+    ///
+    /// `TITLE`, `PERFORMER`, `SONGWRITER`, `COMPOSER`, `ARRANGER`, `MESSAGE` and `DISC_ID`
+    /// before the first `TRACK` command describe the album, the ones after it describe the track.
+    /// `CATALOG` and `ISRC` are written as UPC/EAN and ISRC codes. The same fields are also
+    /// accepted as `REM` comments, e.g. `REM COMPOSER "..."`, as written by some tools,
+    /// along with `REM GENRE`. The genre may be given twice, by the name of a standard genre
+    /// and by the supplementary text. Other commands are ignored, including `CDTEXTFILE`,
+    /// see [`CDTextBuilder::from_cue_file`] for that.
+    ///
+    /// Values are usually quoted. An unquoted value takes the rest of the line.
+    pub fn from_cue(sheet: &str) -> Result<Self, CueError> {
+        let mut builder = Self::new();
+        let mut track = 0;
+
+        let sheet = sheet.strip_prefix('\u{feff}').unwrap_or(sheet);
+
+        for (index, line) in sheet.lines().enumerate() {
+            let line_number = index + 1;
+            let unterminated = CueError::UnterminatedString { line: line_number };
+
+            let (mut command, mut rest) = split_value(line).ok_or(unterminated.clone())?;
+
+            if command.eq_ignore_ascii_case("REM") {
+                (command, rest) = split_value(rest).ok_or(unterminated.clone())?;
+            }
+
+            let command = command.to_ascii_uppercase();
+
+            if command == "TRACK" {
+                let (number, _) = split_value(rest).ok_or(unterminated)?;
+
+                track = match number.parse::<u8>() {
+                    Ok(number @ 1..=99) => number,
+                    _ => return Err(CueError::InvalidTrackNumber { line: line_number }),
+                };
+
+                continue;
+            }
+
+            let pack_type = match command.as_str() {
+                "TITLE" => CDTextPackType::Title,
+                "PERFORMER" => CDTextPackType::Performers,
+                "SONGWRITER" => CDTextPackType::Songwriters,
+                "COMPOSER" => CDTextPackType::Composers,
+                "ARRANGER" => CDTextPackType::Arrangers,
+                "MESSAGE" => CDTextPackType::Message,
+                "DISC_ID" => CDTextPackType::DiscID,
+                "CATALOG" | "ISRC" => CDTextPackType::Code,
+                "GENRE" => CDTextPackType::Genre,
+                _ => continue,
+            };
+
+            let value = line_value(rest).ok_or(unterminated)?;
+
+            if value.is_empty() {
+                return Err(CueError::MissingValue { line: line_number });
+            }
+
+            if pack_type == CDTextPackType::Genre {
+                if track == 0 {
+                    let genre = genre_by_name(value, builder.blocks()[0].genre());
+                    builder = builder.with_genre(genre);
+                }
+            } else {
+                builder = builder.with_text(pack_type, track, value);
+            }
+        }
+
+        Ok(builder)
+    }
+}
+
+#[cfg(feature = "std")]
+impl CDTextBuilder {
+    /// Reads a CUE sheet from the file and creates a builder from it, along with the binary
+    /// CD-Text file referenced by its `CDTEXTFILE` command, if any.
+    ///
+    /// The CD-Text file is looked up relative to the directory of the sheet. Its text takes
+    /// precedence, while the fields of the sheet fill in what it lacks, see [`CDTextBuilder::merge`].
+    /// The sheet is decoded as UTF-8, invalid sequences are replaced.
+    pub fn from_cue_file(path: impl AsRef<std::path::Path>) -> Result<Self, CueFileError> {
+        let path = path.as_ref();
+        let read = |path: &std::path::Path| {
+            std::fs::read(path).map_err(|error| CueFileError::Read {
+                path: path.display().to_string(),
+                code: error.raw_os_error().unwrap_or(0),
+            })
+        };
+
+        let sheet = read(path)?;
+        let sheet = String::from_utf8_lossy(&sheet);
+        let builder = Self::from_cue(&sheet).map_err(CueFileError::Cue)?;
+
+        let Some(name) = cue_cdtext_file(&sheet).map_err(CueFileError::Cue)? else {
+            return Ok(builder);
+        };
+
+        let data = read(&path.parent().unwrap_or(std::path::Path::new("")).join(name))?;
+        let cdtext = CDText::new(&data)
+            .to_builder()
+            .map_err(CueFileError::CDText)?;
+
+        Ok(cdtext.merge(builder))
+    }
+}
+
+/// Internal function. Writes the command with a quoted value. CUE sheets have no escapes,
+/// so double quotes in the value are replaced with single ones.
+fn write_command(sheet: &mut String, indent: &str, command: &str, value: Option<&str>) {
+    if let Some(value) = value.filter(|value| !value.is_empty()) {
+        let _ = writeln!(sheet, "{indent}{command} \"{}\"", value.replace('"', "'"));
+    }
+}
+
+impl Disc {
+    /// Creates CD-Text fields of a CUE sheet, the reverse of [`CDTextBuilder::from_cue`].
+    ///
+    /// `TITLE`, `PERFORMER`, `SONGWRITER`, `CATALOG` and `ISRC` are standard commands, the other
+    /// fields are written as `REM` comments. A genre with both the code and the supplementary
+    /// text takes two `REM GENRE` comments. With the TOC, e.g. from TOC packs, the sheet also gets
+    /// `FILE` and `INDEX 01` commands describing a single image of the disc, otherwise only
+    /// the tracks that have text are listed.
+    pub fn to_cue(&self, toc: Option<&Toc>) -> String {
+        let mut sheet = String::new();
+
+        if let Some(genre) = &self.genre {
+            // The standard genre is written by its name, followed by the supplementary text,
+            // which tools taking the last `REM GENRE` show instead.
+            let name = match genre.code {
+                GenreCode::NotUsed | GenreCode::NotDefined | GenreCode::Reserved(_) => None,
+                code => Some(code.name()),
+            };
+
+            write_command(&mut sheet, "", "REM GENRE", name);
+            write_command(&mut sheet, "", "REM GENRE", genre.text.as_deref());
+        }
+
+        write_command(&mut sheet, "", "REM COMPOSER", self.composer.as_deref());
+        write_command(&mut sheet, "", "REM ARRANGER", self.arranger.as_deref());
+        write_command(&mut sheet, "", "REM MESSAGE", self.message.as_deref());
+        write_command(&mut sheet, "", "REM DISC_ID", self.disc_id.as_deref());
+
+        if let Some(upc) = &self.upc {
+            let _ = writeln!(sheet, "CATALOG {upc}");
+        }
+
+        write_command(&mut sheet, "", "PERFORMER", self.performer.as_deref());
+        write_command(&mut sheet, "", "SONGWRITER", self.songwriter.as_deref());
+        write_command(&mut sheet, "", "TITLE", self.title.as_deref());
+
+        let numbers: Vec<u8> = match toc {
+            Some(toc) => {
+                sheet.push_str("FILE \"CDImage.wav\" WAVE\n");
+                (toc.first_track..=toc.last_track).collect()
+            }
+            None => self.tracks.iter().map(|track| track.number).collect(),
+        };
+
+        let image_start = toc
+            .and_then(|toc| toc.track_start(toc.first_track))
+            .map_or(0, |start| start.to_frames());
+
+        for number in numbers {
+            let _ = writeln!(sheet, "  TRACK {number:02} AUDIO");
+
+            if let Some(track) = self.track(number) {
+                write_command(&mut sheet, "    ", "TITLE", track.title.as_deref());
+                write_command(&mut sheet, "    ", "PERFORMER", track.performer.as_deref());
+                write_command(
+                    &mut sheet,
+                    "    ",
+                    "SONGWRITER",
+                    track.songwriter.as_deref(),
+                );
+                write_command(
+                    &mut sheet,
+                    "    ",
+                    "REM COMPOSER",
+                    track.composer.as_deref(),
+                );
+                write_command(
+                    &mut sheet,
+                    "    ",
+                    "REM ARRANGER",
+                    track.arranger.as_deref(),
+                );
+                write_command(&mut sheet, "    ", "REM MESSAGE", track.message.as_deref());
+
+                if let Some(isrc) = &track.isrc {
+                    let _ = writeln!(sheet, "    ISRC {isrc}");
+                }
+            }
+
+            if let Some(start) = toc.and_then(|toc| toc.track_start(number)) {
+                let frames = start.to_frames().saturating_sub(image_start);
+
+                let _ = writeln!(
+                    sheet,
+                    "    INDEX 01 {:02}:{:02}:{:02}",
+                    frames / 75 / 60,
+                    frames / 75 % 60,
+                    frames % 75
+                );
+            }
+        }
+
+        sheet
+    }
+}
+
+impl CDText<'_> {
+    /// Parses the data into CD-Text fields of a CUE sheet, taking the TOC from TOC packs
+    /// if there are any, see [`Disc::to_cue`].
+    pub fn to_cue(&self) -> Result<String, CDTextError> {
+        let entries = self.parse()?;
+
+        let toc = entries.iter().find_map(|entry| match &entry.data {
+            CDTextEntryDataType::Toc(toc) => Some(toc),
+            _ => None,
+        });
+
+        Ok(Disc::from_entries(&entries).to_cue(toc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unquoted_values() {
+        let sheet = "REM GENRE Jazz Fusion\nTITLE My Album\nPERFORMER \"The Band\"\nCDTEXTFILE disc text.cdt\n\
+                     TRACK 01 AUDIO\n  TITLE  First Track  \n";
+
+        let builder = CDTextBuilder::from_cue(sheet).unwrap();
+        let block = &builder.blocks()[0];
+
+        assert_eq!(block.text(CDTextPackType::Title, 0), Some("My Album"));
+        assert_eq!(block.text(CDTextPackType::Performers, 0), Some("The Band"));
+        assert_eq!(block.text(CDTextPackType::Title, 1), Some("First Track"));
+        assert_eq!(
+            block.genre().and_then(|genre| genre.text.as_deref()),
+            Some("Jazz Fusion")
+        );
+        assert_eq!(cue_cdtext_file(sheet), Ok(Some("disc text.cdt")));
+    }
+
+    #[test]
+    fn import() {
+        let sheet = "\u{feff}CATALOG 036000291452\nPERFORMER \"The Band\"\nTITLE \"My Album\"\n\
+                     REM COMPOSER \"Composer\"\nREM GENRE \"Jazz\"\nFILE \"disc.wav\" WAVE\n\
+                     \x20 TRACK 01 AUDIO\n    TITLE \"First\"\n    ISRC USRC17607839\n\
+                     \x20   INDEX 01 00:00:00\n  TRACK 02 AUDIO\n    TITLE \"Second\"\n\
+                     \x20   REM MESSAGE \"Bonus\"\n    REM GENRE \"Rock\"\n";
+
+        let builder = CDTextBuilder::from_cue(sheet).unwrap();
+        let data = builder.build().unwrap();
+        let disc = Disc::from_entries(&CDText::from_data(&data).parse().unwrap());
+
+        assert_eq!(disc.title.as_deref(), Some("My Album"));
+        assert_eq!(disc.performer.as_deref(), Some("The Band"));
+        assert_eq!(disc.composer.as_deref(), Some("Composer"));
+        assert_eq!(
+            disc.upc.as_ref().map(|upc| upc.as_str()),
+            Some("036000291452")
+        );
+        assert_eq!(
+            disc.genre.as_ref().map(|genre| genre.code),
+            Some(GenreCode::Jazz)
+        );
+        assert_eq!(disc.tracks.len(), 2);
+        assert_eq!(disc.tracks[0].title.as_deref(), Some("First"));
+        assert_eq!(
+            disc.tracks[0].isrc.as_ref().map(|isrc| isrc.as_str()),
+            Some("USRC17607839")
+        );
+        assert_eq!(disc.tracks[1].title.as_deref(), Some("Second"));
+        assert_eq!(disc.tracks[1].message.as_deref(), Some("Bonus"));
+    }
+
+    #[test]
+    fn import_errors() {
+        assert_eq!(
+            CDTextBuilder::from_cue("TITLE \"Album\"\nPERFORMER \"Band").err(),
+            Some(CueError::UnterminatedString { line: 2 })
+        );
+        assert_eq!(
+            CDTextBuilder::from_cue("TRACK 100 AUDIO").err(),
+            Some(CueError::InvalidTrackNumber { line: 1 })
+        );
+        assert_eq!(
+            CDTextBuilder::from_cue("TRACK 01 AUDIO\n  TITLE \"\"").err(),
+            Some(CueError::MissingValue { line: 2 })
+        );
+    }
+
+    #[test]
+    fn export_round_trip() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Performers, 0, "Band")
+            .with_text(CDTextPackType::Message, 0, "Liner \"notes\"")
+            .with_text(CDTextPackType::Code, 0, "036000291452")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .with_text(CDTextPackType::Arrangers, 1, "Arranger")
+            .with_text(CDTextPackType::Code, 1, "USRC17607839")
+            .with_text(CDTextPackType::Title, 2, "Second")
+            .build()
+            .unwrap();
+        let cdtext = CDText::from_data(&data);
+
+        let sheet = cdtext.to_cue().unwrap();
+        assert!(sheet.contains("REM MESSAGE \"Liner 'notes'\"\n"));
+        assert!(sheet.contains("  TRACK 01 AUDIO\n    TITLE \"First\"\n"));
+
+        let mut disc = Disc::from_entries(&cdtext.parse().unwrap());
+        let builder = CDTextBuilder::from_cue(&sheet).unwrap();
+        let imported = Disc::from_entries(
+            &CDText::from_data(&builder.build().unwrap())
+                .parse()
+                .unwrap(),
+        );
+
+        disc.message = Some(String::from("Liner 'notes'"));
+        assert_eq!(imported, disc);
+    }
+
+    #[test]
+    fn genre_round_trip() {
+        let genre = Genre {
+            code: GenreCode::PopMusic,
+            text: Some(String::from("Synth-pop")),
+        };
+        let disc = Disc {
+            genre: Some(genre.clone()),
+            ..Disc::default()
+        };
+
+        let sheet = disc.to_cue(None);
+        assert_eq!(sheet, "REM GENRE \"Pop Music\"\nREM GENRE \"Synth-pop\"\n");
+
+        let builder = CDTextBuilder::from_cue(&sheet).unwrap();
+        assert_eq!(builder.blocks()[0].genre(), Some(&genre));
+    }
+}
@@ -0,0 +1,71 @@
+use alloc::vec::Vec;
+
+use crate::{CDText, DeviceError};
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(windows)]
+mod windows;
+
+#[cfg(target_os = "linux")]
+use linux::Device;
+#[cfg(target_os = "macos")]
+use macos::Device;
+#[cfg(windows)]
+use windows::Device;
+
+/// Internal structure. Stand-in for platforms without a backend.
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+struct Device;
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+impl Device {
+    fn open(_path: &str) -> Result<Self, DeviceError> {
+        Err(DeviceError::Unsupported)
+    }
+
+    fn read_cd_text(&self, _buffer: &mut [u8]) -> Result<usize, DeviceError> {
+        Err(DeviceError::Unsupported)
+    }
+}
+
+/// Reads CD-Text of the disc in the drive with READ TOC/PMA/ATIP command.
+///
+/// The drive is given by its path: e.g. `/dev/sr0` on Linux, where the command is sent
+/// through SG_IO, `/dev/rdisk2` on macOS, where it's sent with `DKIOCCDREADTOC`,
+/// or `D:` (as well as `\\.\D:` or `\\.\CdRom0`) on Windows, where it's sent
+/// with `IOCTL_CDROM_READ_TOC_EX`.
+///
+/// The result starts with the 4-byte header and can be parsed with [`CDText::new`],
+/// or read and parsed at once with [`CDText::from_device`].
+pub fn read_device(path: &str) -> Result<Vec<u8>, DeviceError> {
+    let device = Device::open(path)?;
+
+    // The header holds the length of the whole response, so it is read first.
+    let mut header = [0u8; 4];
+    device.read_cd_text(&mut header)?;
+
+    let length =
+        (usize::from(u16::from_be_bytes([header[0], header[1]])) + 2).min(usize::from(u16::MAX));
+
+    let mut data = alloc::vec![0u8; length.max(4)];
+    let read = device.read_cd_text(&mut data)?;
+    data.truncate(read.max(4));
+
+    Ok(data)
+}
+
+impl<'data> CDText<'data> {
+    /// Reads CD-Text of the disc in the drive into the buffer, see [`read_device`],
+    /// and creates a parser over it. The 4-byte header of the response is stripped.
+    ///
+    /// The parser borrows the data, so it's read into a buffer owned by the caller,
+    /// like by [`CDText::from_file`].
+    pub fn from_device(path: &str, buffer: &'data mut Vec<u8>) -> Result<Self, DeviceError> {
+        *buffer = read_device(path)?;
+
+        Ok(Self::from_received_data(buffer))
+    }
+}
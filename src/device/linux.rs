@@ -0,0 +1,150 @@
+use std::{fs::File, io, os::fd::AsRawFd, os::unix::fs::OpenOptionsExt};
+
+use crate::DeviceError;
+
+/// `SG_IO` ioctl request of the SCSI generic driver.
+const SG_IO: u32 = 0x2285;
+/// Data is transferred from the device.
+const SG_DXFER_FROM_DEV: i32 = -3;
+/// Mask of `info` bits telling whether the command completed successfully.
+const SG_INFO_OK_MASK: u32 = 0x1;
+/// Timeout of a command, in milliseconds.
+const TIMEOUT: u32 = 30_000;
+
+/// Internal function. Builds READ TOC/PMA/ATIP command that reads CD-Text (format 5).
+fn read_toc_command(allocation_length: usize) -> [u8; 10] {
+    let length = u16::try_from(allocation_length)
+        .unwrap_or(u16::MAX)
+        .to_be_bytes();
+
+    [0x43, 0, 0x05, 0, 0, 0, 0, length[0], length[1], 0]
+}
+
+/// Internal function. Extracts the sense key, ASC and ASCQ from fixed or descriptor format
+/// sense data.
+fn rejected(sense: &[u8]) -> DeviceError {
+    let byte = |index: usize| sense.get(index).copied().unwrap_or(0);
+
+    match byte(0) & 0x7f {
+        0x72 | 0x73 => DeviceError::Rejected {
+            sense_key: byte(1) & 0x0f,
+            asc: byte(2),
+            ascq: byte(3),
+        },
+        _ => DeviceError::Rejected {
+            sense_key: byte(2) & 0x0f,
+            asc: byte(12),
+            ascq: byte(13),
+        },
+    }
+}
+
+/// Internal structure. `sg_io_hdr_t` from `<scsi/sg.h>`.
+#[repr(C)]
+struct SgIoHeader {
+    interface_id: i32,
+    dxfer_direction: i32,
+    cmd_len: u8,
+    mx_sb_len: u8,
+    iovec_count: u16,
+    dxfer_len: u32,
+    dxferp: *mut libc::c_void,
+    cmdp: *const u8,
+    sbp: *mut u8,
+    timeout: u32,
+    flags: u32,
+    pack_id: i32,
+    usr_ptr: *mut libc::c_void,
+    status: u8,
+    masked_status: u8,
+    msg_status: u8,
+    sb_len_wr: u8,
+    host_status: u16,
+    driver_status: u16,
+    resid: i32,
+    duration: u32,
+    info: u32,
+}
+
+/// Internal function. Returns the code of the last OS error.
+fn last_error() -> i32 {
+    io::Error::last_os_error().raw_os_error().unwrap_or(0)
+}
+
+/// Internal structure. An opened drive.
+pub(super) struct Device {
+    file: File,
+}
+
+impl Device {
+    /// Internal function. Opens the drive. Non-blocking mode lets it be opened without a disc
+    /// or while the tray is open.
+    pub(super) fn open(path: &str) -> Result<Self, DeviceError> {
+        let file = File::options()
+            .read(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(path)
+            .map_err(|error| DeviceError::Open {
+                code: error.raw_os_error().unwrap_or(0),
+            })?;
+
+        Ok(Self { file })
+    }
+
+    /// Internal method. Reads CD-Text into the buffer, returning the number of bytes read.
+    pub(super) fn read_cd_text(&self, buffer: &mut [u8]) -> Result<usize, DeviceError> {
+        let command = read_toc_command(buffer.len());
+        let mut sense = [0u8; 32];
+
+        let mut header = SgIoHeader {
+            interface_id: i32::from(b'S'),
+            dxfer_direction: SG_DXFER_FROM_DEV,
+            cmd_len: command.len() as u8,
+            mx_sb_len: sense.len() as u8,
+            iovec_count: 0,
+            dxfer_len: buffer.len() as u32,
+            dxferp: buffer.as_mut_ptr().cast(),
+            cmdp: command.as_ptr(),
+            sbp: sense.as_mut_ptr(),
+            timeout: TIMEOUT,
+            flags: 0,
+            pack_id: 0,
+            usr_ptr: core::ptr::null_mut(),
+            status: 0,
+            masked_status: 0,
+            msg_status: 0,
+            sb_len_wr: 0,
+            host_status: 0,
+            driver_status: 0,
+            resid: 0,
+            duration: 0,
+            info: 0,
+        };
+
+        // SAFETY: the header points to the command, sense and data buffers, which outlive
+        // the call and are as large as the header says.
+        let result = unsafe {
+            libc::ioctl(
+                self.file.as_raw_fd(),
+                SG_IO as _,
+                &mut header as *mut SgIoHeader,
+            )
+        };
+
+        if result < 0 {
+            return Err(DeviceError::Command { code: last_error() });
+        }
+
+        if header.info & SG_INFO_OK_MASK != 0 {
+            if header.sb_len_wr > 0 {
+                return Err(rejected(&sense[..usize::from(header.sb_len_wr)]));
+            }
+
+            return Err(DeviceError::Command { code: libc::EIO });
+        }
+
+        let resid = usize::try_from(header.resid).unwrap_or(0);
+
+        Ok(buffer.len().saturating_sub(resid))
+    }
+}
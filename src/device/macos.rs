@@ -0,0 +1,77 @@
+use std::{fs::File, io, os::fd::AsRawFd, os::unix::fs::OpenOptionsExt};
+
+use crate::DeviceError;
+
+/// `kCDTOCFormatTEXT` format of the request.
+const FORMAT_TEXT: u8 = 0x05;
+
+/// Internal structure. `dk_cd_read_toc_t` from `<IOKit/storage/IOCDMediaBSDClient.h>`.
+#[repr(C)]
+struct ReadTocRequest {
+    format: u8,
+    format_as_time: u8,
+    reserved0: [u8; 5],
+    address: u8,
+    reserved1: [u8; 6],
+    buffer_length: u16,
+    buffer: *mut libc::c_void,
+}
+
+/// `DKIOCCDREADTOC` ioctl request, `_IOWR('d', 0x20, dk_cd_read_toc_t)`.
+const DKIOCCDREADTOC: libc::c_ulong = 0xc000_0000
+    | ((size_of::<ReadTocRequest>() as libc::c_ulong & 0x1fff) << 16)
+    | (b'd' as libc::c_ulong) << 8
+    | 0x20;
+
+/// Internal structure. An opened drive.
+pub(super) struct Device {
+    file: File,
+}
+
+impl Device {
+    /// Internal function. Opens the drive, e.g. `/dev/rdisk2`.
+    pub(super) fn open(path: &str) -> Result<Self, DeviceError> {
+        let file = File::options()
+            .read(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(path)
+            .map_err(|error| DeviceError::Open {
+                code: error.raw_os_error().unwrap_or(0),
+            })?;
+
+        Ok(Self { file })
+    }
+
+    /// Internal method. Reads CD-Text into the buffer, returning the number of bytes read.
+    pub(super) fn read_cd_text(&self, buffer: &mut [u8]) -> Result<usize, DeviceError> {
+        let length = u16::try_from(buffer.len()).unwrap_or(u16::MAX);
+
+        let mut request = ReadTocRequest {
+            format: FORMAT_TEXT,
+            format_as_time: 0,
+            reserved0: [0; 5],
+            address: 0,
+            reserved1: [0; 6],
+            buffer_length: length,
+            buffer: buffer.as_mut_ptr().cast(),
+        };
+
+        // SAFETY: the request points to the buffer, which outlives the call
+        // and is as large as the request says.
+        let result = unsafe {
+            libc::ioctl(
+                self.file.as_raw_fd(),
+                DKIOCCDREADTOC,
+                &mut request as *mut ReadTocRequest,
+            )
+        };
+
+        if result < 0 {
+            let code = io::Error::last_os_error().raw_os_error().unwrap_or(0);
+            return Err(DeviceError::Command { code });
+        }
+
+        // The driver reports how much of the buffer it has filled.
+        Ok(usize::from(request.buffer_length.min(length)))
+    }
+}
@@ -0,0 +1,144 @@
+use alloc::{vec, vec::Vec};
+use core::{ffi::c_void, iter, ptr};
+use std::{
+    io,
+    os::windows::io::{AsRawHandle, FromRawHandle, OwnedHandle},
+};
+
+use crate::DeviceError;
+
+/// `IOCTL_CDROM_READ_TOC_EX` control code of the CD-ROM class driver.
+const IOCTL_CDROM_READ_TOC_EX: u32 = 0x24054;
+/// `CDROM_READ_TOC_EX_FORMAT_CDTEXT` format of the request.
+const FORMAT_CDTEXT: u8 = 0x05;
+/// The driver rejects output buffers that don't fit the header and one pack.
+const MIN_OUTPUT_LENGTH: usize = 4 + 18;
+
+const GENERIC_READ: u32 = 0x8000_0000;
+const FILE_SHARE_READ: u32 = 0x1;
+const FILE_SHARE_WRITE: u32 = 0x2;
+const OPEN_EXISTING: u32 = 3;
+const ERROR_MORE_DATA: i32 = 234;
+
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn CreateFileW(
+        file_name: *const u16,
+        desired_access: u32,
+        share_mode: u32,
+        security_attributes: *mut c_void,
+        creation_disposition: u32,
+        flags_and_attributes: u32,
+        template_file: *mut c_void,
+    ) -> *mut c_void;
+
+    fn DeviceIoControl(
+        device: *mut c_void,
+        io_control_code: u32,
+        in_buffer: *const c_void,
+        in_buffer_size: u32,
+        out_buffer: *mut c_void,
+        out_buffer_size: u32,
+        bytes_returned: *mut u32,
+        overlapped: *mut c_void,
+    ) -> i32;
+}
+
+/// Internal function. Returns the code of the last OS error.
+fn last_error() -> i32 {
+    io::Error::last_os_error().raw_os_error().unwrap_or(0)
+}
+
+/// Internal function. Turns a drive letter, like `D:`, into a device path.
+/// Device paths are left as they are.
+fn device_path(path: &str) -> Vec<u16> {
+    let path = path.trim_end_matches('\\');
+
+    let prefix = match path.as_bytes() {
+        [letter] | [letter, b':'] if letter.is_ascii_alphabetic() => "\\\\.\\",
+        _ => "",
+    };
+
+    let suffix = if path.len() == 1 { ":" } else { "" };
+
+    prefix
+        .encode_utf16()
+        .chain(path.encode_utf16())
+        .chain(suffix.encode_utf16())
+        .chain(iter::once(0))
+        .collect()
+}
+
+/// Internal structure. An opened drive.
+pub(super) struct Device {
+    handle: OwnedHandle,
+}
+
+impl Device {
+    /// Internal function. Opens the drive. Reading the TOC needs no write access,
+    /// so administrator rights are not required.
+    pub(super) fn open(path: &str) -> Result<Self, DeviceError> {
+        let path = device_path(path);
+
+        // SAFETY: the path is a NUL-terminated UTF-16 string, other pointers may be null.
+        let handle = unsafe {
+            CreateFileW(
+                path.as_ptr(),
+                GENERIC_READ,
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                ptr::null_mut(),
+                OPEN_EXISTING,
+                0,
+                ptr::null_mut(),
+            )
+        };
+
+        // INVALID_HANDLE_VALUE
+        if handle as isize == -1 {
+            return Err(DeviceError::Open { code: last_error() });
+        }
+
+        // SAFETY: the handle is valid and owned by nothing else.
+        let handle = unsafe { OwnedHandle::from_raw_handle(handle) };
+
+        Ok(Self { handle })
+    }
+
+    /// Internal method. Reads CD-Text into the buffer, returning the number of bytes read.
+    pub(super) fn read_cd_text(&self, buffer: &mut [u8]) -> Result<usize, DeviceError> {
+        // CDROM_READ_TOC_EX: format, session/track number and two reserved bytes.
+        let request = [FORMAT_CDTEXT, 0, 0, 0];
+        let mut output = vec![0u8; buffer.len().max(MIN_OUTPUT_LENGTH)];
+        let mut returned = 0u32;
+
+        // SAFETY: the buffers are valid and as large as passed, the call is synchronous.
+        let result = unsafe {
+            DeviceIoControl(
+                self.handle.as_raw_handle(),
+                IOCTL_CDROM_READ_TOC_EX,
+                request.as_ptr().cast(),
+                request.len() as u32,
+                output.as_mut_ptr().cast(),
+                output.len() as u32,
+                &mut returned,
+                ptr::null_mut(),
+            )
+        };
+
+        if result == 0 {
+            let code = last_error();
+
+            // The output is filled up, there's just more of it than fits.
+            if code != ERROR_MORE_DATA {
+                return Err(DeviceError::Command { code });
+            }
+
+            returned = output.len() as u32;
+        }
+
+        let length = (returned as usize).min(buffer.len());
+        buffer[..length].copy_from_slice(&output[..length]);
+
+        Ok(length)
+    }
+}
@@ -0,0 +1,29 @@
+use crate::CDTextError;
+
+/// What the parser did to recover from a problem.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Recovery {
+    /// The pack was skipped.
+    PackSkipped,
+    /// The data was used as is.
+    DataUsed,
+    /// Reassembly was restarted from the pack header; some text may be lost or incomplete.
+    Resynchronized,
+    /// Text was decoded with replacement characters.
+    TextReplaced,
+    /// Text was returned as raw data.
+    TextKeptAsData,
+    /// Parsing stopped, so entries following the problem are missing.
+    ParsingStopped,
+}
+
+/// A problem the parser has recovered from.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diagnostic {
+    /// What went wrong and where.
+    pub problem: CDTextError,
+    /// What the parser did about it.
+    pub recovery: Recovery,
+}
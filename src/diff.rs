@@ -0,0 +1,196 @@
+use alloc::vec::Vec;
+
+use crate::{
+    BlockSizeInfo, CDText, CDTextEntry, CDTextEntryDataType, CDTextError, CDTextPackType,
+    CDTextTrackNumber, Field, Language,
+};
+
+/// A field that differs between two documents.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Difference {
+    /// Block of the field in the first document, or in the second one if the block is missing
+    /// from the first.
+    pub block_number: u8,
+    /// Language of the block, as declared by Block Size Info.
+    pub language: Option<Language>,
+    pub pack_type: CDTextPackType,
+    pub track_number: CDTextTrackNumber,
+    /// The field in the first document, `None` if it's missing there.
+    pub left: Option<Field>,
+    /// The field in the second document, `None` if it's missing there.
+    pub right: Option<Field>,
+}
+
+/// Internal structure. Identifies a field regardless of how it's stored.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+struct Key {
+    /// Blocks are matched by language. Blocks without Block Size Info are matched by number.
+    language_code: Option<u8>,
+    block_number: Option<u8>,
+    pack_type: u8,
+    track: u8,
+}
+
+/// Internal structure. Fields of a document by their keys.
+struct Fields {
+    fields: Vec<(Key, u8, Field)>,
+}
+
+impl Fields {
+    fn new(entries: &[CDTextEntry]) -> Self {
+        let language = |block_number: u8| {
+            BlockSizeInfo::from_entries(entries, block_number)?.language(block_number)
+        };
+
+        let fields = entries
+            .iter()
+            .filter(|entry| match &entry.data {
+                // Size Info only describes how the rest is stored.
+                CDTextEntryDataType::BlockSizeInfo(_) => false,
+                // Empty strings are written for tracks without text.
+                CDTextEntryDataType::String(text) => !text.is_empty(),
+                _ => true,
+            })
+            .map(|entry| {
+                let language_code = language(entry.block_number).map(u8::from);
+                let key = Key {
+                    language_code,
+                    block_number: language_code.is_none().then_some(entry.block_number),
+                    pack_type: u8::from(entry.entry_type),
+                    track: u8::from(entry.track_number),
+                };
+
+                (key, entry.block_number, entry.field())
+            })
+            .collect();
+
+        Self { fields }
+    }
+
+    fn get(&self, key: &Key) -> Option<&(Key, u8, Field)> {
+        self.fields.iter().find(|(k, _, _)| k == key)
+    }
+}
+
+/// Compares the fields of two documents, e.g. rips of the same disc from different drives.
+///
+/// Blocks are matched by language, so the comparison doesn't depend on block numbers.
+/// Details of how the fields are stored, like sequence numbers, CRCs and Block Size Info,
+/// are ignored, as are empty strings.
+pub fn diff(left: &[CDTextEntry], right: &[CDTextEntry]) -> Vec<Difference> {
+    let left = Fields::new(left);
+    let right = Fields::new(right);
+
+    let mut keys: Vec<Key> = left
+        .fields
+        .iter()
+        .chain(&right.fields)
+        .map(|&(key, _, _)| key)
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let left = left.get(&key);
+            let right = right.get(&key);
+
+            if left.map(|(_, _, field)| field) == right.map(|(_, _, field)| field) {
+                return None;
+            }
+
+            let block_number = left
+                .or(right)
+                .map_or(0, |&(_, block_number, _)| block_number);
+
+            Some(Difference {
+                block_number,
+                language: key.language_code.map(Language::from),
+                pack_type: CDTextPackType::from(key.pack_type),
+                track_number: CDTextTrackNumber::from(key.track),
+                left: left.map(|(_, _, field)| field.clone()),
+                right: right.map(|(_, _, field)| field.clone()),
+            })
+        })
+        .collect()
+}
+
+impl CDText<'_> {
+    /// Compares the fields with the ones of the other document, see [`diff`].
+    pub fn diff(&self, other: &CDText) -> Result<Vec<Difference>, CDTextError> {
+        Ok(diff(&self.parse()?, &other.parse()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockBuilder, CDTextBuilder};
+    use alloc::string::String;
+
+    #[test]
+    fn blocks_matched_by_language() {
+        let left = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .with_block(BlockBuilder::new(Language::German).with_text(
+                CDTextPackType::Title,
+                0,
+                "Titel",
+            ))
+            .build()
+            .unwrap();
+        let right = CDTextBuilder::from_blocks([
+            BlockBuilder::new(Language::German).with_text(CDTextPackType::Title, 0, "Titel"),
+            BlockBuilder::new(Language::English)
+                .with_text(CDTextPackType::Title, 0, "Album")
+                .with_text(CDTextPackType::Title, 1, "First (live)")
+                .with_text(CDTextPackType::Performers, 0, "Band"),
+        ])
+        .build()
+        .unwrap();
+
+        let left = CDText::from_data(&left);
+        let right = CDText::from_data(&right);
+
+        assert_eq!(
+            left.diff(&right).unwrap(),
+            [
+                Difference {
+                    block_number: 0,
+                    language: Some(Language::English),
+                    pack_type: CDTextPackType::Title,
+                    track_number: CDTextTrackNumber::Track(1),
+                    left: Some(Field::Title(String::from("First"))),
+                    right: Some(Field::Title(String::from("First (live)"))),
+                },
+                Difference {
+                    block_number: 1,
+                    language: Some(Language::English),
+                    pack_type: CDTextPackType::Performers,
+                    track_number: CDTextTrackNumber::WholeAlbum,
+                    left: None,
+                    right: Some(Field::Performer(String::from("Band"))),
+                },
+            ]
+        );
+        assert!(left.diff(&left).unwrap().is_empty());
+    }
+
+    #[test]
+    fn storage_ignored() {
+        let builder = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Same")
+            .with_text(CDTextPackType::Title, 1, "Same")
+            .with_text(CDTextPackType::Title, 2, "Same");
+        let plain = builder.build().unwrap();
+        let compressed = builder.with_tab_compression(true).build().unwrap();
+        assert_ne!(plain, compressed);
+
+        assert_eq!(
+            CDText::from_data(&plain).diff(&CDText::from_data(&compressed)),
+            Ok(Vec::new())
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    CDText, CDTextEntry, CDTextEntryDataType, CDTextError, CDTextPackType, CDTextTrackNumber,
+    Genre, Isrc, Upc,
+};
+
+/// Text information of a single track.
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrackInfo {
+    pub number: u8,
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    pub songwriter: Option<String>,
+    pub composer: Option<String>,
+    pub arranger: Option<String>,
+    pub message: Option<String>,
+    pub isrc: Option<Isrc>,
+}
+
+/// Text information of the whole disc, aggregated from the parsed entries.
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Disc {
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    pub songwriter: Option<String>,
+    pub composer: Option<String>,
+    pub arranger: Option<String>,
+    pub message: Option<String>,
+    pub disc_id: Option<String>,
+    pub upc: Option<Upc>,
+    pub genre: Option<Genre>,
+    /// Tracks sorted by number.
+    pub tracks: Vec<TrackInfo>,
+}
+
+impl Disc {
+    /// Collects the entries into the disc model.
+    ///
+    /// Only entries of the first block are used, other blocks hold the same text in other languages.
+    /// Tracks declared by Block Size Info are present even if they have no text.
+    pub fn from_entries(entries: &[CDTextEntry]) -> Self {
+        let Some(block_number) = entries.iter().map(|entry| entry.block_number).min() else {
+            return Self::default();
+        };
+
+        Self::from_block(entries, block_number)
+    }
+
+    /// Collects the entries of the block into the disc model.
+    pub fn from_block(entries: &[CDTextEntry], block_number: u8) -> Self {
+        let mut disc = Self::default();
+        let entries = || {
+            entries
+                .iter()
+                .filter(move |entry| entry.block_number == block_number)
+        };
+
+        for entry in entries() {
+            if let CDTextEntryDataType::BlockSizeInfo(size_info) = &entry.data {
+                for number in size_info.first_track..=size_info.last_track {
+                    disc.track_mut(number);
+                }
+            }
+        }
+
+        for entry in entries() {
+            match entry.track_number {
+                CDTextTrackNumber::WholeAlbum => disc.apply(entry),
+                CDTextTrackNumber::Track(number) => disc.track_mut(number).apply(entry),
+            }
+        }
+
+        disc.tracks.sort_by_key(|track| track.number);
+        disc
+    }
+
+    /// Returns the track with the number.
+    pub fn track(&self, number: u8) -> Option<&TrackInfo> {
+        self.tracks.iter().find(|track| track.number == number)
+    }
+
+    /// Internal method. Returns the track with the number, creating it if needed.
+    fn track_mut(&mut self, number: u8) -> &mut TrackInfo {
+        let index = match self.tracks.iter().position(|track| track.number == number) {
+            Some(index) => index,
+            None => {
+                self.tracks.push(TrackInfo {
+                    number,
+                    ..TrackInfo::default()
+                });
+                self.tracks.len() - 1
+            }
+        };
+
+        &mut self.tracks[index]
+    }
+
+    /// Internal method. Stores the album entry.
+    fn apply(&mut self, entry: &CDTextEntry) {
+        let field = match entry.entry_type {
+            CDTextPackType::Title => &mut self.title,
+            CDTextPackType::Performers => &mut self.performer,
+            CDTextPackType::Songwriters => &mut self.songwriter,
+            CDTextPackType::Composers => &mut self.composer,
+            CDTextPackType::Arrangers => &mut self.arranger,
+            CDTextPackType::Message => &mut self.message,
+            CDTextPackType::DiscID => &mut self.disc_id,
+            _ => {
+                match &entry.data {
+                    CDTextEntryDataType::Upc(upc) => {
+                        self.upc.get_or_insert(*upc);
+                    }
+                    CDTextEntryDataType::Genre(genre) => {
+                        self.genre.get_or_insert_with(|| genre.clone());
+                    }
+                    _ => {}
+                }
+
+                return;
+            }
+        };
+
+        set_text(field, entry);
+    }
+}
+
+impl TrackInfo {
+    /// Internal method. Stores the track entry.
+    fn apply(&mut self, entry: &CDTextEntry) {
+        let field = match entry.entry_type {
+            CDTextPackType::Title => &mut self.title,
+            CDTextPackType::Performers => &mut self.performer,
+            CDTextPackType::Songwriters => &mut self.songwriter,
+            CDTextPackType::Composers => &mut self.composer,
+            CDTextPackType::Arrangers => &mut self.arranger,
+            CDTextPackType::Message => &mut self.message,
+            _ => {
+                if let CDTextEntryDataType::Isrc(isrc) = &entry.data {
+                    self.isrc.get_or_insert(*isrc);
+                }
+
+                return;
+            }
+        };
+
+        set_text(field, entry);
+    }
+}
+
+/// Internal function. Stores the text of the entry, unless the field is set already.
+/// Empty strings mean there's no text.
+fn set_text(field: &mut Option<String>, entry: &CDTextEntry) {
+    if let CDTextEntryDataType::String(text) = &entry.data
+        && !text.is_empty()
+    {
+        field.get_or_insert_with(|| text.clone());
+    }
+}
+
+impl CDText<'_> {
+    /// Parses the data into the disc model. See [`Disc::from_entries`].
+    pub fn disc(&self) -> Result<Disc, CDTextError> {
+        Ok(Disc::from_entries(&self.parse()?))
+    }
+}
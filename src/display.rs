@@ -0,0 +1,192 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use crate::{CDTextEntry, CDTextEntryDataType};
+use crate::{CDTextPack, CDTextPackType, CDTextTrackNumber};
+
+impl fmt::Display for CDTextTrackNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WholeAlbum => f.write_str("Album"),
+            Self::Track(number) => write!(f, "Track #{number}"),
+        }
+    }
+}
+
+impl fmt::Display for CDTextPackType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Title => "Title",
+            Self::Performers => "Performer",
+            Self::Songwriters => "Songwriter",
+            Self::Composers => "Composer",
+            Self::Arrangers => "Arranger",
+            Self::Message => "Message",
+            Self::DiscID => "Disc ID",
+            Self::Genre => "Genre",
+            Self::TOC => "TOC",
+            Self::AdditionalTOC => "Additional TOC",
+            Self::ClosedInfo => "Closed Information",
+            Self::Code => "UPC/EAN/ISRC",
+            Self::BlockSizeInfo => "Block Size Info",
+            Self::Reserved(value) => return write!(f, "Reserved ({value:#04x})"),
+            Self::Unknown(value) => return write!(f, "Unknown ({value:#04x})"),
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// Header fields and the payload in hex, e.g.
+/// `Title pack 3 (Track #1, block 0, character 5): 52 69 76 65 72 00 ...`.
+impl fmt::Display for CDTextPack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} pack {} ({}, block {}, character {}",
+            self.pack_type,
+            self.seq_counter,
+            self.track_number,
+            self.block_number,
+            self.character_position
+        )?;
+
+        if self.is_double_byte_characters {
+            f.write_str(", double-byte")?;
+        }
+
+        f.write_str("):")?;
+
+        for byte in self.payload {
+            write!(f, " {byte:02x}")?;
+        }
+
+        if !self.crc_valid {
+            f.write_str(" (CRC mismatch)")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Text as is, codes and genres as written on the disc, binary data in hex.
+#[cfg(feature = "alloc")]
+impl fmt::Display for CDTextEntryDataType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String(text) => f.write_str(text),
+            Self::Data(data) => {
+                for (index, byte) in data.iter().enumerate() {
+                    let separator = if index == 0 { "" } else { " " };
+                    write!(f, "{separator}{byte:02x}")?;
+                }
+
+                Ok(())
+            }
+            Self::Genre(genre) => write!(f, "{genre}"),
+            Self::Upc(upc) => write!(f, "{upc}"),
+            Self::Isrc(isrc) => write!(f, "{isrc}"),
+            Self::Toc(toc) => write!(
+                f,
+                "tracks {}-{}, lead-out at {}",
+                toc.first_track, toc.last_track, toc.lead_out
+            ),
+            Self::AdditionalToc(additional_toc) => {
+                write!(f, "{} intervals", additional_toc.intervals.len())
+            }
+            Self::BlockSizeInfo(size_info) => {
+                write!(
+                    f,
+                    "{}, tracks {}-{}",
+                    size_info.charset(),
+                    size_info.first_track,
+                    size_info.last_track
+                )?;
+
+                if size_info.is_copy_protected() {
+                    f.write_str(", copy protected")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The track, the type and the data, e.g. `Track #3 Title: River Garden`.
+/// Entries of blocks other than the first one have the block number, e.g.
+/// `Track #3 Title (block 1): Fluss Garten`.
+#[cfg(feature = "alloc")]
+impl fmt::Display for CDTextEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.track_number, self.entry_type)?;
+
+        if self.block_number != 0 {
+            write!(f, " (block {})", self.block_number)?;
+        }
+
+        write!(f, ": {}", self.data)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{BlockBuilder, CDText, CDTextBuilder, Language};
+    use alloc::{format, string::String, vec::Vec};
+
+    #[test]
+    fn names() {
+        assert_eq!(format!("{}", CDTextTrackNumber::WholeAlbum), "Album");
+        assert_eq!(format!("{}", CDTextTrackNumber::Track(3)), "Track #3");
+        assert_eq!(format!("{}", CDTextPackType::Performers), "Performer");
+        assert_eq!(format!("{}", CDTextPackType::Code), "UPC/EAN/ISRC");
+        assert_eq!(
+            format!("{}", CDTextPackType::Reserved(0x8a)),
+            "Reserved (0x8a)"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                CDTextEntryDataType::Data(Vec::from([0x00, 0x1f, 0xff]))
+            ),
+            "00 1f ff"
+        );
+    }
+
+    #[test]
+    fn entries_and_packs() {
+        let mut data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "River Garden")
+            .with_block(BlockBuilder::new(Language::German).with_text(
+                CDTextPackType::Title,
+                1,
+                "Fluss Garten",
+            ))
+            .build()
+            .unwrap();
+        let cdtext = CDText::from_data(&data);
+
+        let lines: Vec<String> = cdtext
+            .parse()
+            .unwrap()
+            .iter()
+            .map(|entry| format!("{entry}"))
+            .collect();
+        assert_eq!(lines[0], "Album Title: Album");
+        assert_eq!(lines[1], "Track #1 Title: River Garden");
+        assert!(lines.contains(&String::from("Track #1 Title (block 1): Fluss Garten")));
+        assert!(lines.contains(&String::from(
+            "Album Block Size Info: ISO 8859-1, tracks 1-1"
+        )));
+
+        data[16] ^= 0xff;
+        let pack = CDText::from_data(&data).iter_pack_chunks().next().unwrap();
+        assert_eq!(
+            format!("{pack}"),
+            "Title pack 0 (Album, block 0, character 0): \
+             41 6c 62 75 6d 00 52 69 76 65 72 20 (CRC mismatch)"
+        );
+    }
+}
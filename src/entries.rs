@@ -0,0 +1,164 @@
+use alloc::collections::VecDeque;
+
+use crate::{
+    BorrowedEntry, CDText, CDTextEntry, CDTextError, parser::ParseState, visitor::CDTextVisitor,
+};
+
+/// Internal structure. Holds entries completed by a pack until they are yielded.
+struct Queue(VecDeque<CDTextEntry>);
+
+impl CDTextVisitor for Queue {
+    fn on_album_field(&mut self, entry: BorrowedEntry<'_>) {
+        self.0.push_back(entry.into_owned());
+    }
+
+    fn on_track_field(&mut self, _track: u8, entry: BorrowedEntry<'_>) {
+        self.0.push_back(entry.into_owned());
+    }
+}
+
+/// Iterator over entries, parsing packs only as far as needed to yield the next one.
+/// Created by [`CDText::iter_entries`].
+///
+/// The iterator is fused: after an error or the last entry it yields nothing.
+pub struct Entries<'cdtext, 'data> {
+    cdtext: &'cdtext CDText<'data>,
+    state: ParseState,
+    /// Index of the next pack to parse.
+    pack_index: usize,
+    queue: Queue,
+    /// Error to yield once the entries preceding it are yielded.
+    error: Option<CDTextError>,
+    finished: bool,
+}
+
+impl Iterator for Entries<'_, '_> {
+    type Item = Result<CDTextEntry, CDTextError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(entry) = self.queue.0.pop_front() {
+                return Some(Ok(entry));
+            }
+
+            if let Some(error) = self.error.take() {
+                return Some(Err(error));
+            }
+
+            if self.finished {
+                return None;
+            }
+
+            let (packs, _) = self.cdtext.data.as_chunks::<18>();
+
+            let result = match packs.get(self.pack_index) {
+                Some(pack) => {
+                    let pack = CDText::parse_pack(pack);
+                    let pack_index = self.pack_index;
+                    self.pack_index += 1;
+
+                    self.cdtext
+                        .feed_pack(&mut self.state, pack_index, pack, &mut self.queue)
+                }
+                None => {
+                    self.finished = true;
+
+                    self.cdtext.finish_parsing(&mut self.state, &mut self.queue)
+                }
+            };
+
+            if let Err(error) = result {
+                self.error = Some(error);
+                self.finished = true;
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for Entries<'_, '_> {}
+
+impl<'data> CDText<'data> {
+    /// Returns an iterator over entries, parsing the data lazily as they are requested.
+    ///
+    /// Yields the same entries as [`CDText::parse`], in the same order. Entries decoded
+    /// from binary packs (TOC, Block Size Info) come last, after all the packs are read.
+    /// In strict mode an error is yielded once, after all the entries preceding the damaged pack.
+    pub fn iter_entries(&self) -> Entries<'_, 'data> {
+        Entries {
+            cdtext: self,
+            state: ParseState::new(self, self.options.mode()),
+            pack_index: 0,
+            queue: Queue(VecDeque::new()),
+            error: None,
+            finished: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CDTextBuilder, CDTextEntryDataType, CDTextPackType};
+    use alloc::{string::String, vec::Vec};
+
+    /// Internal function. Returns the data of a disc with two tracks.
+    fn data() -> Vec<u8> {
+        CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .with_text(CDTextPackType::Title, 2, "Second")
+            .with_text(CDTextPackType::Performers, 0, "Band")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn same_as_parse() {
+        let data = data();
+        let cdtext = CDText::from_data(&data);
+
+        let entries: Result<Vec<CDTextEntry>, CDTextError> = cdtext.iter_entries().collect();
+        assert_eq!(entries, cdtext.parse());
+    }
+
+    #[test]
+    fn lazy() {
+        let data = data();
+        let cdtext = CDText::from_data(&data);
+        let mut entries = cdtext.iter_entries();
+
+        let album = entries.next().unwrap().unwrap();
+        assert_eq!(
+            album.data,
+            CDTextEntryDataType::String(String::from("Album"))
+        );
+        assert_eq!(entries.pack_index, 1);
+    }
+
+    #[test]
+    fn error_after_preceding_entries() {
+        let mut data = data();
+        data[18 + 16] ^= 0xff;
+        let cdtext = CDText::from_data(&data);
+
+        let mut entries = cdtext.iter_entries();
+        let titles: Vec<CDTextEntryDataType> = entries
+            .by_ref()
+            .map_while(Result::ok)
+            .map(|entry| entry.data)
+            .collect();
+
+        assert_eq!(
+            titles,
+            [
+                CDTextEntryDataType::String(String::from("Album")),
+                CDTextEntryDataType::String(String::from("First")),
+            ]
+        );
+        assert!(entries.next().is_none());
+        assert!(matches!(
+            cdtext.iter_entries().nth(2),
+            Some(Err(CDTextError::CrcMismatch { pack_index: 1, .. }))
+        ));
+    }
+}
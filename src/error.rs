@@ -0,0 +1,419 @@
+use core::fmt;
+
+#[cfg(feature = "std")]
+use alloc::string::String;
+
+use crate::{CDTextPackType, Charset};
+
+/// Errors that can occur while parsing CD-Text data.
+///
+/// Pack-level variants carry the index of the offending pack and its byte offset
+/// in the buffer the parser was created from (including the service header, if any).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CDTextError {
+    /// The buffer is too short to contain the 4-byte service header.
+    TruncatedHeader { length: usize },
+    /// The data length declared in the service header doesn't match the buffer.
+    /// Both lengths exclude the two length bytes themselves.
+    LengthMismatch { declared: usize, actual: usize },
+    /// The pack type byte is not a known CD-Text pack type.
+    InvalidPackType {
+        pack_index: usize,
+        offset: usize,
+        pack_type: u8,
+    },
+    /// The pack is shorter than 18 bytes. A single pack parsed on its own must be exactly 18 bytes long.
+    ShortPack {
+        pack_index: usize,
+        offset: usize,
+        length: usize,
+    },
+    /// Text payload could not be decoded.
+    InvalidText { pack_index: usize, offset: usize },
+    /// Text is in a charset that cannot be decoded.
+    UnsupportedCharset {
+        pack_index: usize,
+        offset: usize,
+        character_code: u8,
+    },
+    /// UPC/EAN or ISRC code is malformed.
+    InvalidCode { pack_index: usize, offset: usize },
+    /// Text stream ends in the middle of a string.
+    UnterminatedText { pack_index: usize, offset: usize },
+    /// Track number of a text pack doesn't follow from the preceding packs.
+    TrackNumberMismatch {
+        pack_index: usize,
+        offset: usize,
+        expected: u8,
+        found: u8,
+    },
+    /// Character position of a text pack doesn't follow from the preceding packs.
+    CharacterPositionMismatch {
+        pack_index: usize,
+        offset: usize,
+        expected: u8,
+        found: u8,
+    },
+    /// Sequence number of the pack doesn't follow the previous one, i.e. some packs are missing.
+    SequenceGap {
+        pack_index: usize,
+        offset: usize,
+        expected: u8,
+        found: u8,
+    },
+    /// The CRC stored in the pack doesn't match the computed one.
+    CrcMismatch {
+        pack_index: usize,
+        offset: usize,
+        expected: u16,
+        found: u16,
+    },
+}
+
+impl CDTextError {
+    /// Index of the pack the error occurred at, if the error is related to a pack.
+    pub fn pack_index(&self) -> Option<usize> {
+        match *self {
+            Self::TruncatedHeader { .. } | Self::LengthMismatch { .. } => None,
+            Self::InvalidPackType { pack_index, .. }
+            | Self::ShortPack { pack_index, .. }
+            | Self::InvalidText { pack_index, .. }
+            | Self::UnsupportedCharset { pack_index, .. }
+            | Self::InvalidCode { pack_index, .. }
+            | Self::UnterminatedText { pack_index, .. }
+            | Self::TrackNumberMismatch { pack_index, .. }
+            | Self::CharacterPositionMismatch { pack_index, .. }
+            | Self::SequenceGap { pack_index, .. }
+            | Self::CrcMismatch { pack_index, .. } => Some(pack_index),
+        }
+    }
+
+    /// Byte offset the error occurred at.
+    /// Header errors are reported at the start of the buffer.
+    pub fn offset(&self) -> usize {
+        match *self {
+            Self::TruncatedHeader { .. } | Self::LengthMismatch { .. } => 0,
+            Self::InvalidPackType { offset, .. }
+            | Self::ShortPack { offset, .. }
+            | Self::InvalidText { offset, .. }
+            | Self::UnsupportedCharset { offset, .. }
+            | Self::InvalidCode { offset, .. }
+            | Self::UnterminatedText { offset, .. }
+            | Self::TrackNumberMismatch { offset, .. }
+            | Self::CharacterPositionMismatch { offset, .. }
+            | Self::SequenceGap { offset, .. }
+            | Self::CrcMismatch { offset, .. } => offset,
+        }
+    }
+}
+
+impl fmt::Display for CDTextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TruncatedHeader { length } => {
+                write!(f, "buffer is {length} bytes long, too short for the header")?
+            }
+            Self::LengthMismatch { declared, actual } => write!(
+                f,
+                "header declares {declared} bytes of data, but buffer has {actual}"
+            )?,
+            Self::InvalidPackType { pack_type, .. } => {
+                write!(f, "unknown pack type 0x{pack_type:02x}")?
+            }
+            Self::ShortPack { length, .. } => {
+                write!(f, "pack is {length} bytes long, expected 18")?
+            }
+            Self::InvalidText { .. } => write!(f, "invalid text")?,
+            Self::UnsupportedCharset { character_code, .. } => {
+                write!(f, "unsupported character code 0x{character_code:02x}")?
+            }
+            Self::InvalidCode { .. } => write!(f, "invalid UPC/EAN or ISRC code")?,
+            Self::UnterminatedText { .. } => write!(f, "text is not terminated")?,
+            Self::TrackNumberMismatch {
+                expected, found, ..
+            } => write!(f, "expected text of track {expected}, found {found}")?,
+            Self::CharacterPositionMismatch {
+                expected, found, ..
+            } => write!(f, "expected character position {expected}, found {found}")?,
+            Self::SequenceGap {
+                expected, found, ..
+            } => write!(f, "expected sequence number {expected}, found {found}")?,
+            Self::CrcMismatch {
+                expected, found, ..
+            } => write!(
+                f,
+                "CRC mismatch: expected 0x{expected:04x}, found 0x{found:04x}"
+            )?,
+        }
+
+        match self.pack_index() {
+            Some(pack_index) => write!(f, " (pack #{pack_index}, offset 0x{:x})", self.offset()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl core::error::Error for CDTextError {}
+
+/// Errors that can occur while parsing into fixed-capacity buffers, see [`crate::CDText::parse_into`].
+#[cfg(feature = "heapless")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FixedParseError {
+    /// The data violates the specification.
+    Invalid(CDTextError),
+    /// There are more entries than the buffer can hold.
+    TooManyEntries { capacity: usize },
+    /// The text doesn't fit into the capacity of an entry. Refers to the pack the text starts in.
+    TextTooLong {
+        pack_index: usize,
+        offset: usize,
+        capacity: usize,
+    },
+}
+
+#[cfg(feature = "heapless")]
+impl fmt::Display for FixedParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(error) => write!(f, "{error}"),
+            Self::TooManyEntries { capacity } => {
+                write!(f, "more than {capacity} entries")
+            }
+            Self::TextTooLong {
+                pack_index,
+                offset,
+                capacity,
+            } => write!(
+                f,
+                "text is longer than {capacity} bytes (pack #{pack_index}, offset 0x{offset:x})"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl core::error::Error for FixedParseError {}
+
+/// Errors that can occur while building CD-Text data.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BuildError {
+    /// The pack type cannot hold text.
+    NotTextType { pack_type: CDTextPackType },
+    /// Track number is out of range 1-99.
+    InvalidTrackNumber { track: u8 },
+    /// The text has a character that cannot be encoded, or a NUL character.
+    UnencodableText {
+        pack_type: CDTextPackType,
+        track: u8,
+    },
+    /// There are more than 8 blocks.
+    TooManyBlocks { count: usize },
+    /// Text cannot be encoded in the charset.
+    UnsupportedCharset { charset: Charset },
+    /// The block doesn't fit into the lead-in.
+    CapacityExceeded {
+        block_number: u8,
+        packs: usize,
+        limit: usize,
+        hint: CapacityHint,
+    },
+}
+
+/// What can be done to fit the content into the lead-in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CapacityHint {
+    /// The content fits if repeated strings are replaced with TABs,
+    /// see [`crate::CDTextBuilder::with_tab_compression`].
+    EnableTabCompression,
+    /// The text has to be shortened or removed.
+    ShortenText,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotTextType { pack_type } => write!(f, "{pack_type:?} packs cannot hold text"),
+            Self::InvalidTrackNumber { track } => {
+                write!(f, "track number {track} is out of range 1-99")
+            }
+            Self::UnencodableText { pack_type, track } => {
+                write!(f, "{pack_type:?} text of track {track} cannot be encoded")
+            }
+            Self::TooManyBlocks { count } => write!(f, "{count} blocks given, at most 8 allowed"),
+            Self::UnsupportedCharset { charset } => {
+                write!(f, "text cannot be encoded in {charset:?}")
+            }
+            Self::CapacityExceeded {
+                block_number,
+                packs,
+                limit,
+                hint,
+            } => {
+                write!(
+                    f,
+                    "block {block_number} takes {packs} packs, at most {limit} fit"
+                )?;
+
+                match hint {
+                    CapacityHint::EnableTabCompression => write!(f, "; enable TAB compression"),
+                    CapacityHint::ShortenText => write!(f, "; shorten the text"),
+                }
+            }
+        }
+    }
+}
+
+impl core::error::Error for BuildError {}
+
+/// Errors that can occur while reading a CUE sheet. Lines are numbered from 1.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CueError {
+    /// A quoted string is not closed.
+    UnterminatedString { line: usize },
+    /// Track number of `TRACK` command is out of range 1-99.
+    InvalidTrackNumber { line: usize },
+    /// The command has no value.
+    MissingValue { line: usize },
+}
+
+impl fmt::Display for CueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnterminatedString { line } => write!(f, "line {line}: unterminated string"),
+            Self::InvalidTrackNumber { line } => {
+                write!(f, "line {line}: track number is out of range 1-99")
+            }
+            Self::MissingValue { line } => write!(f, "line {line}: missing value"),
+        }
+    }
+}
+
+impl core::error::Error for CueError {}
+
+/// Errors that can occur while loading a CUE sheet along with its CD-Text file.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CueFileError {
+    /// The file cannot be read. Holds the path and the OS error code.
+    Read { path: String, code: i32 },
+    /// The CUE sheet is malformed.
+    Cue(CueError),
+    /// The CD-Text file cannot be parsed.
+    CDText(CDTextError),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for CueFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read { path, code } => write!(f, "cannot read {path} (os error {code})"),
+            Self::Cue(error) => write!(f, "CUE sheet: {error}"),
+            Self::CDText(error) => write!(f, "CD-Text file: {error}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for CueFileError {}
+
+/// Errors that can occur while reading a Sony Input Sheet. Lines are numbered from 1.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputSheetError {
+    /// `Text Code` is unknown, or the charset cannot be decoded.
+    UnsupportedTextCode { line: usize },
+    /// `Language Code` is not a known language name.
+    UnknownLanguage { line: usize },
+    /// Track number of the keyword is out of range 1-99.
+    InvalidTrackNumber { line: usize },
+    /// The value cannot be decoded in the charset given by `Text Code`.
+    InvalidText { line: usize },
+}
+
+impl fmt::Display for InputSheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedTextCode { line } => write!(f, "line {line}: unsupported text code"),
+            Self::UnknownLanguage { line } => write!(f, "line {line}: unknown language"),
+            Self::InvalidTrackNumber { line } => {
+                write!(f, "line {line}: track number is out of range 1-99")
+            }
+            Self::InvalidText { line } => write!(f, "line {line}: invalid text"),
+        }
+    }
+}
+
+impl core::error::Error for InputSheetError {}
+
+/// Errors that can occur while reading a cdrdao toc-file. Lines are numbered from 1.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TocFileError {
+    /// A quoted string is not closed.
+    UnterminatedString { line: usize },
+    /// The `CD_TEXT` section is malformed.
+    Syntax { line: usize },
+    /// Block number of `LANGUAGE` is out of range 0-7.
+    InvalidBlockNumber { line: usize },
+    /// The language of `LANGUAGE_MAP` is neither `EN` nor a language code.
+    UnknownLanguage { line: usize },
+    /// There are more than 99 tracks.
+    TooManyTracks { line: usize },
+}
+
+impl fmt::Display for TocFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnterminatedString { line } => write!(f, "line {line}: unterminated string"),
+            Self::Syntax { line } => write!(f, "line {line}: syntax error in CD_TEXT"),
+            Self::InvalidBlockNumber { line } => {
+                write!(f, "line {line}: block number is out of range 0-7")
+            }
+            Self::UnknownLanguage { line } => write!(f, "line {line}: unknown language"),
+            Self::TooManyTracks { line } => write!(f, "line {line}: more than 99 tracks"),
+        }
+    }
+}
+
+impl core::error::Error for TocFileError {}
+
+/// Errors that can occur while reading CD-Text from a drive.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeviceError {
+    /// Reading from drives is not supported on this platform.
+    Unsupported,
+    /// The device cannot be opened. Holds the OS error code.
+    Open { code: i32 },
+    /// The command cannot be sent to the drive. Holds the OS error code.
+    Command { code: i32 },
+    /// The drive rejected the command, e.g. because there's no disc or it has no CD-Text.
+    /// Holds the sense key, the additional sense code and its qualifier.
+    Rejected { sense_key: u8, asc: u8, ascq: u8 },
+}
+
+impl fmt::Display for DeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "reading from drives is not supported on this platform"),
+            Self::Open { code } => write!(f, "cannot open the device (os error {code})"),
+            Self::Command { code } => write!(f, "cannot send the command (os error {code})"),
+            Self::Rejected {
+                sense_key,
+                asc,
+                ascq,
+            } => write!(
+                f,
+                "the drive rejected the command (sense key {sense_key:#x}, ASC {asc:#04x}, ASCQ {ascq:#04x})"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for DeviceError {}
@@ -0,0 +1,308 @@
+use alloc::{boxed::Box, ffi::CString, string::ToString, vec::Vec};
+use core::{ffi::c_char, ptr, slice};
+
+use crate::{CDText, CDTextEntry, CDTextEntryDataType, ParseMode, ParserOptions, TextDecoding};
+
+/// Version of the C API, increased on incompatible changes.
+const API_VERSION: u32 = 1;
+
+/// Tolerate violations of the specification, `CDTEXT_PARSE_LENIENT` in `cdtext.h`.
+const PARSE_LENIENT: u32 = 1 << 0;
+/// Replace undecodable text with U+FFFD, `CDTEXT_PARSE_LOSSY_TEXT` in `cdtext.h`.
+const PARSE_LOSSY_TEXT: u32 = 1 << 1;
+/// Keep undecodable text as binary entries, `CDTEXT_PARSE_RAW_TEXT` in `cdtext.h`.
+const PARSE_RAW_TEXT: u32 = 1 << 2;
+/// Keep "same as previous track" TABs, `CDTEXT_PARSE_KEEP_TABS` in `cdtext.h`.
+const PARSE_KEEP_TABS: u32 = 1 << 3;
+
+/// Internal structure. An entry as seen from C, `CdtextEntry` in `cdtext.h`.
+#[repr(C)]
+pub struct CdtextEntry {
+    pub pack_type: u8,
+    /// Track number, 0 for the whole album.
+    pub track: u8,
+    pub block: u8,
+    /// NUL-terminated UTF-8 text, owned by the document. Null if the entry holds no text.
+    pub text: *const c_char,
+}
+
+/// Internal structure. Parsed data owned by C code, opaque `CdtextDocument` in `cdtext.h`.
+pub struct CdtextDocument {
+    entries: Vec<(CDTextEntry, Option<CString>)>,
+}
+
+/// Internal function. Returns the text of the entry: strings, codes and supplementary genre text.
+fn entry_text(entry: &CDTextEntry) -> Option<CString> {
+    let text = match &entry.data {
+        CDTextEntryDataType::String(text) => text.as_str(),
+        CDTextEntryDataType::Upc(upc) => upc.as_str(),
+        CDTextEntryDataType::Isrc(isrc) => isrc.as_str(),
+        CDTextEntryDataType::Genre(genre) => genre.text.as_deref()?,
+        _ => return None,
+    };
+
+    CString::new(text).ok()
+}
+
+/// Returns the version of the C API.
+#[unsafe(no_mangle)]
+pub extern "C" fn cdtext_api_version() -> u32 {
+    API_VERSION
+}
+
+/// Internal function. Returns the parser options for the `CDTEXT_PARSE_*` flags,
+/// or `None` if there are unknown flags or the text decoding flags conflict.
+fn options_from_flags(flags: u32) -> Option<ParserOptions> {
+    let known = PARSE_LENIENT | PARSE_LOSSY_TEXT | PARSE_RAW_TEXT | PARSE_KEEP_TABS;
+
+    if flags & !known != 0 {
+        return None;
+    }
+
+    let mode = match flags & PARSE_LENIENT {
+        0 => ParseMode::Strict,
+        _ => ParseMode::Lenient,
+    };
+
+    let text_decoding = match (flags & PARSE_LOSSY_TEXT != 0, flags & PARSE_RAW_TEXT != 0) {
+        (false, false) => TextDecoding::Strict,
+        (true, false) => TextDecoding::Lossy,
+        (false, true) => TextDecoding::Raw,
+        (true, true) => return None,
+    };
+
+    Some(
+        ParserOptions::strict()
+            .with_mode(mode)
+            .with_text_decoding(text_decoding)
+            .with_tab_expansion(flags & PARSE_KEEP_TABS == 0),
+    )
+}
+
+/// Internal function. Stores the error message into `error` if it's not null.
+///
+/// # Safety
+///
+/// `error` must be null or point to a writable pointer.
+unsafe fn store_error(error: *mut *mut c_char, message: &str) {
+    if !error.is_null() {
+        let message = CString::new(message).unwrap_or_default();
+
+        // SAFETY: the caller guarantees that the pointer is writable.
+        unsafe { *error = message.into_raw() };
+    }
+}
+
+/// Parses the data, with or without the 4-byte header. The data is not used after the call.
+/// Returns null on failure, storing the error message into `error` if it's not null;
+/// the message is freed with `cdtext_string_free`.
+///
+/// # Safety
+///
+/// `data` must point to `length` readable bytes, or be null if `length` is 0.
+/// `error` must be null or point to a writable pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cdtext_parse(
+    data: *const u8,
+    length: usize,
+    error: *mut *mut c_char,
+) -> *mut CdtextDocument {
+    // SAFETY: the caller upholds the same requirements.
+    unsafe { cdtext_parse_with_options(data, length, 0, error) }
+}
+
+/// Parses the data like `cdtext_parse`, with the options given by `CDTEXT_PARSE_*` flags
+/// combined with bitwise OR. 0 is the same as `cdtext_parse`. Fails on unknown flags,
+/// and if both `CDTEXT_PARSE_LOSSY_TEXT` and `CDTEXT_PARSE_RAW_TEXT` are set.
+///
+/// # Safety
+///
+/// `data` must point to `length` readable bytes, or be null if `length` is 0.
+/// `error` must be null or point to a writable pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cdtext_parse_with_options(
+    data: *const u8,
+    length: usize,
+    flags: u32,
+    error: *mut *mut c_char,
+) -> *mut CdtextDocument {
+    let data = if data.is_null() {
+        &[]
+    } else {
+        // SAFETY: the caller guarantees that the data is readable.
+        unsafe { slice::from_raw_parts(data, length) }
+    };
+
+    let Some(options) = options_from_flags(flags) else {
+        // SAFETY: the caller guarantees that the pointer is writable.
+        unsafe { store_error(error, "invalid parse flags") };
+        return ptr::null_mut();
+    };
+
+    match CDText::new(data).with_options(options).parse() {
+        Ok(entries) => {
+            let entries = entries
+                .into_iter()
+                .map(|entry| {
+                    let text = entry_text(&entry);
+                    (entry, text)
+                })
+                .collect();
+
+            Box::into_raw(Box::new(CdtextDocument { entries }))
+        }
+        Err(parse_error) => {
+            // SAFETY: the caller guarantees that the pointer is writable.
+            unsafe { store_error(error, &parse_error.to_string()) };
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Frees the document. Does nothing if it's null.
+///
+/// # Safety
+///
+/// `document` must be null or returned by `cdtext_parse` or `cdtext_parse_with_options`
+/// and not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cdtext_free(document: *mut CdtextDocument) {
+    if !document.is_null() {
+        // SAFETY: the document was allocated by `cdtext_parse`.
+        drop(unsafe { Box::from_raw(document) });
+    }
+}
+
+/// Returns the number of entries in the document, 0 if it's null.
+///
+/// # Safety
+///
+/// `document` must be null or a valid document.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cdtext_entry_count(document: *const CdtextDocument) -> usize {
+    // SAFETY: the caller guarantees that the document is valid.
+    unsafe { document.as_ref() }.map_or(0, |document| document.entries.len())
+}
+
+/// Stores the entry with the index into `entry`, in the order of the data.
+/// Returns false if there's no such entry. The text stays valid until the document is freed.
+///
+/// # Safety
+///
+/// `document` must be null or a valid document, `entry` must point to a writable `CdtextEntry`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cdtext_entry(
+    document: *const CdtextDocument,
+    index: usize,
+    entry: *mut CdtextEntry,
+) -> bool {
+    // SAFETY: the caller guarantees that the document is valid.
+    let Some((item, text)) = unsafe { document.as_ref() }.and_then(|d| d.entries.get(index)) else {
+        return false;
+    };
+
+    if entry.is_null() {
+        return false;
+    }
+
+    let value = CdtextEntry {
+        pack_type: u8::from(item.entry_type),
+        track: u8::from(item.track_number),
+        block: item.block_number,
+        text: text.as_ref().map_or(ptr::null(), |text| text.as_ptr()),
+    };
+
+    // SAFETY: the caller guarantees that the entry is writable.
+    unsafe { entry.write(value) };
+    true
+}
+
+/// Returns the first non-empty text of the pack type (e.g. 0x80 for titles) for the track
+/// in the first block. Track 0 is the whole album. Returns null if there's no such text.
+/// The text stays valid until the document is freed.
+///
+/// # Safety
+///
+/// `document` must be null or a valid document.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cdtext_get(
+    document: *const CdtextDocument,
+    pack_type: u8,
+    track: u8,
+) -> *const c_char {
+    // SAFETY: the caller guarantees that the document is valid.
+    let Some(document) = (unsafe { document.as_ref() }) else {
+        return ptr::null();
+    };
+
+    let block_number = document
+        .entries
+        .iter()
+        .map(|(entry, _)| entry.block_number)
+        .min();
+
+    document
+        .entries
+        .iter()
+        .filter(|(entry, _)| {
+            Some(entry.block_number) == block_number
+                && u8::from(entry.entry_type) == pack_type
+                && u8::from(entry.track_number) == track
+        })
+        .find_map(|(_, text)| text.as_ref().filter(|text| !text.is_empty()))
+        .map_or(ptr::null(), |text| text.as_ptr())
+}
+
+/// Frees a string returned by the library. Does nothing if it's null.
+///
+/// # Safety
+///
+/// `string` must be null or returned by the library and not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cdtext_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        // SAFETY: the string was allocated by `CString::into_raw`.
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CDTextBuilder, CDTextPackType};
+
+    #[test]
+    fn parse_with_options() {
+        let mut data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .build()
+            .unwrap();
+        data[17] ^= 0xff;
+
+        let mut error = ptr::null_mut();
+
+        // SAFETY: the data and the error pointer are valid.
+        unsafe {
+            assert!(cdtext_parse(data.as_ptr(), data.len(), &mut error).is_null());
+            assert!(!error.is_null());
+            cdtext_string_free(error);
+
+            let flags = PARSE_LOSSY_TEXT | PARSE_RAW_TEXT;
+            let document = cdtext_parse_with_options(data.as_ptr(), data.len(), flags, &mut error);
+            assert!(document.is_null());
+            cdtext_string_free(error);
+
+            let document = cdtext_parse_with_options(
+                data.as_ptr(),
+                data.len(),
+                PARSE_LENIENT,
+                ptr::null_mut(),
+            );
+            assert!(!document.is_null());
+
+            let title = cdtext_get(document, 0x80, 0);
+            assert_eq!(core::ffi::CStr::from_ptr(title).to_str(), Ok("Album"));
+            cdtext_free(document);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    AdditionalToc, BlockSizeInfo, CDTextEntry, CDTextEntryDataType, CDTextPackType, Genre, Isrc,
+    Toc, Upc,
+};
+
+/// Typed contents of an entry. Each pack type has its own variant with data of the matching kind.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Field {
+    Title(String),
+    Performer(String),
+    Songwriter(String),
+    Composer(String),
+    Arranger(String),
+    Message(String),
+    DiscId(String),
+    Genre(Genre),
+    Toc(Toc),
+    AdditionalToc(AdditionalToc),
+    ClosedInfo(String),
+    Upc(Upc),
+    Isrc(Isrc),
+    /// Text of a Code pack that is not a valid UPC/EAN or ISRC.
+    Code(String),
+    BlockSizeInfo(BlockSizeInfo),
+    /// Data that cannot be represented otherwise: undecodable text or contents of reserved packs.
+    Raw {
+        pack_type: CDTextPackType,
+        data: Vec<u8>,
+    },
+}
+
+impl From<CDTextEntry> for Field {
+    fn from(entry: CDTextEntry) -> Self {
+        let pack_type = entry.entry_type;
+
+        let text = match entry.data {
+            CDTextEntryDataType::String(text) => text,
+            CDTextEntryDataType::Data(data) => return Self::Raw { pack_type, data },
+            CDTextEntryDataType::Genre(genre) => return Self::Genre(genre),
+            CDTextEntryDataType::Upc(upc) => return Self::Upc(upc),
+            CDTextEntryDataType::Isrc(isrc) => return Self::Isrc(isrc),
+            CDTextEntryDataType::Toc(toc) => return Self::Toc(toc),
+            CDTextEntryDataType::AdditionalToc(toc) => return Self::AdditionalToc(toc),
+            CDTextEntryDataType::BlockSizeInfo(size_info) => {
+                return Self::BlockSizeInfo(size_info);
+            }
+        };
+
+        match pack_type {
+            CDTextPackType::Title => Self::Title(text),
+            CDTextPackType::Performers => Self::Performer(text),
+            CDTextPackType::Songwriters => Self::Songwriter(text),
+            CDTextPackType::Composers => Self::Composer(text),
+            CDTextPackType::Arrangers => Self::Arranger(text),
+            CDTextPackType::Message => Self::Message(text),
+            CDTextPackType::DiscID => Self::DiscId(text),
+            CDTextPackType::ClosedInfo => Self::ClosedInfo(text),
+            CDTextPackType::Code => Self::Code(text),
+            _ => Self::Raw {
+                pack_type,
+                data: text.into_bytes(),
+            },
+        }
+    }
+}
+
+impl CDTextEntry {
+    /// Returns the contents of the entry as a typed field.
+    pub fn field(&self) -> Field {
+        Field::from(self.clone())
+    }
+}
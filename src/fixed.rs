@@ -0,0 +1,510 @@
+use heapless::{String, Vec};
+
+use crate::{
+    BlockSizeInfo, CDText, CDTextError, CDTextPack, CDTextPackType, CDTextTrackNumber, Charset,
+    FixedParseError, Isrc, Upc,
+    reassembly::{StringStream, expected_position, feed_strings},
+    sequence::SequenceChecker,
+};
+
+/// Text entry parsed into fixed-capacity storage, see [`CDText::parse_into`].
+/// The text holds up to `L` bytes of UTF-8.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedEntry<const L: usize> {
+    pub track_number: CDTextTrackNumber,
+    /// Block the entry comes from. Each block holds text in a separate language.
+    pub block_number: u8,
+    pub entry_type: CDTextPackType,
+    pub text: String<L>,
+}
+
+/// Internal structure. State of reassembly of a string stream of one pack type,
+/// like the one of [`CDText::parse`].
+struct FixedStream<const L: usize> {
+    pack_type: CDTextPackType,
+    block_number: u8,
+    /// Track the string being collected belongs to.
+    track: u8,
+    /// Index of the pack the string being collected starts in.
+    pack_index: usize,
+    buffer: Vec<u8, L>,
+    /// First track of the empty strings that are not known to be padding yet.
+    /// The rest of them belong to the tracks up to the current one.
+    pending_empty: Option<u8>,
+    /// Whether the last entry holds the previous string of the stream.
+    has_previous: bool,
+    /// Number of bytes each character takes: 2 in double-byte packs, 1 otherwise.
+    character_width: usize,
+    charset: Option<Charset>,
+}
+
+impl<const L: usize> FixedStream<L> {
+    fn new(pack_index: usize, pack: &CDTextPack, charset: Option<Charset>) -> Self {
+        Self {
+            pack_type: pack.pack_type,
+            block_number: pack.block_number,
+            track: 0,
+            pack_index,
+            buffer: Vec::new(),
+            pending_empty: None,
+            has_previous: false,
+            character_width: if pack.is_double_byte_characters { 2 } else { 1 },
+            charset,
+        }
+    }
+}
+
+impl CDText<'_> {
+    /// Parses the text into the buffer, without allocation. The buffer is cleared first.
+    ///
+    /// Takes up to `N` entries, each holding up to `L` bytes of text, and fails with
+    /// [`FixedParseError::TooManyEntries`] or [`FixedParseError::TextTooLong`] if the data
+    /// doesn't fit. Parsing is strict, like [`crate::ParseMode::Strict`] with
+    /// [`crate::TextDecoding::Strict`], regardless of the options; of them, only the charset
+    /// and TAB expansion are taken into account.
+    ///
+    /// Only text packs are parsed: UPC/ISRC codes are validated and returned as text,
+    /// while genre, TOC and Block Size Info are left out. Block Size Info is available with
+    /// [`CDText::block_size_info`]. Double-byte text cannot be decoded and fails with
+    /// [`CDTextError::UnsupportedCharset`].
+    pub fn parse_into<const N: usize, const L: usize>(
+        &self,
+        entries: &mut Vec<FixedEntry<L>, N>,
+    ) -> Result<(), FixedParseError> {
+        entries.clear();
+
+        let charsets: [Option<Charset>; 8] = match self.options.charset() {
+            Some(charset) => [Some(charset); 8],
+            None => core::array::from_fn(|block_number| {
+                BlockSizeInfo::from_packs(block_number as u8, self.iter_pack_chunks())
+                    .map(|size_info| size_info.charset())
+            }),
+        };
+
+        let mut sequence = SequenceChecker::default();
+        let mut stream: Option<FixedStream<L>> = None;
+
+        for (pack_index, pack) in self.iter_pack_chunks().enumerate() {
+            self.check_fixed_pack(&mut sequence, pack_index, &pack)?;
+
+            let is_text = matches!(
+                pack.pack_type,
+                CDTextPackType::Title
+                    | CDTextPackType::Performers
+                    | CDTextPackType::Songwriters
+                    | CDTextPackType::Composers
+                    | CDTextPackType::Arrangers
+                    | CDTextPackType::Message
+                    | CDTextPackType::DiscID
+                    | CDTextPackType::ClosedInfo
+                    | CDTextPackType::Code
+            );
+
+            if !is_text {
+                if let Some(finished) = stream.take() {
+                    self.finish_fixed_stream(finished)?;
+                }
+
+                continue;
+            }
+
+            let stream = match &mut stream {
+                Some(stream)
+                    if stream.pack_type == pack.pack_type
+                        && stream.block_number == pack.block_number =>
+                {
+                    stream
+                }
+                _ => {
+                    if let Some(finished) = stream.take() {
+                        self.finish_fixed_stream(finished)?;
+                    }
+
+                    stream.insert(FixedStream::new(
+                        pack_index,
+                        &pack,
+                        charsets[pack.block_number as usize],
+                    ))
+                }
+            };
+
+            self.check_fixed_continuity(stream, pack_index, &pack)?;
+
+            let width = stream.character_width;
+            let mut stream = FixedStringStream {
+                cdtext: self,
+                stream,
+                entries: &mut *entries,
+            };
+
+            feed_strings(&mut stream, pack_index, &pack.payload, width, 0)?;
+        }
+
+        if let Some(finished) = stream {
+            self.finish_fixed_stream(finished)?;
+        }
+
+        let trailing = self.trailing_bytes();
+
+        if !trailing.is_empty() {
+            let pack_index = self.data.len() / 18;
+
+            return Err(FixedParseError::Invalid(CDTextError::ShortPack {
+                pack_index,
+                offset: self.pack_offset(pack_index),
+                length: trailing.len(),
+            }));
+        }
+
+        if self.length > self.data.len() {
+            return Err(FixedParseError::Invalid(CDTextError::LengthMismatch {
+                declared: self.length + 2,
+                actual: self.data.len() + 2,
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Internal method. Checks the pack for damage, see [`CDText::parse`].
+    fn check_fixed_pack(
+        &self,
+        sequence: &mut SequenceChecker,
+        pack_index: usize,
+        pack: &CDTextPack,
+    ) -> Result<(), FixedParseError> {
+        let offset = self.pack_offset(pack_index);
+
+        let problem = if let Some(gap) = sequence.check(pack_index, pack) {
+            CDTextError::SequenceGap {
+                pack_index,
+                offset,
+                expected: gap.expected,
+                found: gap.found,
+            }
+        } else if let CDTextPackType::Unknown(pack_type) = pack.pack_type {
+            CDTextError::InvalidPackType {
+                pack_index,
+                offset,
+                pack_type,
+            }
+        } else if !pack.crc_valid {
+            CDTextError::CrcMismatch {
+                pack_index,
+                offset,
+                expected: pack.compute_crc(),
+                found: pack.crc,
+            }
+        } else {
+            return Ok(());
+        };
+
+        Err(FixedParseError::Invalid(problem))
+    }
+
+    /// Internal method. Checks that the pack header agrees with the reassembly state.
+    fn check_fixed_continuity<const L: usize>(
+        &self,
+        stream: &FixedStream<L>,
+        pack_index: usize,
+        pack: &CDTextPack,
+    ) -> Result<(), FixedParseError> {
+        let offset = self.pack_offset(pack_index);
+        let found_track = u8::from(pack.track_number);
+
+        if found_track != stream.track {
+            return Err(FixedParseError::Invalid(CDTextError::TrackNumberMismatch {
+                pack_index,
+                offset,
+                expected: stream.track,
+                found: found_track,
+            }));
+        }
+
+        let expected_position = expected_position(&stream.buffer, stream.character_width);
+
+        if pack.character_position != expected_position {
+            return Err(FixedParseError::Invalid(
+                CDTextError::CharacterPositionMismatch {
+                    pack_index,
+                    offset,
+                    expected: expected_position,
+                    found: pack.character_position,
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Internal method. Emits the string collected so far. Empty strings are held back
+    /// until a non-empty one follows, since they may be padding.
+    fn complete_fixed_string<const N: usize, const L: usize>(
+        &self,
+        stream: &mut FixedStream<L>,
+        entries: &mut Vec<FixedEntry<L>, N>,
+    ) -> Result<(), FixedParseError> {
+        if stream.buffer.iter().all(|&x| x == 0) {
+            stream.pending_empty.get_or_insert(stream.track);
+            stream.has_previous = false;
+            stream.buffer.clear();
+            return Ok(());
+        }
+
+        if let Some(first) = stream.pending_empty.take() {
+            for track in first..stream.track {
+                push_fixed_entry(entries, stream, track, String::new())?;
+            }
+        }
+
+        // A single TAB means the string is the same as for the previous track.
+        let is_tab = stream.buffer.len() == stream.character_width
+            && stream.buffer.iter().all(|&x| x == 0x09);
+
+        let text = match entries.last() {
+            Some(previous) if self.options.expands_tabs() && is_tab && stream.has_previous => {
+                previous.text.clone()
+            }
+            _ => self.decode_fixed_text(stream)?,
+        };
+
+        push_fixed_entry(entries, stream, stream.track, text)?;
+
+        stream.has_previous = true;
+        stream.buffer.clear();
+
+        Ok(())
+    }
+
+    /// Internal method. Finishes the stream. Unterminated text is an error.
+    fn finish_fixed_stream<const L: usize>(
+        &self,
+        stream: FixedStream<L>,
+    ) -> Result<(), FixedParseError> {
+        if stream.buffer.iter().all(|&x| x == 0) {
+            return Ok(());
+        }
+
+        Err(FixedParseError::Invalid(CDTextError::UnterminatedText {
+            pack_index: stream.pack_index,
+            offset: self.pack_offset(stream.pack_index),
+        }))
+    }
+
+    /// Internal method. Decodes the collected string in the charset of the block,
+    /// validating UPC/ISRC codes.
+    fn decode_fixed_text<const L: usize>(
+        &self,
+        stream: &FixedStream<L>,
+    ) -> Result<String<L>, FixedParseError> {
+        let pack_index = stream.pack_index;
+        let offset = self.pack_offset(pack_index);
+        let bytes = stream.buffer.as_slice();
+
+        let too_long = |_| FixedParseError::TextTooLong {
+            pack_index,
+            offset,
+            capacity: L,
+        };
+        let invalid = FixedParseError::Invalid(CDTextError::InvalidText { pack_index, offset });
+
+        let mut text = String::new();
+
+        match stream.charset {
+            // Without Block Size Info the text is assumed to be UTF-8.
+            None => {
+                let decoded = core::str::from_utf8(bytes).map_err(|_| invalid)?;
+                text.push_str(decoded).map_err(too_long)?;
+            }
+            // Latin-1 code points are the same as the first 256 Unicode code points.
+            Some(Charset::Iso8859_1) => {
+                for &byte in bytes {
+                    text.push(byte as char).map_err(too_long)?;
+                }
+            }
+            Some(Charset::Ascii) => {
+                if !bytes.is_ascii() {
+                    return Err(invalid);
+                }
+
+                for &byte in bytes {
+                    text.push(byte as char).map_err(too_long)?;
+                }
+            }
+            Some(charset) => {
+                return Err(FixedParseError::Invalid(CDTextError::UnsupportedCharset {
+                    pack_index,
+                    offset,
+                    character_code: u8::from(charset),
+                }));
+            }
+        }
+
+        // The album has UPC/EAN code, and each track has its ISRC.
+        if stream.pack_type == CDTextPackType::Code {
+            let valid = match stream.track {
+                0 => Upc::parse(&text).is_some(),
+                _ => Isrc::parse(&text).is_some(),
+            };
+
+            if !valid {
+                return Err(FixedParseError::Invalid(CDTextError::InvalidCode {
+                    pack_index,
+                    offset,
+                }));
+            }
+        }
+
+        Ok(text)
+    }
+}
+
+/// Internal structure. The stream being fed with the payload of a pack by [`feed_strings`],
+/// along with the entries its strings are appended to.
+struct FixedStringStream<'a, 'data, const N: usize, const L: usize> {
+    cdtext: &'a CDText<'data>,
+    stream: &'a mut FixedStream<L>,
+    entries: &'a mut Vec<FixedEntry<L>, N>,
+}
+
+impl<const N: usize, const L: usize> StringStream for FixedStringStream<'_, '_, N, L> {
+    type Error = FixedParseError;
+
+    fn buffer(&self) -> &[u8] {
+        &self.stream.buffer
+    }
+
+    fn push(&mut self, pack_index: usize, bytes: &[u8]) -> Result<(), FixedParseError> {
+        let stream = &mut *self.stream;
+
+        if stream.buffer.is_empty() {
+            stream.pack_index = pack_index;
+        }
+
+        stream
+            .buffer
+            .extend_from_slice(bytes)
+            .map_err(|_| FixedParseError::TextTooLong {
+                pack_index: stream.pack_index,
+                offset: self.cdtext.pack_offset(stream.pack_index),
+                capacity: L,
+            })
+    }
+
+    fn emit(&mut self, _pack_index: usize, _end: usize) -> Result<(), FixedParseError> {
+        self.cdtext
+            .complete_fixed_string(self.stream, self.entries)?;
+        self.stream.track = self.stream.track.saturating_add(1);
+
+        Ok(())
+    }
+}
+
+/// Internal function. Appends the entry of the stream, failing if the buffer is full.
+fn push_fixed_entry<const N: usize, const L: usize>(
+    entries: &mut Vec<FixedEntry<L>, N>,
+    stream: &FixedStream<L>,
+    track: u8,
+    text: String<L>,
+) -> Result<(), FixedParseError> {
+    entries
+        .push(FixedEntry {
+            track_number: CDTextTrackNumber::from(track),
+            block_number: stream.block_number,
+            entry_type: stream.pack_type,
+            text,
+        })
+        .map_err(|_| FixedParseError::TooManyEntries { capacity: N })
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{BlockBuilder, CDTextBuilder, CDTextEntryDataType, Language};
+
+    /// Internal function. Parses the data both ways, expecting the same text entries.
+    fn assert_same_as_parse(data: &[u8]) {
+        let cdtext = CDText::from_data(data);
+        let mut fixed = Vec::<FixedEntry<64>, 64>::new();
+        let result = cdtext.parse_into(&mut fixed);
+
+        let entries = match cdtext.parse() {
+            Ok(entries) => entries,
+            Err(error) => {
+                assert_eq!(result, Err(FixedParseError::Invalid(error)));
+                return;
+            }
+        };
+        result.unwrap();
+
+        let expected: alloc::vec::Vec<_> = entries
+            .iter()
+            .filter_map(|entry| {
+                let text = match &entry.data {
+                    CDTextEntryDataType::String(text) => text.as_str(),
+                    CDTextEntryDataType::Upc(upc) => upc.as_str(),
+                    CDTextEntryDataType::Isrc(isrc) => isrc.as_str(),
+                    _ => return None,
+                };
+
+                Some((
+                    entry.track_number,
+                    entry.block_number,
+                    entry.entry_type,
+                    text,
+                ))
+            })
+            .collect();
+
+        let found: alloc::vec::Vec<_> = fixed
+            .iter()
+            .map(|entry| {
+                let text = entry.text.as_str();
+                (
+                    entry.track_number,
+                    entry.block_number,
+                    entry.entry_type,
+                    text,
+                )
+            })
+            .collect();
+
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn same_as_parse() {
+        let data = CDTextBuilder::new()
+            .with_text(
+                CDTextPackType::Title,
+                0,
+                "An album title longer than fifteen characters",
+            )
+            .with_text(CDTextPackType::Title, 1, "Café")
+            .with_text(CDTextPackType::Title, 3, "Third")
+            .with_text(CDTextPackType::Performers, 0, "Band")
+            .with_text(CDTextPackType::Performers, 1, "Band")
+            .with_text(CDTextPackType::Performers, 2, "Band")
+            .with_text(CDTextPackType::Performers, 3, "Band")
+            .with_text(CDTextPackType::Code, 0, "0123456789012")
+            .with_text(CDTextPackType::Code, 1, "USRC17607839")
+            .with_block(BlockBuilder::new(Language::German).with_text(
+                CDTextPackType::Title,
+                0,
+                "Titel",
+            ))
+            .with_tab_compression(true)
+            .build()
+            .unwrap();
+
+        assert_same_as_parse(&data);
+
+        let mut damaged = data.clone();
+        damaged[18 * 3 + 17] ^= 0xff;
+        assert_same_as_parse(&damaged);
+
+        let mut lost = data.clone();
+        lost.drain(18..36);
+        assert_same_as_parse(&lost);
+    }
+}
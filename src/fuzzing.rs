@@ -0,0 +1,200 @@
+use alloc::{string::String, vec::Vec};
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{
+    BlockBuilder, CDText, CDTextBuilder, CDTextPack, CDTextPackType, CDTextTrackNumber, Charset,
+    Genre, GenreCode, Language, Unmappable, builder::TEXT_TYPES, crc,
+};
+
+/// Maximum number of strings in a generated block. Together with [`MAX_TEXT_LENGTH`]
+/// it keeps blocks within [`crate::MAX_BLOCK_PACKS`], even in double-byte charsets.
+const MAX_BLOCK_TEXTS: usize = 64;
+
+/// Maximum number of characters in a generated string.
+const MAX_TEXT_LENGTH: usize = 16;
+
+/// Mostly the pack types defined by the specification, sometimes an unknown one.
+impl<'a> Arbitrary<'a> for CDTextPackType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let value = if u.ratio(1, 16)? {
+            u.arbitrary()?
+        } else {
+            u.int_in_range(0x80..=0x8f)?
+        };
+
+        Ok(Self::from(value))
+    }
+}
+
+/// Mostly the album or tracks 1-99, sometimes an out of range track.
+impl<'a> Arbitrary<'a> for CDTextTrackNumber {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let value = if u.ratio(1, 16)? {
+            u.arbitrary()?
+        } else {
+            u.int_in_range(0..=99)?
+        };
+
+        Ok(Self::from(value))
+    }
+}
+
+/// A pack with in-range header fields and, mostly, the correct CRC.
+impl<'a> Arbitrary<'a> for CDTextPack {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut pack = CDTextPack {
+            pack_type: u.arbitrary()?,
+            track_number: u.arbitrary()?,
+            seq_counter: u.arbitrary()?,
+            character_position: u.int_in_range(0..=15)?,
+            block_number: u.int_in_range(0..=7)?,
+            is_double_byte_characters: u.arbitrary()?,
+            payload: u.arbitrary()?,
+            crc: 0,
+            crc_valid: false,
+        };
+
+        if u.ratio(1, 16)? {
+            damage_crc(&mut pack, u.arbitrary()?);
+        } else {
+            seal(&mut pack);
+        }
+
+        Ok(pack)
+    }
+}
+
+/// Packs of a CD-Text stream generated for fuzzing.
+///
+/// The stream is written by [`CDTextBuilder`] from random blocks of text, so the order of packs,
+/// sequence numbers, character positions and Block Size Info are consistent. Then it's damaged
+/// a few times the way real dumps are: CRC or payload bytes are changed, packs are lost,
+/// repeated or reordered. So parsers are exercised past the first pack, unlike with random bytes.
+#[derive(Debug, Clone)]
+pub struct PackStream(pub Vec<CDTextPack>);
+
+impl PackStream {
+    /// Serializes the packs, without the service header.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.iter().flat_map(CDTextPack::to_bytes).collect()
+    }
+}
+
+impl<'a> Arbitrary<'a> for PackStream {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut builder = CDTextBuilder::from_blocks([])
+            .with_tab_compression(u.arbitrary()?)
+            .with_unmappable(Unmappable::Replace);
+
+        for _ in 0..u.int_in_range(1..=8)? {
+            builder = builder.with_block(arbitrary_block(u)?);
+        }
+
+        let data = builder
+            .build()
+            .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+        let mut packs: Vec<CDTextPack> = CDText::from_data(&data).iter_pack_chunks().collect();
+
+        for _ in 0..u.int_in_range(0..=4)? {
+            if packs.is_empty() {
+                break;
+            }
+
+            let index = u.choose_index(packs.len())?;
+
+            match u.int_in_range(0..=4)? {
+                0 => damage_crc(&mut packs[index], u.arbitrary()?),
+                1 => {
+                    let position = u.choose_index(12)?;
+                    packs[index].payload[position] = u.arbitrary()?;
+
+                    // Damage that passes the CRC check reaches the text assembly.
+                    if u.arbitrary()? {
+                        seal(&mut packs[index]);
+                    } else {
+                        packs[index].crc_valid = false;
+                    }
+                }
+                2 => {
+                    packs.remove(index);
+                }
+                3 => packs.insert(index, packs[index].clone()),
+                _ => {
+                    let next = (index + 1).min(packs.len() - 1);
+                    packs.swap(index, next);
+                }
+            }
+        }
+
+        Ok(Self(packs))
+    }
+}
+
+/// Internal function. Generates a block with random strings for a few tracks.
+fn arbitrary_block(u: &mut Unstructured) -> Result<BlockBuilder> {
+    let charset = *u.choose(&[Charset::Iso8859_1, Charset::Ascii, Charset::MsJis])?;
+    let charset = if charset.is_encodable() {
+        charset
+    } else {
+        Charset::Iso8859_1
+    };
+
+    let mut block = BlockBuilder::new(Language::from(u.arbitrary::<u8>()?)).with_charset(charset);
+    let last_track = u.int_in_range(1..=20)?;
+
+    for _ in 0..u.int_in_range(0..=MAX_BLOCK_TEXTS)? {
+        let pack_type = *u.choose(&TEXT_TYPES)?;
+
+        // Genre is set separately, as it has a code.
+        if pack_type == CDTextPackType::Genre {
+            continue;
+        }
+
+        block.set_text(
+            pack_type,
+            u.int_in_range(0..=last_track)?,
+            arbitrary_text(u)?,
+        );
+    }
+
+    if u.ratio(1, 4)? {
+        block = block.with_genre(Genre {
+            code: GenreCode::from(u.arbitrary::<u16>()?),
+            text: if u.arbitrary()? {
+                Some(arbitrary_text(u)?)
+            } else {
+                None
+            },
+        });
+    }
+
+    Ok(block)
+}
+
+/// Internal function. Generates a string of up to [`MAX_TEXT_LENGTH`] characters.
+/// Sometimes it's a repetition, so TAB compression is used.
+fn arbitrary_text(u: &mut Unstructured) -> Result<String> {
+    if u.ratio(1, 4)? {
+        return Ok(String::from("Same"));
+    }
+
+    let text: &str = u.arbitrary()?;
+
+    Ok(text.chars().take(MAX_TEXT_LENGTH).collect())
+}
+
+/// Internal function. Stores the correct CRC into the pack.
+fn seal(pack: &mut CDTextPack) {
+    pack.crc = crc::crc16(&pack.to_bytes()[..16]);
+    pack.crc_valid = true;
+}
+
+/// Internal function. Stores a mismatching CRC into the pack.
+fn damage_crc(pack: &mut CDTextPack, flipped_bits: u16) {
+    seal(pack);
+
+    // At least one bit is flipped, so the CRC cannot stay correct.
+    pack.crc ^= flipped_bits.max(1);
+    pack.crc_valid = false;
+}
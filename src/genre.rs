@@ -0,0 +1,194 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use core::fmt;
+
+/// Standard genre code, as defined by the CD-Text specification.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GenreCode {
+    NotUsed,
+    NotDefined,
+    AdultContemporary,
+    AlternativeRock,
+    ChildrensMusic,
+    Classical,
+    ContemporaryChristian,
+    Country,
+    Dance,
+    EasyListening,
+    Erotic,
+    Folk,
+    Gospel,
+    HipHop,
+    Jazz,
+    Latin,
+    Musical,
+    NewAge,
+    Opera,
+    Operetta,
+    PopMusic,
+    Rap,
+    Reggae,
+    RockMusic,
+    RhythmAndBlues,
+    SoundEffects,
+    SpokenWord,
+    WorldMusic,
+    /// A code reserved by the specification. Holds the raw value.
+    Reserved(u16),
+}
+
+impl GenreCode {
+    /// Returns a human-readable name of the genre.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::NotUsed => "Not used",
+            Self::NotDefined => "Not defined",
+            Self::AdultContemporary => "Adult Contemporary",
+            Self::AlternativeRock => "Alternative Rock",
+            Self::ChildrensMusic => "Childrens Music",
+            Self::Classical => "Classical",
+            Self::ContemporaryChristian => "Contemporary Christian",
+            Self::Country => "Country",
+            Self::Dance => "Dance",
+            Self::EasyListening => "Easy Listening",
+            Self::Erotic => "Erotic",
+            Self::Folk => "Folk",
+            Self::Gospel => "Gospel",
+            Self::HipHop => "Hip Hop",
+            Self::Jazz => "Jazz",
+            Self::Latin => "Latin",
+            Self::Musical => "Musical",
+            Self::NewAge => "New Age",
+            Self::Opera => "Opera",
+            Self::Operetta => "Operetta",
+            Self::PopMusic => "Pop Music",
+            Self::Rap => "Rap",
+            Self::Reggae => "Reggae",
+            Self::RockMusic => "Rock Music",
+            Self::RhythmAndBlues => "Rhythm & Blues",
+            Self::SoundEffects => "Sound Effects",
+            Self::SpokenWord => "Spoken Word",
+            Self::WorldMusic => "World Music",
+            Self::Reserved(_) => "Reserved",
+        }
+    }
+}
+
+impl GenreCode {
+    /// Finds the standard genre by its name, as returned by [`GenreCode::name`], ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        (0..=27)
+            .map(Self::from)
+            .find(|code| code.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl From<u16> for GenreCode {
+    fn from(value: u16) -> Self {
+        match value {
+            0 => Self::NotUsed,
+            1 => Self::NotDefined,
+            2 => Self::AdultContemporary,
+            3 => Self::AlternativeRock,
+            4 => Self::ChildrensMusic,
+            5 => Self::Classical,
+            6 => Self::ContemporaryChristian,
+            7 => Self::Country,
+            8 => Self::Dance,
+            9 => Self::EasyListening,
+            10 => Self::Erotic,
+            11 => Self::Folk,
+            12 => Self::Gospel,
+            13 => Self::HipHop,
+            14 => Self::Jazz,
+            15 => Self::Latin,
+            16 => Self::Musical,
+            17 => Self::NewAge,
+            18 => Self::Opera,
+            19 => Self::Operetta,
+            20 => Self::PopMusic,
+            21 => Self::Rap,
+            22 => Self::Reggae,
+            23 => Self::RockMusic,
+            24 => Self::RhythmAndBlues,
+            25 => Self::SoundEffects,
+            26 => Self::SpokenWord,
+            27 => Self::WorldMusic,
+            n => Self::Reserved(n),
+        }
+    }
+}
+
+impl From<GenreCode> for u16 {
+    fn from(value: GenreCode) -> Self {
+        match value {
+            GenreCode::NotUsed => 0,
+            GenreCode::NotDefined => 1,
+            GenreCode::AdultContemporary => 2,
+            GenreCode::AlternativeRock => 3,
+            GenreCode::ChildrensMusic => 4,
+            GenreCode::Classical => 5,
+            GenreCode::ContemporaryChristian => 6,
+            GenreCode::Country => 7,
+            GenreCode::Dance => 8,
+            GenreCode::EasyListening => 9,
+            GenreCode::Erotic => 10,
+            GenreCode::Folk => 11,
+            GenreCode::Gospel => 12,
+            GenreCode::HipHop => 13,
+            GenreCode::Jazz => 14,
+            GenreCode::Latin => 15,
+            GenreCode::Musical => 16,
+            GenreCode::NewAge => 17,
+            GenreCode::Opera => 18,
+            GenreCode::Operetta => 19,
+            GenreCode::PopMusic => 20,
+            GenreCode::Rap => 21,
+            GenreCode::Reggae => 22,
+            GenreCode::RockMusic => 23,
+            GenreCode::RhythmAndBlues => 24,
+            GenreCode::SoundEffects => 25,
+            GenreCode::SpokenWord => 26,
+            GenreCode::WorldMusic => 27,
+            GenreCode::Reserved(n) => n,
+        }
+    }
+}
+
+/// Contents of a Genre pack: the standard genre code followed by optional supplementary text.
+#[cfg(feature = "alloc")]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Genre {
+    pub code: GenreCode,
+    /// Supplementary genre information, e.g. a more specific genre name.
+    pub text: Option<String>,
+}
+
+#[cfg(feature = "alloc")]
+impl Genre {
+    /// Returns the genre as it's usually written into tags: the supplementary text if any,
+    /// otherwise the name of a standard genre. Returns `None` if the genre is not set.
+    pub fn tag_name(&self) -> Option<&str> {
+        match (&self.text, self.code) {
+            (Some(text), _) if !text.is_empty() => Some(text),
+            (_, GenreCode::NotUsed | GenreCode::NotDefined | GenreCode::Reserved(_)) => None,
+            (_, code) => Some(code.name()),
+        }
+    }
+}
+
+/// The name of the genre, followed by the supplementary text, if any, e.g. `Pop Music: Synth-pop`.
+#[cfg(feature = "alloc")]
+impl fmt::Display for Genre {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code.name())?;
+
+        match &self.text {
+            Some(text) if !text.is_empty() => write!(f, ": {text}"),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use alloc::vec::Vec;
+
+/// Internal function. Parses a token of exactly two hex digits.
+/// Labels like `80:` are not bytes, even if they look like ones.
+fn hex_byte(token: &str) -> Option<u8> {
+    if token.len() != 2 {
+        return None;
+    }
+
+    u8::from_str_radix(token, 16).ok()
+}
+
+/// Extracts packs from a textual hex dump, e.g. the one printed by `cdrecord -vv -toc`
+/// or `wodim -vv -toc`, one pack per line.
+///
+/// Each line is searched for 18 consecutive two-digit hex bytes, the first of which is
+/// a pack type (0x80-0x8F). Anything around them, like pack indices, labels or the text column,
+/// is ignored, as are lines without a complete pack.
+/// The result can be parsed with [`crate::CDText::from_data`].
+pub fn parse_hex_dump(dump: &str) -> Vec<u8> {
+    let mut data = Vec::new();
+
+    for line in dump.lines() {
+        let bytes: Vec<Option<u8>> = line.split_whitespace().map(hex_byte).collect();
+
+        let pack = bytes.windows(18).find(|window| {
+            matches!(window[0], Some(0x80..=0x8f)) && window.iter().all(Option::is_some)
+        });
+
+        if let Some(pack) = pack {
+            data.extend(pack.iter().flatten());
+        }
+    }
+
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CDText, CDTextEntryDataType, CDTextPackType};
+    use alloc::string::String;
+
+    /// Log of `cdrecord -vv -toc` with the CD-Text of a disc with one track.
+    const LOG: &str = "\
+Device type    : Removable CD-ROM
+Drive buf size : 1048576 = 1024 KB
+Text len: 92
+pack type 80 81 8F
+  0 80 00 00 00 41 6C 62 75 6D 00 46 69 72 73 74 00 1A 02  Album.First.
+  1 81 00 01 00 42 61 6E 64 00 00 00 00 00 00 00 00 DF AE  Band........
+  2 8F 00 02 00 00 01 01 00 01 01 00 00 00 00 00 00 30 E8  ............
+  3 8F 01 03 00 00 00 00 00 00 00 00 03 04 00 00 00 89 E8  ............
+  4 8F 02 04 00 00 00 00 00 09 00 00 00 00 00 00 00 67 E1  ............
+first: 1 last 1
+track:   1 lba:         0 (        0) 00:02:00 adr: 1 control: 0 mode: 0
+track:lout lba:     13000 (    52000) 02:55:25 adr: 1 control: 0 mode: -1
+";
+
+    #[test]
+    fn cdrecord_log() {
+        let data = parse_hex_dump(LOG);
+        assert_eq!(data.len(), 5 * 18);
+        assert!(data.starts_with(&[0x80, 0x00, 0x00, 0x00, b'A', b'l', b'b']));
+
+        let cdtext = CDText::from_data(&data);
+        assert!(cdtext.iter_pack_chunks().all(|pack| pack.verify_crc()));
+
+        let entries = cdtext.parse().unwrap();
+        let text = |pack_type, track: u8| {
+            entries
+                .iter()
+                .find(|entry| {
+                    entry.entry_type == pack_type && u8::from(entry.track_number) == track
+                })
+                .map(|entry| entry.data.clone())
+        };
+        let string = |text: &str| Some(CDTextEntryDataType::String(String::from(text)));
+
+        assert_eq!(text(CDTextPackType::Title, 0), string("Album"));
+        assert_eq!(text(CDTextPackType::Title, 1), string("First"));
+        assert_eq!(text(CDTextPackType::Performers, 0), string("Band"));
+    }
+
+    #[test]
+    fn incomplete_lines() {
+        assert!(parse_hex_dump("80: 00 00 00 41\n").is_empty());
+        assert!(
+            parse_hex_dump("  0 80 00 00 00 41 6C 62 75 6D 00 46 69 72 73 74 00 1A\n").is_empty()
+        );
+        assert_eq!(
+            parse_hex_dump("00 80 00 00 00 41 6C 62 75 6D 00 46 69 72 73 74 00 1A 02").len(),
+            18
+        );
+    }
+}
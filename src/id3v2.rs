@@ -0,0 +1,181 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::Disc;
+
+/// An ID3v2 text frame.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Id3Frame {
+    /// Frame ID, e.g. `TIT2`.
+    pub id: &'static str,
+    /// Description of `COMM` and `TXXX` frames, empty for the other frames.
+    pub description: &'static str,
+    pub text: String,
+}
+
+impl Id3Frame {
+    /// Internal function. Creates a frame without description.
+    fn new(id: &'static str, text: impl Into<String>) -> Self {
+        Self {
+            id,
+            description: "",
+            text: text.into(),
+        }
+    }
+}
+
+impl Disc {
+    /// Maps CD-Text fields of the track to ID3v2.4 text frames, to tag a file ripped from it.
+    ///
+    /// | Frame          | Field                                                 |
+    /// |----------------|-------------------------------------------------------|
+    /// | `TIT2`         | Track title                                           |
+    /// | `TALB`         | Album title                                           |
+    /// | `TPE1`         | Performer                                             |
+    /// | `TPE2`         | Album performer                                       |
+    /// | `TEXT`         | Songwriter                                            |
+    /// | `TCOM`         | Composer                                              |
+    /// | `TIPL`         | Arranger, as `arranger` and the name separated by NUL |
+    /// | `TRCK`         | Track number and the number of the last track         |
+    /// | `TCON`         | Genre, see [`crate::Genre::tag_name`]                 |
+    /// | `TSRC`         | ISRC                                                  |
+    /// | `COMM`         | Message                                               |
+    /// | `TXXX:BARCODE` | UPC/EAN                                               |
+    ///
+    /// Performer, songwriter, composer, arranger and message of the track fall back to the album
+    /// ones. Missing fields are left out. Language of the `COMM` frame is up to the caller.
+    pub fn id3v2_frames(&self, track: u8) -> Vec<Id3Frame> {
+        let info = self.track(track);
+        let inherited = |track_field: Option<&String>, album_field: &Option<String>| {
+            track_field.or(album_field.as_ref()).cloned()
+        };
+
+        let last_track = self.tracks.last().map_or(track, |info| info.number);
+
+        let fields = [
+            ("TIT2", info.and_then(|info| info.title.clone())),
+            ("TALB", self.title.clone()),
+            (
+                "TPE1",
+                inherited(
+                    info.and_then(|info| info.performer.as_ref()),
+                    &self.performer,
+                ),
+            ),
+            ("TPE2", self.performer.clone()),
+            (
+                "TEXT",
+                inherited(
+                    info.and_then(|info| info.songwriter.as_ref()),
+                    &self.songwriter,
+                ),
+            ),
+            (
+                "TCOM",
+                inherited(info.and_then(|info| info.composer.as_ref()), &self.composer),
+            ),
+            (
+                "TIPL",
+                inherited(info.and_then(|info| info.arranger.as_ref()), &self.arranger)
+                    .map(|arranger| format!("arranger\0{arranger}")),
+            ),
+            ("TRCK", Some(format!("{track}/{last_track}"))),
+            (
+                "TCON",
+                self.genre
+                    .as_ref()
+                    .and_then(|genre| genre.tag_name())
+                    .map(String::from),
+            ),
+            (
+                "TSRC",
+                info.and_then(|info| info.isrc)
+                    .map(|isrc| String::from(isrc.as_str())),
+            ),
+            (
+                "COMM",
+                inherited(info.and_then(|info| info.message.as_ref()), &self.message),
+            ),
+        ];
+
+        let mut frames: Vec<Id3Frame> = fields
+            .into_iter()
+            .filter_map(|(id, text)| Some(Id3Frame::new(id, text?)))
+            .collect();
+
+        if let Some(upc) = &self.upc {
+            frames.push(Id3Frame {
+                id: "TXXX",
+                description: "BARCODE",
+                text: String::from(upc.as_str()),
+            });
+        }
+
+        frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Genre, GenreCode, Isrc, TrackInfo, Upc};
+
+    #[test]
+    fn frames() {
+        let disc = Disc {
+            title: Some(String::from("Album")),
+            performer: Some(String::from("Band")),
+            composer: Some(String::from("Composer")),
+            message: Some(String::from("Liner notes")),
+            upc: Upc::parse("036000291452"),
+            genre: Some(Genre {
+                code: GenreCode::Jazz,
+                text: None,
+            }),
+            tracks: Vec::from([
+                TrackInfo {
+                    number: 1,
+                    title: Some(String::from("First")),
+                    performer: Some(String::from("Guest")),
+                    arranger: Some(String::from("Arranger")),
+                    isrc: Isrc::parse("USRC17607839"),
+                    ..TrackInfo::default()
+                },
+                TrackInfo {
+                    number: 2,
+                    ..TrackInfo::default()
+                },
+            ]),
+            ..Disc::default()
+        };
+
+        assert_eq!(
+            disc.id3v2_frames(1),
+            [
+                Id3Frame::new("TIT2", "First"),
+                Id3Frame::new("TALB", "Album"),
+                Id3Frame::new("TPE1", "Guest"),
+                Id3Frame::new("TPE2", "Band"),
+                Id3Frame::new("TCOM", "Composer"),
+                Id3Frame::new("TIPL", "arranger\0Arranger"),
+                Id3Frame::new("TRCK", "1/2"),
+                Id3Frame::new("TCON", "Jazz"),
+                Id3Frame::new("TSRC", "USRC17607839"),
+                Id3Frame::new("COMM", "Liner notes"),
+                Id3Frame {
+                    id: "TXXX",
+                    description: "BARCODE",
+                    text: String::from("036000291452"),
+                },
+            ]
+        );
+
+        let ids: Vec<&str> = disc.id3v2_frames(2).iter().map(|frame| frame.id).collect();
+        assert_eq!(
+            ids,
+            [
+                "TALB", "TPE1", "TPE2", "TCOM", "TRCK", "TCON", "COMM", "TXXX"
+            ]
+        );
+        assert_eq!(disc.id3v2_frames(2)[1].text, "Band");
+    }
+}
@@ -0,0 +1,247 @@
+use alloc::{string::String, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Disc, Genre, GenreCode, Isrc, TrackInfo, Upc};
+
+/// Version of the schema written by [`Disc::to_json`].
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// Internal structure. Genre in the JSON schema.
+#[derive(Serialize, Deserialize)]
+struct GenreJson {
+    code: u16,
+    #[serde(default, skip_deserializing)]
+    name: &'static str,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+}
+
+/// Internal structure. Track in the JSON schema.
+#[derive(Serialize, Deserialize)]
+struct TrackJson {
+    number: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    performer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    songwriter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    composer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    arranger: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    isrc: Option<Isrc>,
+}
+
+/// Internal structure. Disc in the JSON schema.
+#[derive(Serialize, Deserialize)]
+struct DiscJson {
+    version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    performer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    songwriter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    composer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    arranger: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    disc_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    upc: Option<Upc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    genre: Option<GenreJson>,
+    #[serde(default)]
+    tracks: Vec<TrackJson>,
+}
+
+impl Disc {
+    /// Serializes the disc to JSON. The schema is stable and doesn't depend on how the types
+    /// are laid out in Rust:
+    ///
+    /// ```json
+    /// {
+    ///   "version": 1,
+    ///   "title": "Album",
+    ///   "performer": "Performer",
+    ///   "songwriter": "...", "composer": "...", "arranger": "...", "message": "...", "disc_id": "...",
+    ///   "upc": "0123456789012",
+    ///   "genre": { "code": 14, "name": "Jazz", "text": "Smooth Jazz" },
+    ///   "tracks": [
+    ///     { "number": 1, "title": "First Track", "isrc": "USRC17607839" }
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Missing fields are left out. `name` of the genre is informative and ignored on import.
+    pub fn to_json(&self) -> String {
+        let disc = DiscJson {
+            version: JSON_SCHEMA_VERSION,
+            title: self.title.clone(),
+            performer: self.performer.clone(),
+            songwriter: self.songwriter.clone(),
+            composer: self.composer.clone(),
+            arranger: self.arranger.clone(),
+            message: self.message.clone(),
+            disc_id: self.disc_id.clone(),
+            upc: self.upc,
+            genre: self.genre.as_ref().map(|genre| GenreJson {
+                code: u16::from(genre.code),
+                name: genre.code.name(),
+                text: genre.text.clone(),
+            }),
+            tracks: self
+                .tracks
+                .iter()
+                .map(|track| TrackJson {
+                    number: track.number,
+                    title: track.title.clone(),
+                    performer: track.performer.clone(),
+                    songwriter: track.songwriter.clone(),
+                    composer: track.composer.clone(),
+                    arranger: track.arranger.clone(),
+                    message: track.message.clone(),
+                    isrc: track.isrc,
+                })
+                .collect(),
+        };
+
+        // Serialization of these structures to a string cannot fail.
+        serde_json::to_string(&disc).unwrap_or_default()
+    }
+
+    /// Deserializes the disc from JSON, see [`Disc::to_json`] for the schema.
+    /// Fails if the JSON doesn't match the schema, or is of a newer version.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let disc: DiscJson = serde_json::from_str(json)?;
+
+        if disc.version > JSON_SCHEMA_VERSION {
+            return Err(serde::de::Error::custom(alloc::format!(
+                "unsupported schema version {}",
+                disc.version
+            )));
+        }
+
+        let mut tracks: Vec<TrackInfo> = disc
+            .tracks
+            .into_iter()
+            .map(|track| TrackInfo {
+                number: track.number,
+                title: track.title,
+                performer: track.performer,
+                songwriter: track.songwriter,
+                composer: track.composer,
+                arranger: track.arranger,
+                message: track.message,
+                isrc: track.isrc,
+            })
+            .collect();
+        tracks.sort_by_key(|track| track.number);
+
+        Ok(Self {
+            title: disc.title,
+            performer: disc.performer,
+            songwriter: disc.songwriter,
+            composer: disc.composer,
+            arranger: disc.arranger,
+            message: disc.message,
+            disc_id: disc.disc_id,
+            upc: disc.upc,
+            genre: disc.genre.map(|genre| Genre {
+                code: GenreCode::from(genre.code),
+                text: genre.text,
+            }),
+            tracks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Internal function. Returns a disc with all the fields.
+    fn disc() -> Disc {
+        Disc {
+            title: Some(String::from("Caf\u{e9} \"Live\"")),
+            performer: Some(String::from("Band")),
+            disc_id: Some(String::from("XY-1234")),
+            upc: Upc::parse("036000291452"),
+            genre: Some(Genre {
+                code: GenreCode::Jazz,
+                text: Some(String::from("Bebop")),
+            }),
+            tracks: Vec::from([
+                TrackInfo {
+                    number: 1,
+                    title: Some(String::from("First")),
+                    isrc: Isrc::parse("USRC17607839"),
+                    ..TrackInfo::default()
+                },
+                TrackInfo {
+                    number: 2,
+                    message: Some(String::from("Bonus")),
+                    ..TrackInfo::default()
+                },
+            ]),
+            ..Disc::default()
+        }
+    }
+
+    #[test]
+    fn schema() {
+        assert_eq!(
+            disc().to_json(),
+            "{\"version\":1,\"title\":\"Caf\u{e9} \\\"Live\\\"\",\"performer\":\"Band\",\
+             \"disc_id\":\"XY-1234\",\"upc\":\"036000291452\",\
+             \"genre\":{\"code\":14,\"name\":\"Jazz\",\"text\":\"Bebop\"},\
+             \"tracks\":[{\"number\":1,\"title\":\"First\",\"isrc\":\"USRC17607839\"},\
+             {\"number\":2,\"message\":\"Bonus\"}]}"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let disc = disc();
+        assert_eq!(Disc::from_json(&disc.to_json()).unwrap(), disc);
+        assert_eq!(Disc::from_json("{\"version\":1}").unwrap(), Disc::default());
+    }
+
+    #[test]
+    fn import() {
+        let json = r#"{"version": 1, "genre": {"code": 14, "name": "Rock"},
+                       "tracks": [{"number": 2, "title": "Second"}, {"number": 1}]}"#;
+        let disc = Disc::from_json(json).unwrap();
+
+        assert_eq!(
+            disc.genre,
+            Some(Genre {
+                code: GenreCode::Jazz,
+                text: None,
+            })
+        );
+        assert_eq!(disc.tracks[0].number, 1);
+        assert_eq!(disc.tracks[1].title.as_deref(), Some("Second"));
+
+        assert!(Disc::from_json("{\"version\":2}").is_err());
+        assert!(Disc::from_json("{\"title\":\"Album\"}").is_err());
+        assert!(Disc::from_json("{\"version\":1,\"upc\":\"123\"}").is_err());
+    }
+
+    #[cfg(feature = "synthetic")]
+    #[test]
+    fn synthetic_round_trip() {
+        let data = crate::SyntheticDisc::new(20).build().unwrap();
+        let disc = Disc::from_entries(&crate::CDText::from_data(&data).parse().unwrap());
+
+        assert_eq!(Disc::from_json(&disc.to_json()).unwrap(), disc);
+    }
+}
@@ -0,0 +1,526 @@
+use crate::{BlockSizeInfo, CDText};
+
+/// Language of a block, as defined by the EBU Tech 3264 code table.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Language {
+    /// The language is not specified.
+    Unknown,
+    Albanian,
+    Breton,
+    Catalan,
+    Croatian,
+    Welsh,
+    Czech,
+    Danish,
+    German,
+    English,
+    Spanish,
+    Esperanto,
+    Estonian,
+    Basque,
+    Faroese,
+    French,
+    Frisian,
+    Irish,
+    Gaelic,
+    Galician,
+    Icelandic,
+    Italian,
+    Lappish,
+    Latin,
+    Latvian,
+    Luxembourgian,
+    Lithuanian,
+    Hungarian,
+    Maltese,
+    Dutch,
+    Norwegian,
+    Occitan,
+    Polish,
+    Portuguese,
+    Romanian,
+    Romansh,
+    Serbian,
+    Slovak,
+    Slovenian,
+    Finnish,
+    Swedish,
+    Turkish,
+    Flemish,
+    Walloon,
+    Zulu,
+    Vietnamese,
+    Uzbek,
+    Urdu,
+    Ukrainian,
+    Thai,
+    Telugu,
+    Tatar,
+    Tamil,
+    Tajik,
+    Swahili,
+    SrananTongo,
+    Somali,
+    Sinhalese,
+    Shona,
+    SerboCroatian,
+    Ruthenian,
+    Russian,
+    Quechua,
+    Pashto,
+    Punjabi,
+    Persian,
+    Papiamento,
+    Oriya,
+    Nepali,
+    Ndebele,
+    Marathi,
+    Moldavian,
+    Malaysian,
+    Malagasy,
+    Macedonian,
+    Laotian,
+    Korean,
+    Khmer,
+    Kazakh,
+    Kannada,
+    Japanese,
+    Indonesian,
+    Hindi,
+    Hebrew,
+    Hausa,
+    Guarani,
+    Gujarati,
+    Greek,
+    Georgian,
+    Fulani,
+    Dari,
+    Chuvash,
+    Chinese,
+    Burmese,
+    Bulgarian,
+    Bengali,
+    Belarusian,
+    Bambara,
+    Azerbaijani,
+    Assamese,
+    Armenian,
+    Arabic,
+    Amharic,
+    /// A code not assigned by the table. Holds the raw value.
+    Reserved(u8),
+}
+
+impl Language {
+    /// Returns the English name of the language.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Unknown => "Unknown",
+            Self::Albanian => "Albanian",
+            Self::Breton => "Breton",
+            Self::Catalan => "Catalan",
+            Self::Croatian => "Croatian",
+            Self::Welsh => "Welsh",
+            Self::Czech => "Czech",
+            Self::Danish => "Danish",
+            Self::German => "German",
+            Self::English => "English",
+            Self::Spanish => "Spanish",
+            Self::Esperanto => "Esperanto",
+            Self::Estonian => "Estonian",
+            Self::Basque => "Basque",
+            Self::Faroese => "Faroese",
+            Self::French => "French",
+            Self::Frisian => "Frisian",
+            Self::Irish => "Irish",
+            Self::Gaelic => "Gaelic",
+            Self::Galician => "Galician",
+            Self::Icelandic => "Icelandic",
+            Self::Italian => "Italian",
+            Self::Lappish => "Lappish",
+            Self::Latin => "Latin",
+            Self::Latvian => "Latvian",
+            Self::Luxembourgian => "Luxembourgian",
+            Self::Lithuanian => "Lithuanian",
+            Self::Hungarian => "Hungarian",
+            Self::Maltese => "Maltese",
+            Self::Dutch => "Dutch",
+            Self::Norwegian => "Norwegian",
+            Self::Occitan => "Occitan",
+            Self::Polish => "Polish",
+            Self::Portuguese => "Portuguese",
+            Self::Romanian => "Romanian",
+            Self::Romansh => "Romansh",
+            Self::Serbian => "Serbian",
+            Self::Slovak => "Slovak",
+            Self::Slovenian => "Slovenian",
+            Self::Finnish => "Finnish",
+            Self::Swedish => "Swedish",
+            Self::Turkish => "Turkish",
+            Self::Flemish => "Flemish",
+            Self::Walloon => "Walloon",
+            Self::Zulu => "Zulu",
+            Self::Vietnamese => "Vietnamese",
+            Self::Uzbek => "Uzbek",
+            Self::Urdu => "Urdu",
+            Self::Ukrainian => "Ukrainian",
+            Self::Thai => "Thai",
+            Self::Telugu => "Telugu",
+            Self::Tatar => "Tatar",
+            Self::Tamil => "Tamil",
+            Self::Tajik => "Tajik",
+            Self::Swahili => "Swahili",
+            Self::SrananTongo => "Sranan Tongo",
+            Self::Somali => "Somali",
+            Self::Sinhalese => "Sinhalese",
+            Self::Shona => "Shona",
+            Self::SerboCroatian => "Serbo-Croatian",
+            Self::Ruthenian => "Ruthenian",
+            Self::Russian => "Russian",
+            Self::Quechua => "Quechua",
+            Self::Pashto => "Pashto",
+            Self::Punjabi => "Punjabi",
+            Self::Persian => "Persian",
+            Self::Papiamento => "Papiamento",
+            Self::Oriya => "Oriya",
+            Self::Nepali => "Nepali",
+            Self::Ndebele => "Ndebele",
+            Self::Marathi => "Marathi",
+            Self::Moldavian => "Moldavian",
+            Self::Malaysian => "Malaysian",
+            Self::Malagasy => "Malagasy",
+            Self::Macedonian => "Macedonian",
+            Self::Laotian => "Laotian",
+            Self::Korean => "Korean",
+            Self::Khmer => "Khmer",
+            Self::Kazakh => "Kazakh",
+            Self::Kannada => "Kannada",
+            Self::Japanese => "Japanese",
+            Self::Indonesian => "Indonesian",
+            Self::Hindi => "Hindi",
+            Self::Hebrew => "Hebrew",
+            Self::Hausa => "Hausa",
+            Self::Guarani => "Guarani",
+            Self::Gujarati => "Gujarati",
+            Self::Greek => "Greek",
+            Self::Georgian => "Georgian",
+            Self::Fulani => "Fulani",
+            Self::Dari => "Dari",
+            Self::Chuvash => "Chuvash",
+            Self::Chinese => "Chinese",
+            Self::Burmese => "Burmese",
+            Self::Bulgarian => "Bulgarian",
+            Self::Bengali => "Bengali",
+            Self::Belarusian => "Belarusian",
+            Self::Bambara => "Bambara",
+            Self::Azerbaijani => "Azerbaijani",
+            Self::Assamese => "Assamese",
+            Self::Armenian => "Armenian",
+            Self::Arabic => "Arabic",
+            Self::Amharic => "Amharic",
+            Self::Reserved(_) => "Reserved",
+        }
+    }
+}
+
+impl From<u8> for Language {
+    fn from(value: u8) -> Self {
+        match value {
+            0x00 => Self::Unknown,
+            0x01 => Self::Albanian,
+            0x02 => Self::Breton,
+            0x03 => Self::Catalan,
+            0x04 => Self::Croatian,
+            0x05 => Self::Welsh,
+            0x06 => Self::Czech,
+            0x07 => Self::Danish,
+            0x08 => Self::German,
+            0x09 => Self::English,
+            0x0a => Self::Spanish,
+            0x0b => Self::Esperanto,
+            0x0c => Self::Estonian,
+            0x0d => Self::Basque,
+            0x0e => Self::Faroese,
+            0x0f => Self::French,
+            0x10 => Self::Frisian,
+            0x11 => Self::Irish,
+            0x12 => Self::Gaelic,
+            0x13 => Self::Galician,
+            0x14 => Self::Icelandic,
+            0x15 => Self::Italian,
+            0x16 => Self::Lappish,
+            0x17 => Self::Latin,
+            0x18 => Self::Latvian,
+            0x19 => Self::Luxembourgian,
+            0x1a => Self::Lithuanian,
+            0x1b => Self::Hungarian,
+            0x1c => Self::Maltese,
+            0x1d => Self::Dutch,
+            0x1e => Self::Norwegian,
+            0x1f => Self::Occitan,
+            0x20 => Self::Polish,
+            0x21 => Self::Portuguese,
+            0x22 => Self::Romanian,
+            0x23 => Self::Romansh,
+            0x24 => Self::Serbian,
+            0x25 => Self::Slovak,
+            0x26 => Self::Slovenian,
+            0x27 => Self::Finnish,
+            0x28 => Self::Swedish,
+            0x29 => Self::Turkish,
+            0x2a => Self::Flemish,
+            0x2b => Self::Walloon,
+            0x45 => Self::Zulu,
+            0x46 => Self::Vietnamese,
+            0x47 => Self::Uzbek,
+            0x48 => Self::Urdu,
+            0x49 => Self::Ukrainian,
+            0x4a => Self::Thai,
+            0x4b => Self::Telugu,
+            0x4c => Self::Tatar,
+            0x4d => Self::Tamil,
+            0x4e => Self::Tajik,
+            0x4f => Self::Swahili,
+            0x50 => Self::SrananTongo,
+            0x51 => Self::Somali,
+            0x52 => Self::Sinhalese,
+            0x53 => Self::Shona,
+            0x54 => Self::SerboCroatian,
+            0x55 => Self::Ruthenian,
+            0x56 => Self::Russian,
+            0x57 => Self::Quechua,
+            0x58 => Self::Pashto,
+            0x59 => Self::Punjabi,
+            0x5a => Self::Persian,
+            0x5b => Self::Papiamento,
+            0x5c => Self::Oriya,
+            0x5d => Self::Nepali,
+            0x5e => Self::Ndebele,
+            0x5f => Self::Marathi,
+            0x60 => Self::Moldavian,
+            0x61 => Self::Malaysian,
+            0x62 => Self::Malagasy,
+            0x63 => Self::Macedonian,
+            0x64 => Self::Laotian,
+            0x65 => Self::Korean,
+            0x66 => Self::Khmer,
+            0x67 => Self::Kazakh,
+            0x68 => Self::Kannada,
+            0x69 => Self::Japanese,
+            0x6a => Self::Indonesian,
+            0x6b => Self::Hindi,
+            0x6c => Self::Hebrew,
+            0x6d => Self::Hausa,
+            0x6e => Self::Guarani,
+            0x6f => Self::Gujarati,
+            0x70 => Self::Greek,
+            0x71 => Self::Georgian,
+            0x72 => Self::Fulani,
+            0x73 => Self::Dari,
+            0x74 => Self::Chuvash,
+            0x75 => Self::Chinese,
+            0x76 => Self::Burmese,
+            0x77 => Self::Bulgarian,
+            0x78 => Self::Bengali,
+            0x79 => Self::Belarusian,
+            0x7a => Self::Bambara,
+            0x7b => Self::Azerbaijani,
+            0x7c => Self::Assamese,
+            0x7d => Self::Armenian,
+            0x7e => Self::Arabic,
+            0x7f => Self::Amharic,
+            n => Self::Reserved(n),
+        }
+    }
+}
+
+impl From<Language> for u8 {
+    fn from(value: Language) -> Self {
+        match value {
+            Language::Unknown => 0x00,
+            Language::Albanian => 0x01,
+            Language::Breton => 0x02,
+            Language::Catalan => 0x03,
+            Language::Croatian => 0x04,
+            Language::Welsh => 0x05,
+            Language::Czech => 0x06,
+            Language::Danish => 0x07,
+            Language::German => 0x08,
+            Language::English => 0x09,
+            Language::Spanish => 0x0a,
+            Language::Esperanto => 0x0b,
+            Language::Estonian => 0x0c,
+            Language::Basque => 0x0d,
+            Language::Faroese => 0x0e,
+            Language::French => 0x0f,
+            Language::Frisian => 0x10,
+            Language::Irish => 0x11,
+            Language::Gaelic => 0x12,
+            Language::Galician => 0x13,
+            Language::Icelandic => 0x14,
+            Language::Italian => 0x15,
+            Language::Lappish => 0x16,
+            Language::Latin => 0x17,
+            Language::Latvian => 0x18,
+            Language::Luxembourgian => 0x19,
+            Language::Lithuanian => 0x1a,
+            Language::Hungarian => 0x1b,
+            Language::Maltese => 0x1c,
+            Language::Dutch => 0x1d,
+            Language::Norwegian => 0x1e,
+            Language::Occitan => 0x1f,
+            Language::Polish => 0x20,
+            Language::Portuguese => 0x21,
+            Language::Romanian => 0x22,
+            Language::Romansh => 0x23,
+            Language::Serbian => 0x24,
+            Language::Slovak => 0x25,
+            Language::Slovenian => 0x26,
+            Language::Finnish => 0x27,
+            Language::Swedish => 0x28,
+            Language::Turkish => 0x29,
+            Language::Flemish => 0x2a,
+            Language::Walloon => 0x2b,
+            Language::Zulu => 0x45,
+            Language::Vietnamese => 0x46,
+            Language::Uzbek => 0x47,
+            Language::Urdu => 0x48,
+            Language::Ukrainian => 0x49,
+            Language::Thai => 0x4a,
+            Language::Telugu => 0x4b,
+            Language::Tatar => 0x4c,
+            Language::Tamil => 0x4d,
+            Language::Tajik => 0x4e,
+            Language::Swahili => 0x4f,
+            Language::SrananTongo => 0x50,
+            Language::Somali => 0x51,
+            Language::Sinhalese => 0x52,
+            Language::Shona => 0x53,
+            Language::SerboCroatian => 0x54,
+            Language::Ruthenian => 0x55,
+            Language::Russian => 0x56,
+            Language::Quechua => 0x57,
+            Language::Pashto => 0x58,
+            Language::Punjabi => 0x59,
+            Language::Persian => 0x5a,
+            Language::Papiamento => 0x5b,
+            Language::Oriya => 0x5c,
+            Language::Nepali => 0x5d,
+            Language::Ndebele => 0x5e,
+            Language::Marathi => 0x5f,
+            Language::Moldavian => 0x60,
+            Language::Malaysian => 0x61,
+            Language::Malagasy => 0x62,
+            Language::Macedonian => 0x63,
+            Language::Laotian => 0x64,
+            Language::Korean => 0x65,
+            Language::Khmer => 0x66,
+            Language::Kazakh => 0x67,
+            Language::Kannada => 0x68,
+            Language::Japanese => 0x69,
+            Language::Indonesian => 0x6a,
+            Language::Hindi => 0x6b,
+            Language::Hebrew => 0x6c,
+            Language::Hausa => 0x6d,
+            Language::Guarani => 0x6e,
+            Language::Gujarati => 0x6f,
+            Language::Greek => 0x70,
+            Language::Georgian => 0x71,
+            Language::Fulani => 0x72,
+            Language::Dari => 0x73,
+            Language::Chuvash => 0x74,
+            Language::Chinese => 0x75,
+            Language::Burmese => 0x76,
+            Language::Bulgarian => 0x77,
+            Language::Bengali => 0x78,
+            Language::Belarusian => 0x79,
+            Language::Bambara => 0x7a,
+            Language::Azerbaijani => 0x7b,
+            Language::Assamese => 0x7c,
+            Language::Armenian => 0x7d,
+            Language::Arabic => 0x7e,
+            Language::Amharic => 0x7f,
+            Language::Reserved(n) => n,
+        }
+    }
+}
+
+impl Language {
+    /// Finds the language by its name, as returned by [`Language::name`], ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        (0..=0x7f)
+            .map(Self::from)
+            .filter(|language| !matches!(language, Self::Reserved(_)))
+            .find(|language| language.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl BlockSizeInfo {
+    /// Returns the language of the block.
+    pub fn language(&self, block_number: u8) -> Option<Language> {
+        self.language_codes
+            .get(block_number as usize)
+            .map(|&code| Language::from(code))
+    }
+}
+
+impl CDText<'_> {
+    /// Returns the language of the block, as declared by its Block Size Info.
+    pub fn block_language(&self, block_number: u8) -> Option<Language> {
+        self.block_size_info(block_number)?.language(block_number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::{BlockBuilder, CDTextBuilder, CDTextPackType};
+
+    #[test]
+    fn codes() {
+        assert_eq!(Language::from(0x09), Language::English);
+        assert_eq!(Language::from(0x69), Language::Japanese);
+        assert_eq!(Language::from(0x56), Language::Russian);
+        assert_eq!(Language::from(0x80), Language::Reserved(0x80));
+
+        for code in 0..=u8::MAX {
+            assert_eq!(u8::from(Language::from(code)), code);
+        }
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(Language::German.name(), "German");
+        assert_eq!(Language::from_name("japanese"), Some(Language::Japanese));
+        assert_eq!(Language::from_name("Klingon"), None);
+
+        for code in 0..=0x7f {
+            let language = Language::from(code);
+
+            if !matches!(language, Language::Reserved(_)) {
+                assert_eq!(Language::from_name(language.name()), Some(language));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn block_languages() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_block(BlockBuilder::new(Language::Japanese).with_text(
+                CDTextPackType::Title,
+                0,
+                "Album",
+            ))
+            .build()
+            .unwrap();
+
+        let cdtext = CDText::from_data(&data);
+        assert_eq!(cdtext.block_language(0), Some(Language::English));
+        assert_eq!(cdtext.block_language(1), Some(Language::Japanese));
+        assert_eq!(cdtext.block_language(2), None);
+    }
+}
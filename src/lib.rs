@@ -1,104 +1,555 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
-
-use alloc::{borrow::ToOwned, string::String, vec::Vec};
-use num_derive::FromPrimitive;
-use num_traits::FromPrimitive;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::ops::Range;
+
+mod block_info;
+#[cfg(feature = "alloc")]
+mod borrowed;
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "alloc")]
+mod cdio;
+#[cfg(feature = "alloc")]
+mod cdrdao;
+mod charset;
+mod code;
+mod crc;
+#[cfg(feature = "alloc")]
+mod cue;
+#[cfg(feature = "device")]
+mod device;
+mod diagnostics;
+#[cfg(feature = "alloc")]
+mod diff;
+#[cfg(feature = "alloc")]
+mod disc;
+mod display;
+#[cfg(feature = "alloc")]
+mod entries;
+mod error;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(feature = "alloc")]
+mod field;
+#[cfg(feature = "heapless")]
+mod fixed;
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+mod genre;
+#[cfg(feature = "alloc")]
+mod hex_dump;
+#[cfg(feature = "id3v2")]
+mod id3v2;
+#[cfg(feature = "json")]
+mod json;
+mod language;
+#[cfg(feature = "alloc")]
+mod musicbrainz;
+#[cfg(feature = "alloc")]
+mod normalize;
+mod options;
+#[cfg(feature = "alloc")]
+mod parser;
+#[cfg(feature = "alloc")]
+mod query;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+mod reassembly;
+#[cfg(feature = "alloc")]
+mod report;
+mod sequence;
+#[cfg(feature = "alloc")]
+mod streaming;
+#[cfg(feature = "alloc")]
+mod subchannel;
+mod summary;
+#[cfg(feature = "synthetic")]
+mod synthetic;
+#[cfg(feature = "alloc")]
+mod toc;
+#[cfg(feature = "alloc")]
+mod v07t;
+#[cfg(feature = "alloc")]
+mod validate;
+#[cfg(feature = "alloc")]
+mod visitor;
+#[cfg(feature = "vorbis")]
+mod vorbis;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "alloc")]
+mod xmcd;
+
+pub use block_info::BlockSizeInfo;
+#[cfg(feature = "alloc")]
+pub use borrowed::{BorrowedData, BorrowedEntry};
+#[cfg(feature = "alloc")]
+pub use builder::{
+    BlockBuilder, CDTextBuilder, DegradedText, MAX_BLOCK_PACKS, SizeEstimate, Unmappable,
+};
+#[cfg(feature = "alloc")]
+pub use cdio::CdioField;
+#[cfg(feature = "alloc")]
+pub use cdrdao::CdrdaoText;
+pub use charset::Charset;
+pub use code::{Isrc, Upc};
+pub use crc::{finalize_pack, pack_crc};
+#[cfg(feature = "alloc")]
+pub use cue::cue_cdtext_file;
+#[cfg(feature = "device")]
+pub use device::read_device;
+pub use diagnostics::{Diagnostic, Recovery};
+#[cfg(feature = "alloc")]
+pub use diff::{Difference, diff};
+#[cfg(feature = "alloc")]
+pub use disc::{Disc, TrackInfo};
+#[cfg(feature = "alloc")]
+pub use entries::Entries;
+#[cfg(feature = "std")]
+pub use error::CueFileError;
+#[cfg(feature = "heapless")]
+pub use error::FixedParseError;
+pub use error::{
+    BuildError, CDTextError, CapacityHint, CueError, DeviceError, InputSheetError, TocFileError,
+};
+#[cfg(feature = "alloc")]
+pub use field::Field;
+#[cfg(feature = "heapless")]
+pub use fixed::FixedEntry;
+#[cfg(feature = "arbitrary")]
+pub use fuzzing::PackStream;
+#[cfg(feature = "alloc")]
+pub use genre::Genre;
+pub use genre::GenreCode;
+#[cfg(feature = "alloc")]
+pub use hex_dump::parse_hex_dump;
+#[cfg(feature = "id3v2")]
+pub use id3v2::Id3Frame;
+#[cfg(feature = "json")]
+pub use json::JSON_SCHEMA_VERSION;
+pub use language::Language;
+#[cfg(feature = "alloc")]
+pub use normalize::normalize;
+pub use options::{ParseMode, ParserOptions, TextDecoding};
+#[cfg(feature = "mmap")]
+pub use reader::MappedFile;
+#[cfg(feature = "alloc")]
+pub use report::{ReportFormat, render_report};
+pub use sequence::SequenceGap;
+#[cfg(feature = "alloc")]
+pub use streaming::StreamingParser;
+#[cfg(feature = "alloc")]
+pub use subchannel::{
+    SUBCHANNEL_FRAME_SIZE, SubchannelFormat, packs_from_subchannel, subchannel_frames,
+};
+pub use summary::Summary;
+#[cfg(feature = "synthetic")]
+pub use synthetic::SyntheticDisc;
+#[cfg(feature = "alloc")]
+pub use toc::{AdditionalToc, Msf, Toc, TocInterval, TocTrack};
+#[cfg(feature = "alloc")]
+pub use v07t::to_v07t;
+#[cfg(feature = "alloc")]
+pub use validate::{IssueKind, Severity, ValidationIssue, ValidationReport};
+#[cfg(feature = "alloc")]
+pub use visitor::CDTextVisitor;
+
+#[cfg(feature = "alloc")]
+use sequence::SequenceChecker;
 
 /// Main parser structure.
 pub struct CDText<'data> {
-    _length: usize,
+    /// Length of the data as declared by the service header, excluding the header.
+    /// Greater than the length of `data` if the buffer is truncated.
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    length: usize,
+    /// Service bytes stripped before the first pack. Used to report file offsets.
+    header: &'data [u8],
     data: &'data [u8],
+    /// Bytes past the length declared by the header.
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    padding: &'data [u8],
+    options: ParserOptions,
 }
 
 /// The pack type
-#[derive(Debug, FromPrimitive, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CDTextPackType {
-    Title = 0x80,
-    Performers = 0x81,
-    Songwriters = 0x82,
-    Composers = 0x83,
-    Arrangers = 0x84,
-    Message = 0x85,
-    DiscID = 0x86,
-    Genre = 0x87,
-    TOC = 0x88,
-    AdditionalTOC = 0x89,
-    ClosedInfo = 0x8d,
-    Code = 0x8e,
-    BlockSizeInfo = 0x8f,
+    Title,
+    Performers,
+    Songwriters,
+    Composers,
+    Arrangers,
+    Message,
+    DiscID,
+    Genre,
+    TOC,
+    AdditionalTOC,
+    ClosedInfo,
+    Code,
+    BlockSizeInfo,
+    /// A pack type reserved by the specification (0x8A-0x8C). Holds the raw type byte.
+    Reserved(u8),
+    /// A pack type not defined by the specification. Holds the raw type byte.
+    Unknown(u8),
+}
+
+impl From<u8> for CDTextPackType {
+    fn from(value: u8) -> Self {
+        match value {
+            0x80 => Self::Title,
+            0x81 => Self::Performers,
+            0x82 => Self::Songwriters,
+            0x83 => Self::Composers,
+            0x84 => Self::Arrangers,
+            0x85 => Self::Message,
+            0x86 => Self::DiscID,
+            0x87 => Self::Genre,
+            0x88 => Self::TOC,
+            0x89 => Self::AdditionalTOC,
+            n @ 0x8a..=0x8c => Self::Reserved(n),
+            0x8d => Self::ClosedInfo,
+            0x8e => Self::Code,
+            0x8f => Self::BlockSizeInfo,
+            n => Self::Unknown(n),
+        }
+    }
+}
+
+impl From<CDTextPackType> for u8 {
+    fn from(value: CDTextPackType) -> Self {
+        match value {
+            CDTextPackType::Title => 0x80,
+            CDTextPackType::Performers => 0x81,
+            CDTextPackType::Songwriters => 0x82,
+            CDTextPackType::Composers => 0x83,
+            CDTextPackType::Arrangers => 0x84,
+            CDTextPackType::Message => 0x85,
+            CDTextPackType::DiscID => 0x86,
+            CDTextPackType::Genre => 0x87,
+            CDTextPackType::TOC => 0x88,
+            CDTextPackType::AdditionalTOC => 0x89,
+            CDTextPackType::ClosedInfo => 0x8d,
+            CDTextPackType::Code => 0x8e,
+            CDTextPackType::BlockSizeInfo => 0x8f,
+            CDTextPackType::Reserved(n) | CDTextPackType::Unknown(n) => n,
+        }
+    }
 }
 
 /// Track number entry referring to.
 /// Entry can refer to whole album or on separate track in it.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CDTextTrackNumber {
     WholeAlbum,
     Track(u8),
 }
 
+impl From<u8> for CDTextTrackNumber {
+    fn from(value: u8) -> Self {
+        match value {
+            // A zero track value indicates that the information pertains to the whole album.
+            0 => Self::WholeAlbum,
+            n => Self::Track(n),
+        }
+    }
+}
+
+impl From<CDTextTrackNumber> for u8 {
+    fn from(value: CDTextTrackNumber) -> Self {
+        match value {
+            CDTextTrackNumber::WholeAlbum => 0,
+            CDTextTrackNumber::Track(n) => n,
+        }
+    }
+}
+
 /// A pack itself.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CDTextPack {
     pub pack_type: CDTextPackType,
     pub track_number: CDTextTrackNumber,
     pub seq_counter: u8,
+    /// Number of characters of the string the pack starts with stored in preceding packs.
+    /// The field is 4 bits wide, so 15 means "15 or more".
     pub character_position: u8,
     pub block_number: u8,
     pub is_double_byte_characters: bool,
 
     pub payload: [u8; 12],
     pub crc: u16,
+    /// Whether the stored CRC matches the one computed over the pack contents.
+    pub crc_valid: bool,
 }
 
-/// Data can be represented as string or raw data.
-#[derive(Debug, Clone)]
+impl CDTextPack {
+    /// Serializes the pack to its 18-byte form: 4-byte header, payload, and CRC.
+    ///
+    /// The stored CRC is written as is, even if it doesn't match the contents.
+    /// Use [`finalize_pack`] on the result to write the correct one.
+    pub fn to_bytes(&self) -> [u8; 18] {
+        let mut data = [0u8; 18];
+
+        data[0] = u8::from(self.pack_type);
+        data[1] = u8::from(self.track_number);
+        data[2] = self.seq_counter;
+        data[3] = (self.is_double_byte_characters as u8) << 7
+            | (self.block_number & 0b111) << 4
+            | self.character_position.min(15);
+        data[4..16].copy_from_slice(&self.payload);
+        data[16..].copy_from_slice(&self.crc.to_be_bytes());
+
+        data
+    }
+
+    /// Computes the correct CRC over the header and the payload of the pack.
+    pub fn compute_crc(&self) -> u16 {
+        pack_crc(&self.to_bytes())
+    }
+
+    /// Whether the stored CRC matches the contents of the pack.
+    ///
+    /// Unlike [`CDTextPack::crc_valid`], which is set by the parser, this takes into account
+    /// changes made to the pack since.
+    pub fn verify_crc(&self) -> bool {
+        self.compute_crc() == self.crc
+    }
+
+    /// Replaces the stored CRC with the correct one.
+    pub fn fix_crc(&mut self) {
+        self.crc = self.compute_crc();
+        self.crc_valid = true;
+    }
+}
+
+/// Parses a single pack, e.g. one received from a drive.
+///
+/// Fails if the pack type is unknown or the CRC doesn't match, like the strict parser does.
+/// Errors refer to the pack as the first one, at offset 0.
+impl TryFrom<&[u8; 18]> for CDTextPack {
+    type Error = CDTextError;
+
+    fn try_from(data: &[u8; 18]) -> Result<Self, Self::Error> {
+        let pack = CDText::parse_pack(data);
+
+        if let CDTextPackType::Unknown(pack_type) = pack.pack_type {
+            return Err(CDTextError::InvalidPackType {
+                pack_index: 0,
+                offset: 0,
+                pack_type,
+            });
+        }
+
+        if !pack.crc_valid {
+            return Err(CDTextError::CrcMismatch {
+                pack_index: 0,
+                offset: 0,
+                expected: pack.compute_crc(),
+                found: pack.crc,
+            });
+        }
+
+        Ok(pack)
+    }
+}
+
+/// Parses a single pack like `TryFrom<&[u8; 18]>`, also failing if the data is not 18 bytes long.
+impl TryFrom<&[u8]> for CDTextPack {
+    type Error = CDTextError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        let data: &[u8; 18] = data.try_into().map_err(|_| CDTextError::ShortPack {
+            pack_index: 0,
+            offset: 0,
+            length: data.len(),
+        })?;
+
+        Self::try_from(data)
+    }
+}
+
+/// Data can be represented as string, raw data, or a structure decoded from binary packs.
+#[cfg(feature = "alloc")]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CDTextEntryDataType {
     String(String),
     Data(Vec<u8>),
+    Genre(Genre),
+    Upc(Upc),
+    Isrc(Isrc),
+    Toc(Toc),
+    AdditionalToc(AdditionalToc),
+    BlockSizeInfo(BlockSizeInfo),
 }
 
 /// The processed entry.
-#[derive(Debug, Clone)]
+#[cfg(feature = "alloc")]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CDTextEntry {
     pub track_number: CDTextTrackNumber,
+    /// Block the entry comes from. Each block holds text in a separate language.
+    pub block_number: u8,
     pub entry_type: CDTextPackType,
     pub data: CDTextEntryDataType,
+    /// Indices of the packs the entry is assembled from, as yielded by [`CDText::iter_pack_chunks`],
+    /// including the pack with the terminator of the string. Binary data, e.g. TOC, covers
+    /// all the packs of its type in the block. Tells which entries are affected by a damaged pack.
+    pub packs: Range<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl CDTextEntry {
+    /// Whether the entry is private information of the mastering facility
+    /// (Closed Information), not intended to be shown to the user.
+    pub fn is_private(&self) -> bool {
+        self.entry_type == CDTextPackType::ClosedInfo
+    }
 }
 
 impl<'data> CDText<'data> {
-    /// Creates a parser from data, assuming that first 4 bytes are used for service info.
-    /// First two bytes are the data length minus two.
-    pub fn from_data_with_length(data: &'data [u8]) -> Self {
+    /// Creates a parser from data, detecting whether it starts with the 4-byte service header
+    /// (as returned by READ TOC/PMA/ATIP command) or with the first pack.
+    ///
+    /// Data with the header may be shorter than the length the header declares, e.g. a dump
+    /// cut off in the middle. The packs that are there are parsed, and the truncation
+    /// is reported as [`CDTextError::LengthMismatch`].
+    pub fn new(data: &'data [u8]) -> Self {
+        if Self::has_service_header(data, Some(data.len())) {
+            Self::from_received_data(data)
+        } else {
+            Self::from_data(data)
+        }
+    }
+
+    /// Internal function. Creates a parser from data starting with the service header,
+    /// like [`CDText::from_data_with_length`], but keeps the packs of data shorter than
+    /// the declared length. The truncation is reported during parsing.
+    /// The data must be at least 4 bytes long.
+    fn from_received_data(data: &'data [u8]) -> Self {
+        if let Ok(cdtext) = Self::from_data_with_length(data) {
+            return cdtext;
+        }
+
+        let declared = u16::from_be_bytes([data[0], data[1]]) as usize;
+
         Self {
-            _length: (((data[0] as usize) << 8) | (data[1] as usize)) - 2,
+            length: declared.saturating_sub(2),
+            header: &data[..4],
             data: &data[4..],
+            padding: &[],
+            options: ParserOptions::default(),
+        }
+    }
+
+    /// Internal function. Guesses whether the data starts with the service header.
+    /// `total_length` is the length of the whole data, if it's known.
+    fn has_service_header(data: &[u8], total_length: Option<usize>) -> bool {
+        let is_pack = |pack: &[u8]| {
+            pack.len() >= 18
+                && (0x80..=0x8f).contains(&pack[0])
+                && crc::crc16(&pack[..16]) == u16::from_be_bytes([pack[16], pack[17]])
+        };
+
+        // Intact first pack is the most reliable sign.
+        if is_pack(data.get(4..).unwrap_or_default()) {
+            return true;
+        }
+
+        if is_pack(data) {
+            return false;
+        }
+
+        // Otherwise check that the header is plausible: reserved bytes are zero,
+        // and the length covers a whole number of packs that fits in the buffer.
+        let [high, low, 0, 0, pack_type, ..] = *data else {
+            return false;
+        };
+
+        let declared = u16::from_be_bytes([high, low]) as usize;
+
+        (0x80..=0x8f).contains(&pack_type)
+            && declared >= 2
+            && total_length.is_none_or(|length| declared + 2 <= length)
+            && (declared - 2).is_multiple_of(18)
+    }
+
+    /// Creates a parser from data, assuming that first 4 bytes are used for service info.
+    /// First two bytes are the data length minus two.
+    ///
+    /// Fails if the buffer is shorter than the declared length.
+    /// Bytes past the declared length (e.g. trailing zero byte appended by cdrecord) are ignored.
+    pub fn from_data_with_length(data: &'data [u8]) -> Result<Self, CDTextError> {
+        if data.len() < 4 {
+            return Err(CDTextError::TruncatedHeader { length: data.len() });
+        }
+
+        let declared = u16::from_be_bytes([data[0], data[1]]) as usize;
+        let actual = data.len() - 2;
+
+        // Declared length covers two reserved bytes of the header too.
+        if declared < 2 || declared > actual {
+            return Err(CDTextError::LengthMismatch { declared, actual });
         }
+
+        Ok(Self {
+            length: declared - 2,
+            header: &data[..4],
+            data: &data[4..declared + 2],
+            padding: &data[declared + 2..],
+            options: ParserOptions::default(),
+        })
     }
 
     /// Creates a parser from data.
     pub fn from_data(data: &'data [u8]) -> Self {
         Self {
-            _length: data.len(),
+            length: data.len(),
+            header: &[],
             data,
+            padding: &[],
+            options: ParserOptions::default(),
         }
     }
 
-    /// Internal method. Parses a separate pack from data.
-    /// Data (sub)slice must be 18 bytes long.
-    fn parse_pack(&self, subdata: &[u8]) -> Option<CDTextPack> {
-        debug_assert!(subdata.len() == 18);
+    /// Whether the data started with the 4-byte service header.
+    pub fn has_header(&self) -> bool {
+        !self.header.is_empty()
+    }
+
+    /// Sets the options used for parsing.
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
 
+    /// Internal method. Returns the offset of the pack with given index in the original buffer.
+    #[cfg(any(feature = "alloc", feature = "heapless"))]
+    fn pack_offset(&self, pack_index: usize) -> usize {
+        self.header.len() + pack_index * 18
+    }
+
+    /// Internal method. Parses a separate pack from data.
+    fn parse_pack(subdata: &[u8; 18]) -> CDTextPack {
         // The first byte of each pack contains the pack type.
-        let pack_type = CDTextPackType::from_u8(subdata[0])?;
+        let pack_type = CDTextPackType::from(subdata[0]);
 
         // The second byte often gives the track number of the pack.
-        let track_number = match subdata[1] {
-            // However, a zero track value indicates that the information pertains to the whole album.
-            0 => CDTextTrackNumber::WholeAlbum,
-            n => CDTextTrackNumber::Track(n),
-        };
+        // However, a zero track value indicates that the information pertains to the whole album.
+        let track_number = CDTextTrackNumber::from(subdata[1]);
 
         // The third byte is a sequential counter.
         let seq_counter = subdata[2];
@@ -112,158 +563,199 @@ impl<'data> CDText<'data> {
         // bit 7: Is 0 if single byte characters, 1 if double-byte characters.
         let is_double_byte_chars = ((subdata[3] >> 7) & 1) != 0;
 
-        let payload = &subdata[4..16];
+        let mut payload = [0u8; 12];
+        payload.copy_from_slice(&subdata[4..16]);
 
-        let crc = u16::from_be_bytes(subdata[16..18].try_into().unwrap());
+        // Last two bytes are the CRC of the preceding 16 bytes, stored big-endian.
+        let crc = u16::from_be_bytes([subdata[16], subdata[17]]);
+        let crc_valid = crc::crc16(&subdata[..16]) == crc;
 
-        Some(CDTextPack {
+        CDTextPack {
             pack_type,
             track_number,
             seq_counter,
             character_position,
             block_number: block_nr,
             is_double_byte_characters: is_double_byte_chars,
-            payload: payload.try_into().unwrap(),
+            payload,
             crc,
-        })
+            crc_valid,
+        }
     }
 
-    /// Wrapper method.
-    pub fn iter_pack_chunks(&self) -> impl Iterator<Item = Option<CDTextPack>> {
+    /// Wrapper method. Yields every complete pack in the data, including ones with
+    /// mismatching CRC or unknown type.
+    /// Incomplete trailing pack is not yielded, see [`CDText::trailing_bytes`].
+    pub fn iter_pack_chunks(&self) -> impl Iterator<Item = CDTextPack> {
         // Each pack consists of a 4-byte header, 12 bytes of payload, and 2 bytes of CRC.
         // 4 + 12 + 2 = 18
-        self.data.chunks(18).map(|x| self.parse_pack(x))
+        let (packs, _) = self.data.as_chunks::<18>();
+
+        packs.iter().map(Self::parse_pack)
     }
 
-    /// Parses all the entries from the data and returns a Vec with parsed entries.
-    pub fn parse(&self) -> Vec<CDTextEntry> {
-        let mut payload_buffer: Vec<u8> = Vec::with_capacity(16);
-        let mut prev_pack = self.iter_pack_chunks().next().unwrap().unwrap();
-
-        let mut parsed_data: Vec<CDTextEntry> = Vec::new();
-
-        for pack in self.iter_pack_chunks().skip(1) {
-            let pack = pack.as_ref().unwrap();
-
-            // let index = if pack.character_position <= 12 {
-            //     12 - pack.character_position
-            // } else {
-            //     0
-            // } as usize;
-
-            let index = 12u8.saturating_sub(pack.character_position) as usize;
-
-            match pack.pack_type {
-                CDTextPackType::Arrangers
-                | CDTextPackType::Composers
-                | CDTextPackType::Title
-                | CDTextPackType::Performers
-                | CDTextPackType::Songwriters => {
-                    let mut track_number = prev_pack.track_number;
-                    let mut before = &prev_pack.payload[..index];
-                    let after = &prev_pack.payload[index..];
-
-                    let is_terminal = before.ends_with(&[0]);
-
-                    // I don't know why 2.
-                    // More than one nul-terminated strings can be encountered in one entry (usually in short strings).
-                    // So we need to handle it somehow.
-                    if before.iter().filter(|&x| *x == 0).count() == 2 {
-                        // println!("===== INCREMENT! {before:?}");
-
-                        let position = before.iter().position(|&x| x == 0).unwrap();
-                        payload_buffer.extend_from_slice(&before[..position]);
-
-                        if !payload_buffer.is_empty() {
-                            // println!("===== PAYLOAD: {payload_buffer:?}");
-
-                            parsed_data.push(CDTextEntry {
-                                track_number,
-                                entry_type: prev_pack.pack_type,
-                                data: CDTextEntryDataType::String(
-                                    str::from_utf8(&payload_buffer).unwrap().to_owned(),
-                                ),
-                            });
-                        } else {
-                            parsed_data.push(CDTextEntry {
-                                track_number,
-                                entry_type: prev_pack.pack_type,
-                                data: CDTextEntryDataType::String(
-                                    str::from_utf8(&payload_buffer).unwrap().to_owned(),
-                                ),
-                            });
-                        }
-
-                        payload_buffer.clear();
-
-                        before = &before[before.iter().position(|&x| x == 0).unwrap_or(0) + 1..];
-
-                        if let CDTextTrackNumber::Track(nr) = track_number {
-                            track_number = CDTextTrackNumber::Track(nr + 1);
-                        } else if let CDTextTrackNumber::WholeAlbum = track_number {
-                            track_number = CDTextTrackNumber::Track(1);
-                        }
-                    }
-
-                    payload_buffer.extend_from_slice(if is_terminal {
-                        let len = before.iter().rev().position(|x| *x != 0);
-
-                        if let Some(ix) = len {
-                            &before[..before.len() - ix]
-                        } else {
-                            before
-                        }
-                    } else {
-                        before
-                    });
-
-                    // println!("Before: {before:?}");
-                    // println!("After: {after:?}");
-
-                    // println!("{:x?} ({:?} / {index})", pack, unsafe {
-                    //     str::from_utf8_unchecked(&payload_buffer)
-                    // });
-
-                    if is_terminal {
-                        parsed_data.push(CDTextEntry {
-                            track_number,
-                            entry_type: prev_pack.pack_type,
-                            data: CDTextEntryDataType::String(
-                                str::from_utf8(&payload_buffer).unwrap().trim_end_matches(|x| x as u32 == 0).to_owned(),
-                            ),
-                        });
-
-                        payload_buffer.clear();
-                    }
-
-                    payload_buffer.extend_from_slice(after);
-                }
-                _ => {
-                    break;
-                },
-            };
-
-            prev_pack = pack.clone();
+    /// Returns bytes of a truncated pack at the end of the data, if there's any.
+    pub fn trailing_bytes(&self) -> &'data [u8] {
+        self.data.as_chunks::<18>().1
+    }
+
+    /// Serializes the parsed packs back, along with the service header, the truncated pack,
+    /// and the bytes past the declared length, if there are any.
+    ///
+    /// The result is identical to the data the parser has been created from: packs keep
+    /// their order, padding, and stored CRCs, even mismatching ones, and packs of unknown type
+    /// are serialized as is.
+    #[cfg(feature = "alloc")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.header.len() + self.data.len() + self.padding.len());
+
+        data.extend_from_slice(self.header);
+
+        for pack in self.iter_pack_chunks() {
+            data.extend_from_slice(&pack.to_bytes());
         }
 
-        // println!("[{payload_buffer:?}]: Prev pack: {prev_pack:?}");
+        data.extend_from_slice(self.trailing_bytes());
+        data.extend_from_slice(self.padding);
+
+        data
+    }
+
+    /// Checks that sequence numbers of the packs are continuous.
+    /// Returns all the discontinuities found, i.e. places where packs are missing.
+    #[cfg(feature = "alloc")]
+    pub fn sequence_gaps(&self) -> Vec<SequenceGap> {
+        let mut sequence = SequenceChecker::default();
+
+        self.iter_pack_chunks()
+            .enumerate()
+            .filter_map(|(pack_index, pack)| sequence.check(pack_index, &pack))
+            .collect()
+    }
+}
 
-        payload_buffer.extend_from_slice(&prev_pack.payload[..prev_pack.payload.iter().position(|&x| x == 0).unwrap()]);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_bytes_saturates_character_position() {
+        let pack = CDTextPack {
+            pack_type: CDTextPackType::Title,
+            track_number: CDTextTrackNumber::Track(1),
+            seq_counter: 3,
+            character_position: 20,
+            block_number: 1,
+            is_double_byte_characters: false,
+            payload: [0; 12],
+            crc: 0,
+            crc_valid: false,
+        };
+
+        assert_eq!(pack.to_bytes()[..4], [0x80, 1, 3, 0x1f]);
+    }
 
-        parsed_data.push(CDTextEntry {
-            track_number: prev_pack.track_number,
-            entry_type: prev_pack.pack_type,
-            data: CDTextEntryDataType::String(
-                str::from_utf8(&payload_buffer).unwrap().to_owned(),
-            ),
-        });
+    #[test]
+    fn compute_and_verify_crc() {
+        let mut pack = CDTextPack::try_from(b"\x80\x00\x00\x00My Album\x00Ope\x07\x6e").unwrap();
+        assert!(pack.crc_valid);
+        assert_eq!(pack.compute_crc(), 0x076e);
+        assert!(pack.verify_crc());
 
-        // println!("Length is: {}", self.length);
+        pack.seq_counter = 1;
+        assert!(!pack.verify_crc());
 
-        // for i in parsed_data {
-        //     println!("{:?} => {:?}", i.track_number, i.data);
-        // }
+        pack.fix_crc();
+        assert!(pack.verify_crc());
+        assert_eq!(pack.to_bytes()[16..], pack.compute_crc().to_be_bytes());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn to_bytes_is_identical_to_input() {
+        let packs = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "Track")
+            .build()
+            .unwrap();
+
+        // Header, packs with one mismatching CRC, a truncated pack and cdrecord's trailing zero.
+        let mut data = Vec::new();
+        data.extend_from_slice(&((packs.len() + 7 + 2) as u16).to_be_bytes());
+        data.extend_from_slice(&[0, 0]);
+        data.extend_from_slice(&packs);
+        data[4 + 17] ^= 0xff;
+        data.extend_from_slice(&packs[..7]);
+        data.push(0);
+
+        let cdtext = CDText::from_data_with_length(&data).unwrap();
+        assert!(!cdtext.iter_pack_chunks().next().unwrap().crc_valid);
+        assert_eq!(cdtext.trailing_bytes().len(), 7);
+        assert_eq!(cdtext.to_bytes(), data);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn truncated_dump_with_header() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "Track")
+            .with_header(true)
+            .build()
+            .unwrap();
+
+        // The last Block Size Info pack is lost.
+        let truncated = &data[..data.len() - 18];
+        let cdtext = CDText::new(truncated);
+        assert!(cdtext.has_header());
+        assert_eq!(cdtext.iter_pack_chunks().count(), (data.len() - 4) / 18 - 1);
+
+        let mismatch = CDTextError::LengthMismatch {
+            declared: data.len() - 2,
+            actual: truncated.len() - 2,
+        };
+        assert_eq!(cdtext.parse(), Err(mismatch.clone()));
+
+        let (entries, diagnostics) = cdtext.parse_with_diagnostics();
+        assert_eq!(
+            diagnostics,
+            [Diagnostic {
+                problem: mismatch,
+                recovery: Recovery::DataUsed,
+            }]
+        );
+        assert_eq!(entries, CDText::new(&data).parse().unwrap()[..2]);
+    }
 
-        parsed_data
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn split_pack_with_header() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "Track")
+            .with_header(true)
+            .build()
+            .unwrap();
+
+        // A dump cut off 5 bytes before the end keeps all the packs but the last one.
+        let truncated = &data[..data.len() - 5];
+        let cdtext = CDText::new(truncated).with_options(ParserOptions::lenient());
+        assert_eq!(cdtext.iter_pack_chunks().count(), (data.len() - 4) / 18 - 1);
+        assert_eq!(
+            cdtext.trailing_bytes(),
+            &data[data.len() - 18..data.len() - 5]
+        );
+
+        let (entries, diagnostics) = cdtext.parse_with_diagnostics();
+        assert_eq!(entries, CDText::new(&data).parse().unwrap()[..2]);
+        assert_eq!(
+            diagnostics[0].problem,
+            CDTextError::ShortPack {
+                pack_index: (data.len() - 4) / 18 - 1,
+                offset: data.len() - 18,
+                length: 13,
+            }
+        );
+        assert_eq!(cdtext.parse().unwrap(), entries);
     }
 }
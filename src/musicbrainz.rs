@@ -0,0 +1,202 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{CDText, CDTextEntryDataType, CDTextError, Toc};
+
+/// Internal function. Computes SHA-1 digest of the data.
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+    // The message is padded with 0x80, zeros and its length in bits to a multiple of 64 bytes.
+    let mut message = Vec::from(data);
+    message.push(0x80);
+
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for chunk in message.chunks_exact(64) {
+        let mut words = [0u32; 80];
+
+        for (word, bytes) in words.iter_mut().zip(chunk.chunks_exact(4)) {
+            *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+
+        for index in 16..80 {
+            words[index] =
+                (words[index - 3] ^ words[index - 8] ^ words[index - 14] ^ words[index - 16])
+                    .rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+
+        for (index, &word) in words.iter().enumerate() {
+            let (f, k) = match index {
+                0..20 => ((b & c) | (!b & d), 0x5a827999),
+                20..40 => (b ^ c ^ d, 0x6ed9eba1),
+                40..60 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+                _ => (b ^ c ^ d, 0xca62c1d6),
+            };
+
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(word);
+
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (value, add) in state.iter_mut().zip([a, b, c, d, e]) {
+            *value = value.wrapping_add(add);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+
+    for (bytes, value) in digest.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&value.to_be_bytes());
+    }
+
+    digest
+}
+
+/// Internal function. Encodes the data in Base64 with the URL-safe alphabet used by MusicBrainz:
+/// `.`, `_` and `-` instead of `+`, `/` and `=`.
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789._";
+
+    let mut encoded = String::new();
+
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let value = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (value >> (18 - index * 6)) & 0x3f;
+                encoded.push(ALPHABET[sextet as usize] as char);
+            } else {
+                encoded.push('-');
+            }
+        }
+    }
+
+    encoded
+}
+
+impl Toc {
+    /// Computes the MusicBrainz disc ID of the TOC, used to look up the release.
+    ///
+    /// The ID is SHA-1 over the first and last track numbers, the lead-out offset and offsets
+    /// of 99 tracks (zero for missing ones), encoded in Base64.
+    pub fn musicbrainz_id(&self) -> String {
+        let mut text = String::new();
+
+        let _ = write!(
+            text,
+            "{:02X}{:02X}{:08X}",
+            self.first_track,
+            self.last_track,
+            self.lead_out.to_frames()
+        );
+
+        for number in 1..=99 {
+            let offset = self
+                .track_start(number)
+                .map_or(0, |start| start.to_frames());
+            let _ = write!(text, "{offset:08X}");
+        }
+
+        base64(&sha1(text.as_bytes()))
+    }
+}
+
+impl CDText<'_> {
+    /// Computes the MusicBrainz disc ID from TOC packs, see [`Toc::musicbrainz_id`].
+    /// Returns `None` if there are no TOC packs.
+    pub fn musicbrainz_id(&self) -> Result<Option<String>, CDTextError> {
+        let entries = self.parse()?;
+
+        Ok(entries.iter().find_map(|entry| match &entry.data {
+            CDTextEntryDataType::Toc(toc) => Some(toc.musicbrainz_id()),
+            _ => None,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CDTextBuilder, CDTextPackType, Msf, TocTrack};
+
+    /// Internal function. Returns the MSF of the frame number.
+    fn msf(frames: u32) -> Msf {
+        Msf {
+            minute: (frames / 75 / 60) as u8,
+            second: (frames / 75 % 60) as u8,
+            frame: (frames % 75) as u8,
+        }
+    }
+
+    #[test]
+    fn sha1_digest() {
+        let hex = |digest: [u8; 20]| {
+            digest.iter().fold(String::new(), |mut text, byte| {
+                let _ = write!(text, "{byte:02x}");
+                text
+            })
+        };
+
+        assert_eq!(hex(sha1(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(
+            hex(sha1(b"abc")),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            hex(sha1(&[b'a'; 1000])),
+            "291e9a6c66994949b57ba5e650361e98fc36b1ba"
+        );
+    }
+
+    #[test]
+    fn reference_disc() {
+        // The example of the MusicBrainz disc ID calculation documentation.
+        let offsets = [150, 15363, 32314, 46592, 63414, 80489];
+        let toc = Toc {
+            first_track: 1,
+            last_track: 6,
+            lead_out: msf(95462),
+            tracks: (1..)
+                .zip(offsets)
+                .map(|(number, offset)| TocTrack {
+                    number,
+                    start: msf(offset),
+                })
+                .collect(),
+        };
+
+        assert_eq!(toc.musicbrainz_id(), "49HHV7Eb8UKF3aQiNmu1GR8vKTY-");
+    }
+
+    #[test]
+    fn without_toc_packs() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .build()
+            .unwrap();
+
+        assert_eq!(CDText::from_data(&data).musicbrainz_id(), Ok(None));
+    }
+}
@@ -0,0 +1,171 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{CDText, CDTextEntry, CDTextEntryDataType, CDTextError};
+
+/// Cleans up text of sloppily mastered discs: leading and trailing whitespace is trimmed,
+/// runs of whitespace inside the text are collapsed into a single space,
+/// and entries that repeat an earlier one exactly, except for [`CDTextEntry::packs`], are dropped.
+///
+/// Applies to text entries and the text of genres. Codes and binary entries are left
+/// as they are, except for duplicates. The order of the remaining entries is kept.
+pub fn normalize(entries: &mut Vec<CDTextEntry>) {
+    for entry in entries.iter_mut() {
+        match &mut entry.data {
+            CDTextEntryDataType::String(text) => normalize_text(text),
+            CDTextEntryDataType::Genre(genre) => {
+                if let Some(text) = &mut genre.text {
+                    normalize_text(text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut unique = Vec::with_capacity(entries.len());
+
+    // Repeated packs give the same entry again, so where it comes from isn't compared.
+    for entry in entries.drain(..) {
+        let is_duplicate = unique.iter().any(|other: &CDTextEntry| {
+            other.track_number == entry.track_number
+                && other.block_number == entry.block_number
+                && other.entry_type == entry.entry_type
+                && other.data == entry.data
+        });
+
+        if !is_duplicate {
+            unique.push(entry);
+        }
+    }
+
+    *entries = unique;
+}
+
+/// Internal function. Trims the text and collapses runs of whitespace into a single space.
+fn normalize_text(text: &mut String) {
+    let is_normal = text
+        .split(' ')
+        .all(|word| !word.is_empty() && !word.contains(char::is_whitespace));
+
+    // Most text is already clean, so it's only rebuilt when needed.
+    if is_normal || text.is_empty() {
+        return;
+    }
+
+    let mut normalized = String::with_capacity(text.len());
+
+    for word in text.split_whitespace() {
+        if !normalized.is_empty() {
+            normalized.push(' ');
+        }
+
+        normalized.push_str(word);
+    }
+
+    *text = normalized;
+}
+
+impl CDText<'_> {
+    /// Parses the data and normalizes the entries, see [`normalize`].
+    pub fn parse_normalized(&self) -> Result<Vec<CDTextEntry>, CDTextError> {
+        let mut entries = self.parse()?;
+        normalize(&mut entries);
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CDTextBuilder, CDTextPackType, CDTextTrackNumber, Genre, GenreCode};
+
+    /// Internal function. Creates an entry of the album from the packs.
+    fn entry(
+        entry_type: CDTextPackType,
+        data: CDTextEntryDataType,
+        packs: core::ops::Range<usize>,
+    ) -> CDTextEntry {
+        CDTextEntry {
+            track_number: CDTextTrackNumber::WholeAlbum,
+            block_number: 0,
+            entry_type,
+            data,
+            packs,
+        }
+    }
+
+    #[test]
+    fn text() {
+        let normalized = |text: &str| {
+            let mut text = String::from(text);
+            normalize_text(&mut text);
+            text
+        };
+
+        assert_eq!(normalized("Album"), "Album");
+        assert_eq!(normalized("The  Album"), "The Album");
+        assert_eq!(normalized("  The Album\t"), "The Album");
+        assert_eq!(normalized("The\u{3000}\nAlbum"), "The Album");
+        assert_eq!(normalized(" \t "), "");
+        assert_eq!(normalized(""), "");
+    }
+
+    #[test]
+    fn entries() {
+        let string = |text: &str| CDTextEntryDataType::String(String::from(text));
+        let code = CDTextEntryDataType::Data(Vec::from(*b"  "));
+
+        let mut entries = Vec::from([
+            entry(CDTextPackType::Title, string(" Album "), 0..1),
+            entry(CDTextPackType::Performers, string("Band"), 1..2),
+            entry(CDTextPackType::Title, string("Album"), 4..5),
+            entry(CDTextPackType::Message, code.clone(), 2..3),
+            entry(
+                CDTextPackType::Genre,
+                CDTextEntryDataType::Genre(Genre {
+                    code: GenreCode::Jazz,
+                    text: Some(String::from("Smooth  Jazz")),
+                }),
+                3..4,
+            ),
+            entry(CDTextPackType::Performers, string("Band  "), 5..6),
+        ]);
+        normalize(&mut entries);
+
+        assert_eq!(
+            entries,
+            [
+                entry(CDTextPackType::Title, string("Album"), 0..1),
+                entry(CDTextPackType::Performers, string("Band"), 1..2),
+                entry(CDTextPackType::Message, code, 2..3),
+                entry(
+                    CDTextPackType::Genre,
+                    CDTextEntryDataType::Genre(Genre {
+                        code: GenreCode::Jazz,
+                        text: Some(String::from("Smooth Jazz")),
+                    }),
+                    3..4,
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_normalized() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "  The   Album ")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .build()
+            .unwrap();
+
+        let entries = CDText::from_data(&data).parse_normalized().unwrap();
+        assert_eq!(
+            entries[0].data,
+            CDTextEntryDataType::String(String::from("The Album"))
+        );
+        assert_eq!(
+            entries[1].data,
+            CDTextEntryDataType::String(String::from("First"))
+        );
+    }
+}
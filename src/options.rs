@@ -0,0 +1,150 @@
+use crate::Charset;
+
+/// Parser behavior on data that violates the CD-Text specification.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseMode {
+    /// Any violation (bad CRC, unknown pack type, truncated pack, undecodable text) is an error.
+    #[default]
+    Strict,
+    /// Violations are tolerated and the parser recovers as much text as possible.
+    /// Packs of unknown type are skipped, and packs with bad CRC are used as is.
+    Lenient,
+}
+
+/// What to do with text that cannot be decoded.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextDecoding {
+    /// Treat as a violation of the specification: fail with [`crate::CDTextError::InvalidText`]
+    /// in strict mode, return as raw [`crate::CDTextEntryDataType::Data`] otherwise.
+    #[default]
+    Strict,
+    /// Replace invalid sequences with U+FFFD REPLACEMENT CHARACTER.
+    Lossy,
+    /// Return the undecodable entry as raw [`crate::CDTextEntryDataType::Data`].
+    Raw,
+}
+
+/// Options that control parsing.
+///
+/// Options are set per parser, so the same data can be parsed with different options
+/// by creating another [`crate::CDText`] over it.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParserOptions {
+    mode: ParseMode,
+    text_decoding: TextDecoding,
+    expand_tabs: bool,
+    charset: Option<Charset>,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self::strict()
+    }
+}
+
+impl ParserOptions {
+    /// Options that reject any violation of the specification, including undecodable text.
+    pub fn strict() -> Self {
+        Self {
+            mode: ParseMode::Strict,
+            text_decoding: TextDecoding::Strict,
+            expand_tabs: true,
+            charset: None,
+        }
+    }
+
+    /// Options that recover as much text as possible, decoding invalid text lossily.
+    pub fn lenient() -> Self {
+        Self {
+            mode: ParseMode::Lenient,
+            text_decoding: TextDecoding::Lossy,
+            ..Self::strict()
+        }
+    }
+
+    /// Sets the parse mode.
+    pub fn with_mode(mut self, mode: ParseMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the policy for text that cannot be decoded.
+    pub fn with_text_decoding(mut self, text_decoding: TextDecoding) -> Self {
+        self.text_decoding = text_decoding;
+        self
+    }
+
+    /// Sets whether a string consisting of a single TAB (0x09) character, which means
+    /// "same as previous track", is replaced with the previous track's string. Enabled by default.
+    pub fn with_tab_expansion(mut self, expand_tabs: bool) -> Self {
+        self.expand_tabs = expand_tabs;
+        self
+    }
+
+    /// Forces the charset of all the blocks, overriding the one declared by Block Size Info.
+    ///
+    /// Useful for discs mastered with a wrong character code, e.g. Latin-1 text
+    /// in blocks without Block Size Info, which are decoded as UTF-8 otherwise.
+    pub fn with_charset(mut self, charset: Charset) -> Self {
+        self.charset = Some(charset);
+        self
+    }
+
+    /// Returns the parse mode.
+    pub fn mode(&self) -> ParseMode {
+        self.mode
+    }
+
+    /// Returns the policy for text that cannot be decoded.
+    pub fn text_decoding(&self) -> TextDecoding {
+        self.text_decoding
+    }
+
+    /// Returns whether "same as previous track" markers are expanded.
+    pub fn expands_tabs(&self) -> bool {
+        self.expand_tabs
+    }
+
+    /// Returns the charset forced for all the blocks, if any.
+    pub fn charset(&self) -> Option<Charset> {
+        self.charset
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{CDText, CDTextEntryDataType, CDTextError, finalize_pack};
+    use alloc::string::String;
+
+    #[test]
+    fn forced_charset() {
+        let mut pack = [0; 18];
+        pack[..4].copy_from_slice(&[0x80, 0, 0, 0]);
+        pack[4..16].copy_from_slice(b"Caf\xe9\0Cr\xe8me\0\0");
+        finalize_pack(&mut pack);
+
+        // Without Block Size Info the text is expected to be UTF-8.
+        assert!(matches!(
+            CDText::from_data(&pack).parse(),
+            Err(CDTextError::InvalidText { pack_index: 0, .. })
+        ));
+
+        let options = ParserOptions::strict().with_charset(Charset::Iso8859_1);
+        let entries = CDText::from_data(&pack)
+            .with_options(options)
+            .parse()
+            .unwrap();
+        assert_eq!(
+            entries[0].data,
+            CDTextEntryDataType::String(String::from("Café"))
+        );
+        assert_eq!(
+            entries[1].data,
+            CDTextEntryDataType::String(String::from("Crème"))
+        );
+    }
+}
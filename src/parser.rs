@@ -0,0 +1,1131 @@
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::String,
+    vec::Vec,
+};
+use core::ops::Range;
+
+use crate::{
+    AdditionalToc, BlockSizeInfo, CDText, CDTextEntry, CDTextEntryDataType, CDTextError,
+    CDTextPack, CDTextPackType, CDTextTrackNumber, Charset, Diagnostic, Genre, GenreCode, Isrc,
+    ParseMode, Recovery, TextDecoding, Toc, Upc,
+    borrowed::{BorrowedData, BorrowedEntry, EntrySink},
+    crc,
+    reassembly::{StringStream, expected_position, feed_strings},
+    sequence::SequenceChecker,
+};
+
+/// Internal structure. Decides what to do on violations of the specification
+/// and collects diagnostics.
+struct Reporter {
+    mode: ParseMode,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Reporter {
+    fn new(mode: ParseMode) -> Self {
+        Self {
+            mode,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Returns an error in strict mode, lets the parser recover otherwise.
+    fn violation(&mut self, problem: CDTextError, recovery: Recovery) -> Result<(), CDTextError> {
+        match self.mode {
+            ParseMode::Strict => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(?problem, "parsing stopped");
+
+                Err(problem)
+            }
+            ParseMode::Lenient => {
+                self.note(problem, recovery);
+                Ok(())
+            }
+        }
+    }
+
+    /// Records a problem that is handled the same way in any mode.
+    fn note(&mut self, problem: CDTextError, recovery: Recovery) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(?problem, ?recovery, "recovered from a problem");
+
+        self.diagnostics.push(Diagnostic { problem, recovery });
+    }
+}
+
+/// Internal structure. State of reassembly of a string stream of one pack type.
+struct TextRun {
+    pack_type: CDTextPackType,
+    block_number: u8,
+    /// Track the string being collected belongs to.
+    track: u8,
+    /// Index of the pack the string being collected starts in.
+    pack_index: usize,
+    /// Index of the last pack of the stream so far.
+    last_pack_index: usize,
+    buffer: Vec<u8>,
+    /// Empty strings (track, pack index, index of the pack with the terminator)
+    /// that are not known to be padding yet.
+    pending_empty: Vec<(u8, usize, usize)>,
+    /// The last emitted string, used to expand "same as previous track" markers.
+    previous: Vec<u8>,
+    /// Number of binary bytes each string starts with, that are taken as is, even if zero.
+    prefix_length: usize,
+    /// Number of bytes each character takes: 2 in double-byte packs, 1 otherwise.
+    character_width: usize,
+    /// Charset declared for the block. Without Block Size Info the text is assumed to be UTF-8.
+    charset: Option<Charset>,
+}
+
+impl TextRun {
+    /// Starts the stream of the pack, reusing buffers of the finished one, if any,
+    /// so they aren't allocated again for each pack type.
+    fn new(
+        pack_index: usize,
+        pack: &CDTextPack,
+        charset: Option<Charset>,
+        finished: Option<TextRun>,
+    ) -> Self {
+        let (mut buffer, mut pending_empty, mut previous) = match finished {
+            Some(run) => (run.buffer, run.pending_empty, run.previous),
+            None => (Vec::with_capacity(16), Vec::new(), Vec::new()),
+        };
+
+        buffer.clear();
+        pending_empty.clear();
+        previous.clear();
+
+        Self {
+            pack_type: pack.pack_type,
+            block_number: pack.block_number,
+            track: 0,
+            pack_index,
+            last_pack_index: pack_index,
+            buffer,
+            pending_empty,
+            previous,
+            // Genre text is preceded by the 2-byte genre code.
+            prefix_length: match pack.pack_type {
+                CDTextPackType::Genre => 2,
+                _ => 0,
+            },
+            character_width: if pack.is_double_byte_characters { 2 } else { 1 },
+            charset,
+        }
+    }
+
+    /// Whether nothing but zeros has been collected. Such strings may be padding.
+    ///
+    /// The genre of the disc is never blank: genre code 0 without text is still a genre.
+    /// Only the zeros following it are padding.
+    fn is_blank(&self) -> bool {
+        let is_genre = self.pack_type == CDTextPackType::Genre && self.track == 0;
+
+        !is_genre && self.buffer.iter().all(|&x| x == 0)
+    }
+
+    /// Indices of the packs the string collected so far is stored in, up to its terminator.
+    fn packs(&self) -> Range<usize> {
+        self.pack_index..self.last_pack_index + 1
+    }
+}
+
+/// Internal structure. State of parsing kept between packs.
+pub(crate) struct ParseState {
+    reporter: Reporter,
+    run: Option<TextRun>,
+    sequence: SequenceChecker,
+    /// Binary packs and their indices, decoded once all of them are collected.
+    binary_packs: Vec<(usize, CDTextPack)>,
+    charsets: [Option<Charset>; 8],
+}
+
+impl ParseState {
+    pub(crate) fn new(cdtext: &CDText, mode: ParseMode) -> Self {
+        Self {
+            reporter: Reporter::new(mode),
+            run: None,
+            sequence: SequenceChecker::default(),
+            binary_packs: Vec::new(),
+            charsets: cdtext.block_charsets(),
+        }
+    }
+
+    /// Sets the charset used for text of the block from now on.
+    pub(crate) fn set_charset(&mut self, block_number: u8, charset: Option<Charset>) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(block = block_number, ?charset, "charset of the block");
+
+        self.charsets[block_number as usize] = charset;
+    }
+
+    /// Returns all the problems the parser has recovered from so far.
+    pub(crate) fn diagnostics(&self) -> &[Diagnostic] {
+        &self.reporter.diagnostics
+    }
+
+    /// Returns all the problems the parser has recovered from, ending parsing.
+    pub(crate) fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.reporter.diagnostics
+    }
+}
+
+impl<'data> CDText<'data> {
+    /// Parses all the entries from the data and returns a Vec with parsed entries.
+    ///
+    /// In strict mode (the default) fails on any damaged pack, see [`crate::ParserOptions`].
+    pub fn parse(&self) -> Result<Vec<CDTextEntry>, CDTextError> {
+        let mut entries = Vec::new();
+        self.parse_entries(
+            &mut ParseState::new(self, self.options.mode()),
+            &mut entries,
+        )?;
+
+        Ok(entries)
+    }
+
+    /// Parses all the entries, recovering from any problem like in lenient mode.
+    /// Returns the entries along with descriptions of all the problems encountered.
+    ///
+    /// Text that cannot be decoded under [`TextDecoding::Strict`] is returned as raw data.
+    pub fn parse_with_diagnostics(&self) -> (Vec<CDTextEntry>, Vec<Diagnostic>) {
+        let mut state = ParseState::new(self, ParseMode::Lenient);
+        let mut entries = Vec::new();
+
+        // Lenient mode recovers from every violation, but should an error get through anyway,
+        // the entries parsed so far are still returned.
+        if let Err(problem) = self.parse_entries(&mut state, &mut entries) {
+            state.reporter.note(problem, Recovery::ParsingStopped);
+        }
+
+        (entries, state.reporter.diagnostics)
+    }
+
+    /// Parses all the entries and returns them along with all the packs of the data,
+    /// so decoded text can be shown next to the raw packs without reading the data twice.
+    /// [`CDTextEntry::packs`] are indices into the returned packs.
+    pub fn parse_with_packs(&self) -> Result<(Vec<CDTextEntry>, Vec<CDTextPack>), CDTextError> {
+        let mut state = ParseState::new(self, self.options.mode());
+        let mut entries = Vec::new();
+        let mut packs = Vec::with_capacity(self.data.len() / 18);
+
+        for (pack_index, pack) in self.iter_pack_chunks().enumerate() {
+            packs.push(pack.clone());
+            self.feed_pack(&mut state, pack_index, pack, &mut entries)?;
+        }
+
+        self.finish_parsing(&mut state, &mut entries)?;
+
+        Ok((entries, packs))
+    }
+
+    /// Internal method. Parses all the entries, passing them to the sink.
+    pub(crate) fn parse_entries(
+        &self,
+        state: &mut ParseState,
+        sink: &mut dyn EntrySink<'data>,
+    ) -> Result<(), CDTextError> {
+        for (pack_index, pack) in self.iter_pack_chunks().enumerate() {
+            self.feed_pack(state, pack_index, pack, sink)?;
+        }
+
+        self.finish_parsing(state, sink)
+    }
+
+    /// Internal method. Parses the next pack, passing the entries it completes to the sink.
+    pub(crate) fn feed_pack(
+        &self,
+        state: &mut ParseState,
+        pack_index: usize,
+        pack: CDTextPack,
+        sink: &mut dyn EntrySink<'data>,
+    ) -> Result<(), CDTextError> {
+        let ParseState {
+            reporter,
+            run,
+            sequence,
+            binary_packs,
+            charsets,
+        } = state;
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            pack_index,
+            pack_type = ?pack.pack_type,
+            track = u8::from(pack.track_number),
+            block = pack.block_number,
+            sequence = pack.seq_counter,
+            character = pack.character_position,
+            double_byte = pack.is_double_byte_characters,
+            crc_valid = pack.crc_valid,
+            "pack"
+        );
+
+        if !self.accept_pack(reporter, sequence, pack_index, &pack)? {
+            return Ok(());
+        }
+
+        match pack.pack_type {
+            CDTextPackType::Arrangers
+            | CDTextPackType::Composers
+            | CDTextPackType::Title
+            | CDTextPackType::Performers
+            | CDTextPackType::Songwriters
+            | CDTextPackType::Message
+            | CDTextPackType::DiscID
+            | CDTextPackType::Genre
+            | CDTextPackType::ClosedInfo
+            | CDTextPackType::Code => {}
+            // Binary data spread over several packs is decoded once all the packs are collected.
+            CDTextPackType::TOC | CDTextPackType::AdditionalTOC | CDTextPackType::BlockSizeInfo => {
+                if let Some(mut finished) = run.take() {
+                    self.finish_run(reporter, &mut finished, sink)?;
+                }
+
+                sink.binary_pack(pack_index, &pack);
+                binary_packs.push((pack_index, pack));
+                return Ok(());
+            }
+            // Contents of reserved packs are unknown, keep them as is.
+            CDTextPackType::Reserved(_) => {
+                if let Some(mut finished) = run.take() {
+                    self.finish_run(reporter, &mut finished, sink)?;
+                }
+
+                sink.binary_pack(pack_index, &pack);
+                Self::emit(
+                    sink,
+                    BorrowedEntry {
+                        track_number: pack.track_number,
+                        block_number: pack.block_number,
+                        entry_type: pack.pack_type,
+                        data: BorrowedData::Other(CDTextEntryDataType::Data(pack.payload.to_vec())),
+                        packs: pack_index..pack_index + 1,
+                    },
+                );
+                return Ok(());
+            }
+            // Such packs have been rejected already.
+            CDTextPackType::Unknown(_) => return Ok(()),
+        };
+
+        // Strings of one type in one block form a continuous stream spread over
+        // consecutive packs. A pack of another type or block starts a new stream.
+        let run = match &mut *run {
+            Some(run)
+                if run.pack_type == pack.pack_type && run.block_number == pack.block_number =>
+            {
+                self.check_continuity(reporter, run, pack_index, &pack, sink)?;
+                run
+            }
+            _ => {
+                let mut finished = run.take();
+
+                if let Some(finished) = &mut finished {
+                    self.finish_run(reporter, finished, sink)?;
+                }
+
+                let run = run.insert(TextRun::new(
+                    pack_index,
+                    &pack,
+                    charsets[pack.block_number as usize],
+                    finished,
+                ));
+
+                // The stream must start with a beginning of a string.
+                self.check_continuity(reporter, run, pack_index, &pack, sink)?;
+                run
+            }
+        };
+
+        run.last_pack_index = pack_index;
+
+        let (width, prefix_length) = (run.character_width, run.prefix_length);
+        let mut stream = RunStream {
+            cdtext: self,
+            reporter,
+            run,
+            sink,
+        };
+
+        feed_strings(&mut stream, pack_index, &pack.payload, width, prefix_length)
+    }
+
+    /// Internal method. Emits what's left after the last pack: the unterminated string,
+    /// if any, and entries decoded from binary packs.
+    pub(crate) fn finish_parsing(
+        &self,
+        state: &mut ParseState,
+        sink: &mut dyn EntrySink<'data>,
+    ) -> Result<(), CDTextError> {
+        let ParseState {
+            reporter,
+            run,
+            binary_packs,
+            ..
+        } = state;
+
+        if let Some(mut finished) = run.take() {
+            self.finish_run(reporter, &mut finished, sink)?;
+        }
+
+        let packs = || binary_packs.iter().map(|(_, pack)| pack);
+
+        if let Some((_, first)) = binary_packs.first() {
+            let block_number = first.block_number;
+
+            if let Some(toc) = Toc::from_packs(block_number, packs()) {
+                Self::emit(
+                    sink,
+                    BorrowedEntry {
+                        track_number: CDTextTrackNumber::WholeAlbum,
+                        block_number,
+                        entry_type: CDTextPackType::TOC,
+                        data: BorrowedData::Other(CDTextEntryDataType::Toc(toc)),
+                        packs: binary_range(binary_packs, block_number, CDTextPackType::TOC),
+                    },
+                );
+            }
+
+            if let Some(toc) = AdditionalToc::from_packs(block_number, packs()) {
+                Self::emit(
+                    sink,
+                    BorrowedEntry {
+                        track_number: CDTextTrackNumber::WholeAlbum,
+                        block_number,
+                        entry_type: CDTextPackType::AdditionalTOC,
+                        data: BorrowedData::Other(CDTextEntryDataType::AdditionalToc(toc)),
+                        packs: binary_range(
+                            binary_packs,
+                            block_number,
+                            CDTextPackType::AdditionalTOC,
+                        ),
+                    },
+                );
+            }
+        }
+
+        let mut blocks: Vec<u8> = Vec::new();
+
+        for pack in packs() {
+            if pack.pack_type == CDTextPackType::BlockSizeInfo
+                && !blocks.contains(&pack.block_number)
+            {
+                blocks.push(pack.block_number);
+            }
+        }
+
+        for block_number in blocks {
+            if let Some(size_info) = BlockSizeInfo::from_packs(block_number, packs()) {
+                Self::emit(
+                    sink,
+                    BorrowedEntry {
+                        track_number: CDTextTrackNumber::WholeAlbum,
+                        block_number,
+                        entry_type: CDTextPackType::BlockSizeInfo,
+                        data: BorrowedData::Other(CDTextEntryDataType::BlockSizeInfo(size_info)),
+                        packs: binary_range(
+                            binary_packs,
+                            block_number,
+                            CDTextPackType::BlockSizeInfo,
+                        ),
+                    },
+                );
+            }
+        }
+
+        let trailing = self.trailing_bytes();
+
+        if !trailing.is_empty() {
+            self.check_trailing(state, self.data.len() / 18, trailing.len())?;
+        }
+
+        if self.length > self.data.len() {
+            self.check_length(state, self.length + 2, self.data.len() + 2)?;
+        }
+
+        Ok(())
+    }
+
+    /// Internal method. Reports a truncated pack at the end of the data.
+    pub(crate) fn check_trailing(
+        &self,
+        state: &mut ParseState,
+        pack_index: usize,
+        length: usize,
+    ) -> Result<(), CDTextError> {
+        let problem = CDTextError::ShortPack {
+            pack_index,
+            offset: self.pack_offset(pack_index),
+            length,
+        };
+
+        // A truncated pack can't be recovered.
+        state.reporter.violation(problem, Recovery::PackSkipped)
+    }
+
+    /// Internal method. Reports data shorter than the length declared by the service header.
+    /// Both lengths exclude the two length bytes, like in [`CDTextError::LengthMismatch`].
+    pub(crate) fn check_length(
+        &self,
+        state: &mut ParseState,
+        declared: usize,
+        actual: usize,
+    ) -> Result<(), CDTextError> {
+        let problem = CDTextError::LengthMismatch { declared, actual };
+
+        // The packs received before the end of the data are used.
+        state.reporter.violation(problem, Recovery::DataUsed)
+    }
+
+    /// Internal method. Returns charsets of each block, as declared by Block Size Info
+    /// or forced by the options.
+    fn block_charsets(&self) -> [Option<Charset>; 8] {
+        if let Some(charset) = self.options.charset() {
+            return [Some(charset); 8];
+        }
+
+        // Only Block Size Info packs are parsed here, the rest are parsed once, by the main loop.
+        let (chunks, _) = self.data.as_chunks::<18>();
+        let packs: Vec<CDTextPack> = chunks
+            .iter()
+            .filter(|chunk| CDTextPackType::from(chunk[0]) == CDTextPackType::BlockSizeInfo)
+            .map(Self::parse_pack)
+            .collect();
+
+        let charsets = core::array::from_fn(|block_number| {
+            BlockSizeInfo::from_packs(block_number as u8, &packs).map(|info| info.charset())
+        });
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(?charsets, "charsets declared by Block Size Info");
+
+        charsets
+    }
+
+    /// Internal method. Checks the pack for damage.
+    /// Returns whether the pack should be used.
+    fn accept_pack(
+        &self,
+        reporter: &mut Reporter,
+        sequence: &mut SequenceChecker,
+        pack_index: usize,
+        pack: &CDTextPack,
+    ) -> Result<bool, CDTextError> {
+        let offset = self.pack_offset(pack_index);
+
+        if let Some(gap) = sequence.check(pack_index, pack) {
+            let problem = CDTextError::SequenceGap {
+                pack_index,
+                offset,
+                expected: gap.expected,
+                found: gap.found,
+            };
+
+            // Packs after a gap are still fine.
+            reporter.violation(problem, Recovery::DataUsed)?;
+        }
+
+        if let CDTextPackType::Unknown(pack_type) = pack.pack_type {
+            let problem = CDTextError::InvalidPackType {
+                pack_index,
+                offset,
+                pack_type,
+            };
+
+            reporter.violation(problem, Recovery::PackSkipped)?;
+            return Ok(false);
+        }
+
+        if !pack.crc_valid {
+            let problem = CDTextError::CrcMismatch {
+                pack_index,
+                offset,
+                expected: crc::crc16(&pack.to_bytes()[..16]),
+                found: pack.crc,
+            };
+
+            // Damaged packs are still better than nothing.
+            reporter.violation(problem, Recovery::DataUsed)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Internal method. Checks that the pack header agrees with the reassembly state.
+    ///
+    /// The track number of a pack is the track of its first character, and the character
+    /// position is the number of characters of that string stored in preceding packs.
+    /// On mismatch (e.g. a pack was lost) the state is resynchronized with the header.
+    fn check_continuity(
+        &self,
+        reporter: &mut Reporter,
+        run: &mut TextRun,
+        pack_index: usize,
+        pack: &CDTextPack,
+        sink: &mut dyn EntrySink<'data>,
+    ) -> Result<(), CDTextError> {
+        let offset = self.pack_offset(pack_index);
+        let found_track = u8::from(pack.track_number);
+
+        if found_track != run.track {
+            let problem = CDTextError::TrackNumberMismatch {
+                pack_index,
+                offset,
+                expected: run.track,
+                found: found_track,
+            };
+
+            reporter.violation(problem, Recovery::Resynchronized)?;
+
+            // Don't lose the text collected so far, even if incomplete.
+            if !run.buffer.is_empty() {
+                self.complete_string(reporter, run, pack_index, None, sink)?;
+            }
+
+            run.track = found_track;
+            run.buffer.clear();
+            return Ok(());
+        }
+
+        let expected_position = expected_position(&run.buffer, run.character_width);
+
+        if pack.character_position != expected_position {
+            let problem = CDTextError::CharacterPositionMismatch {
+                pack_index,
+                offset,
+                expected: expected_position,
+                found: pack.character_position,
+            };
+
+            reporter.violation(problem, Recovery::DataUsed)?;
+        }
+
+        Ok(())
+    }
+
+    /// Internal method. Emits the string collected so far. `source` is the same string
+    /// in the data, if it's stored there as is.
+    ///
+    /// Empty strings are held back until a non-empty one follows, because unused space
+    /// at the end of the last pack of a stream is padded with NULs too.
+    fn complete_string(
+        &self,
+        reporter: &mut Reporter,
+        run: &mut TextRun,
+        pack_index: usize,
+        mut source: Option<&'data [u8]>,
+        sink: &mut dyn EntrySink<'data>,
+    ) -> Result<(), CDTextError> {
+        if run.is_blank() {
+            run.pending_empty
+                .push((run.track, pack_index, run.last_pack_index));
+            run.previous.clear();
+            run.buffer.clear();
+            return Ok(());
+        }
+
+        for (track, pack_index, last_pack_index) in core::mem::take(&mut run.pending_empty) {
+            Self::emit(
+                sink,
+                BorrowedEntry {
+                    track_number: CDTextTrackNumber::from(track),
+                    block_number: run.block_number,
+                    entry_type: run.pack_type,
+                    data: self
+                        .decode_entry(reporter, run, track, pack_index, &[])?
+                        .into(),
+                    packs: last_pack_index..last_pack_index + 1,
+                },
+            );
+        }
+
+        // A single TAB means the string is the same as for the previous track.
+        let is_tab =
+            run.buffer.len() == run.character_width && run.buffer.iter().all(|&x| x == 0x09);
+
+        if self.options.expands_tabs() && is_tab && !run.previous.is_empty() {
+            run.buffer.clone_from(&run.previous);
+            source = None;
+        }
+
+        let data = match source.and_then(|bytes| Self::borrow_text(run, bytes)) {
+            Some(text) => BorrowedData::Text(Cow::Borrowed(text)),
+            None => self
+                .decode_entry(reporter, run, run.track, run.pack_index, &run.buffer)?
+                .into(),
+        };
+
+        Self::emit(
+            sink,
+            BorrowedEntry {
+                track_number: CDTextTrackNumber::from(run.track),
+                block_number: run.block_number,
+                entry_type: run.pack_type,
+                data,
+                packs: run.packs(),
+            },
+        );
+
+        core::mem::swap(&mut run.previous, &mut run.buffer);
+        run.buffer.clear();
+
+        Ok(())
+    }
+
+    /// Internal function. Passes the completed entry to the sink.
+    fn emit(sink: &mut dyn EntrySink<'data>, entry: BorrowedEntry<'data>) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            track = u8::from(entry.track_number),
+            block = entry.block_number,
+            entry_type = ?entry.entry_type,
+            data = ?entry.data,
+            "entry"
+        );
+
+        sink.entry(entry);
+    }
+
+    /// Internal method. Finishes the stream, emitting the unterminated string, if any.
+    fn finish_run(
+        &self,
+        reporter: &mut Reporter,
+        run: &mut TextRun,
+        sink: &mut dyn EntrySink<'data>,
+    ) -> Result<(), CDTextError> {
+        if run.is_blank() {
+            #[cfg(feature = "tracing")]
+            if !run.pending_empty.is_empty() {
+                tracing::trace!(
+                    pack_type = ?run.pack_type,
+                    block = run.block_number,
+                    strings = run.pending_empty.len(),
+                    "empty strings skipped as padding"
+                );
+            }
+
+            return Ok(());
+        }
+
+        let problem = CDTextError::UnterminatedText {
+            pack_index: run.pack_index,
+            offset: self.pack_offset(run.pack_index),
+        };
+
+        reporter.violation(problem, Recovery::DataUsed)?;
+
+        let pack_index = run.pack_index;
+        self.complete_string(reporter, run, pack_index, None, sink)
+    }
+
+    /// Internal function. Returns the text without copying, if it needs no conversion to UTF-8.
+    /// Codes and genres are always decoded.
+    fn borrow_text(run: &TextRun, bytes: &'data [u8]) -> Option<&'data str> {
+        match run.pack_type {
+            CDTextPackType::Code | CDTextPackType::Genre => None,
+            _ => match run.charset {
+                Some(charset) => charset.decode_borrowed(bytes),
+                None => str::from_utf8(bytes).ok(),
+            },
+        }
+    }
+
+    /// Internal method. Converts collected payload bytes into entry data of the pack type.
+    fn decode_entry(
+        &self,
+        reporter: &mut Reporter,
+        run: &TextRun,
+        track: u8,
+        pack_index: usize,
+        bytes: &[u8],
+    ) -> Result<CDTextEntryDataType, CDTextError> {
+        match run.pack_type {
+            // The album has UPC/EAN code, and each track has its ISRC.
+            CDTextPackType::Code if !bytes.is_empty() => {
+                let data = self.decode_text(reporter, run.charset, pack_index, bytes)?;
+
+                let code = match &data {
+                    CDTextEntryDataType::String(text) if track == 0 => {
+                        Upc::parse(text).map(CDTextEntryDataType::Upc)
+                    }
+                    CDTextEntryDataType::String(text) => {
+                        Isrc::parse(text).map(CDTextEntryDataType::Isrc)
+                    }
+                    _ => None,
+                };
+
+                if let Some(code) = code {
+                    return Ok(code);
+                }
+
+                let problem = CDTextError::InvalidCode {
+                    pack_index,
+                    offset: self.pack_offset(pack_index),
+                };
+
+                reporter.violation(problem, Recovery::DataUsed)?;
+
+                Ok(data)
+            }
+            CDTextPackType::Genre => {
+                let (code, text) = bytes.split_at(bytes.len().min(2));
+                let code = match *code {
+                    [high, low] => u16::from_be_bytes([high, low]),
+                    _ => 0,
+                };
+
+                let text = match self.decode_text(reporter, run.charset, pack_index, text)? {
+                    CDTextEntryDataType::String(text) => (!text.is_empty()).then_some(text),
+                    _ => return Ok(CDTextEntryDataType::Data(bytes.to_vec())),
+                };
+
+                Ok(CDTextEntryDataType::Genre(Genre {
+                    code: GenreCode::from(code),
+                    text,
+                }))
+            }
+            _ => self.decode_text(reporter, run.charset, pack_index, bytes),
+        }
+    }
+
+    /// Internal method. Converts collected payload bytes into entry data using
+    /// the charset of the block and the text decoding policy.
+    fn decode_text(
+        &self,
+        reporter: &mut Reporter,
+        charset: Option<Charset>,
+        pack_index: usize,
+        bytes: &[u8],
+    ) -> Result<CDTextEntryDataType, CDTextError> {
+        let text = match charset {
+            Some(charset) if !charset.is_supported() => {
+                let problem = CDTextError::UnsupportedCharset {
+                    pack_index,
+                    offset: self.pack_offset(pack_index),
+                    character_code: u8::from(charset),
+                };
+
+                reporter.violation(problem, Recovery::TextKeptAsData)?;
+
+                return Ok(CDTextEntryDataType::Data(bytes.to_vec()));
+            }
+            Some(charset) => charset.decode(bytes),
+            None => str::from_utf8(bytes).ok().map(ToOwned::to_owned),
+        };
+
+        if let Some(text) = text {
+            return Ok(CDTextEntryDataType::String(text));
+        }
+
+        let problem = CDTextError::InvalidText {
+            pack_index,
+            offset: self.pack_offset(pack_index),
+        };
+
+        match self.options.text_decoding() {
+            TextDecoding::Strict => {
+                reporter.violation(problem, Recovery::TextKeptAsData)?;
+
+                Ok(CDTextEntryDataType::Data(bytes.to_vec()))
+            }
+            TextDecoding::Lossy => {
+                reporter.note(problem, Recovery::TextReplaced);
+
+                let text = match charset {
+                    Some(charset) => charset.decode_lossy(bytes),
+                    None => String::from_utf8_lossy(bytes).into_owned(),
+                };
+
+                Ok(CDTextEntryDataType::String(text))
+            }
+            TextDecoding::Raw => {
+                reporter.note(problem, Recovery::TextKeptAsData);
+
+                Ok(CDTextEntryDataType::Data(bytes.to_vec()))
+            }
+        }
+    }
+}
+
+/// Internal structure. The text run being fed with the payload of a pack by [`feed_strings`],
+/// along with what completing its strings takes.
+struct RunStream<'a, 'data> {
+    cdtext: &'a CDText<'data>,
+    reporter: &'a mut Reporter,
+    run: &'a mut TextRun,
+    sink: &'a mut dyn EntrySink<'data>,
+}
+
+impl StringStream for RunStream<'_, '_> {
+    type Error = CDTextError;
+
+    fn buffer(&self) -> &[u8] {
+        &self.run.buffer
+    }
+
+    fn push(&mut self, pack_index: usize, bytes: &[u8]) -> Result<(), CDTextError> {
+        if self.run.buffer.is_empty() {
+            self.run.pack_index = pack_index;
+        }
+
+        self.run.buffer.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn emit(&mut self, pack_index: usize, end: usize) -> Result<(), CDTextError> {
+        let run = &mut *self.run;
+
+        // A string that starts in this pack can be borrowed from the data as is.
+        let source = (run.pack_index == pack_index && !run.buffer.is_empty())
+            .then(|| {
+                self.cdtext
+                    .data
+                    .get(pack_index * 18 + 4..)?
+                    .get(end - run.buffer.len()..end)
+            })
+            .flatten();
+
+        self.cdtext
+            .complete_string(self.reporter, run, pack_index, source, self.sink)?;
+        run.track = run.track.saturating_add(1);
+
+        Ok(())
+    }
+}
+
+/// Internal function. Returns the range of indices of the binary packs of the type in the block.
+fn binary_range(
+    binary_packs: &[(usize, CDTextPack)],
+    block_number: u8,
+    pack_type: CDTextPackType,
+) -> Range<usize> {
+    let mut indices = binary_packs
+        .iter()
+        .filter(|(_, pack)| pack.block_number == block_number && pack.pack_type == pack_type)
+        .map(|&(pack_index, _)| pack_index);
+
+    let first = indices.next().unwrap_or(0);
+    let last = indices.next_back().unwrap_or(first);
+
+    first..last + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "tracing")]
+    use crate::ParserOptions;
+    use crate::{Genre, GenreCode, finalize_pack};
+    #[cfg(feature = "tracing")]
+    extern crate std;
+
+    /// Internal function. Builds a pack with the correct CRC.
+    fn pack(pack_type: u8, track: u8, seq: u8, position: u8, payload: &[u8]) -> [u8; 18] {
+        let mut pack = [0u8; 18];
+
+        pack[0] = pack_type;
+        pack[1] = track;
+        pack[2] = seq;
+        pack[3] = position;
+        pack[4..4 + payload.len()].copy_from_slice(payload);
+        finalize_pack(&mut pack);
+
+        pack
+    }
+
+    #[test]
+    fn saturated_character_position() {
+        // 30 characters: the third pack starts 24 characters into the string, stored as 15.
+        let title = b"Thirty characters long title!!";
+        let data = [
+            pack(0x80, 0, 0, 0, &title[..12]),
+            pack(0x80, 0, 1, 12, &title[12..24]),
+            pack(0x80, 0, 2, 15, &title[24..]),
+        ]
+        .concat();
+
+        let entries = CDText::from_data(&data).parse().unwrap();
+
+        assert_eq!(
+            entries[0].data,
+            CDTextEntryDataType::String(String::from("Thirty characters long title!!"))
+        );
+    }
+
+    #[test]
+    fn genre_without_code_and_text() {
+        let data = [pack(0x80, 0, 0, 0, b"Album\0"), pack(0x87, 0, 1, 0, &[])].concat();
+
+        let entries = CDText::from_data(&data).parse().unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[1].data,
+            CDTextEntryDataType::Genre(Genre {
+                code: GenreCode::NotUsed,
+                text: None,
+            })
+        );
+    }
+
+    #[test]
+    fn lenient_parsing_recovers() {
+        let mut damaged = pack(0x80, 0, 3, 12, b"\0Track\0");
+        damaged[17] ^= 0xff;
+
+        // An unknown pack type, a sequence gap, a bad CRC, an invalid UPC and ISRC
+        // and a truncated pack.
+        let data = [
+            &pack(0x80, 0, 0, 0, b"Album title ")[..],
+            &pack(0x70, 0, 1, 0, &[])[..],
+            &damaged[..],
+            &pack(0x8e, 0, 4, 0, b"0\0bad\0")[..],
+            &[0x80, 0, 4][..],
+        ]
+        .concat();
+
+        let (entries, diagnostics) = CDText::from_data(&data).parse_with_diagnostics();
+
+        assert_eq!(
+            entries[1].data,
+            CDTextEntryDataType::String(String::from("Track"))
+        );
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.recovery)
+                .collect::<Vec<_>>(),
+            [
+                Recovery::PackSkipped,
+                Recovery::DataUsed,
+                Recovery::DataUsed,
+                Recovery::DataUsed,
+                Recovery::DataUsed,
+                Recovery::PackSkipped,
+            ]
+        );
+    }
+
+    #[test]
+    fn codes() {
+        let data = [
+            pack(0x8e, 0, 0, 0, b"036000291452"),
+            pack(0x8e, 0, 1, 12, b"\0USRC1760783"),
+            pack(0x8e, 1, 2, 11, b"9\0"),
+        ]
+        .concat();
+
+        let entries = CDText::from_data(&data).parse().unwrap();
+
+        assert_eq!(
+            entries[0].data,
+            CDTextEntryDataType::Upc(Upc::parse("036000291452").unwrap())
+        );
+        assert_eq!(
+            entries[1].data,
+            CDTextEntryDataType::Isrc(Isrc::parse("USRC17607839").unwrap())
+        );
+        assert_eq!(entries[1].track_number, CDTextTrackNumber::Track(1));
+
+        // Invalid codes are rejected in strict mode and kept as text in lenient mode.
+        let data = [pack(0x8e, 0, 0, 0, b"12345\0")].concat();
+        let cdtext = CDText::from_data(&data);
+        assert!(matches!(
+            cdtext.parse(),
+            Err(CDTextError::InvalidCode { pack_index: 0, .. })
+        ));
+
+        let (entries, _) = cdtext.parse_with_diagnostics();
+        assert_eq!(
+            entries[0].data,
+            CDTextEntryDataType::String(String::from("12345"))
+        );
+    }
+
+    /// Internal structure. Records level, message and fields of the events.
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct Recorder(std::sync::Arc<std::sync::Mutex<Vec<(tracing::Level, String)>>>);
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for Recorder {
+        fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            tracing::span::Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+        fn event(&self, event: &tracing::Event<'_>) {
+            struct Fields(String);
+
+            impl tracing::field::Visit for Fields {
+                fn record_debug(
+                    &mut self,
+                    field: &tracing::field::Field,
+                    value: &dyn core::fmt::Debug,
+                ) {
+                    self.0 += &alloc::format!(" {}={value:?}", field.name());
+                }
+            }
+
+            let mut fields = Fields(String::new());
+            event.record(&mut fields);
+
+            let mut events = self.0.lock().unwrap();
+            events.push((*event.metadata().level(), fields.0));
+        }
+
+        fn enter(&self, _span: &tracing::span::Id) {}
+
+        fn exit(&self, _span: &tracing::span::Id) {}
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_events() {
+        let mut damaged = pack(0x81, 0, 1, 0, b"Band\0");
+        damaged[17] ^= 0xff;
+        let data = [pack(0x80, 0, 0, 0, b"Album\0"), damaged].concat();
+
+        let events = |options: ParserOptions| {
+            let recorder = Recorder::default();
+            tracing::subscriber::with_default(recorder.clone(), || {
+                let _ = CDText::from_data(&data).with_options(options).parse();
+            });
+
+            let events = recorder.0.lock().unwrap();
+            events.clone()
+        };
+        let count = |events: &[(tracing::Level, String)], level, message: &str| {
+            events
+                .iter()
+                .filter(|(event_level, fields)| *event_level == level && fields.contains(message))
+                .count()
+        };
+
+        let lenient = events(ParserOptions::lenient());
+        assert_eq!(count(&lenient, tracing::Level::TRACE, "message=pack"), 2);
+        assert_eq!(count(&lenient, tracing::Level::DEBUG, "message=entry"), 2);
+        assert_eq!(count(&lenient, tracing::Level::TRACE, "crc_valid=false"), 1);
+        assert_eq!(
+            count(&lenient, tracing::Level::WARN, "problem=CrcMismatch"),
+            1
+        );
+
+        let strict = events(ParserOptions::strict());
+        assert_eq!(count(&strict, tracing::Level::DEBUG, "message=entry"), 1);
+        assert_eq!(
+            count(&strict, tracing::Level::WARN, "message=parsing stopped"),
+            1
+        );
+    }
+}
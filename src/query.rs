@@ -0,0 +1,97 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    CDText, CDTextEntry, CDTextEntryDataType, CDTextError, CDTextPackType, CDTextTrackNumber,
+};
+
+impl CDText<'_> {
+    /// Returns the title of the album.
+    ///
+    /// Like other accessors, parses the data on each call and returns `None` if it cannot be parsed.
+    pub fn album_title(&self) -> Option<String> {
+        self.text(CDTextPackType::Title, 0)
+    }
+
+    /// Returns the title of the track. Track 0 is the whole album.
+    pub fn track_title(&self, track: u8) -> Option<String> {
+        self.text(CDTextPackType::Title, track)
+    }
+
+    /// Returns the performer of the track. Track 0 is the whole album.
+    pub fn performer(&self, track: u8) -> Option<String> {
+        self.text(CDTextPackType::Performers, track)
+    }
+
+    /// Returns the songwriter of the track. Track 0 is the whole album.
+    pub fn songwriter(&self, track: u8) -> Option<String> {
+        self.text(CDTextPackType::Songwriters, track)
+    }
+
+    /// Returns the composer of the track. Track 0 is the whole album.
+    pub fn composer(&self, track: u8) -> Option<String> {
+        self.text(CDTextPackType::Composers, track)
+    }
+
+    /// Returns the arranger of the track. Track 0 is the whole album.
+    pub fn arranger(&self, track: u8) -> Option<String> {
+        self.text(CDTextPackType::Arrangers, track)
+    }
+
+    /// Returns all the entries of the track, or of the whole album, in the order of the data.
+    pub fn entries_for_track(
+        &self,
+        track_number: CDTextTrackNumber,
+    ) -> Result<Vec<CDTextEntry>, CDTextError> {
+        let mut entries = self.parse()?;
+        entries.retain(|entry| entry.track_number == track_number);
+
+        Ok(entries)
+    }
+
+    /// Returns all the entries of the block, in the order of the data.
+    pub fn entries_for_block(&self, block_number: u8) -> Result<Vec<CDTextEntry>, CDTextError> {
+        let mut entries = self.parse()?;
+        entries.retain(|entry| entry.block_number == block_number);
+
+        Ok(entries)
+    }
+
+    /// Returns the numbers of the blocks that have entries, in ascending order.
+    pub fn block_numbers(&self) -> Result<Vec<u8>, CDTextError> {
+        let mut blocks: Vec<u8> = self
+            .parse()?
+            .iter()
+            .map(|entry| entry.block_number)
+            .collect();
+
+        blocks.sort_unstable();
+        blocks.dedup();
+
+        Ok(blocks)
+    }
+
+    /// Returns all the entries of the type across all tracks, in the order of the data.
+    pub fn entries_of_type(
+        &self,
+        pack_type: CDTextPackType,
+    ) -> Result<Vec<CDTextEntry>, CDTextError> {
+        let mut entries = self.parse()?;
+        entries.retain(|entry| entry.entry_type == pack_type);
+
+        Ok(entries)
+    }
+
+    /// Internal method. Returns the first non-empty string of the type for the track.
+    fn text(&self, pack_type: CDTextPackType, track: u8) -> Option<String> {
+        let track_number = CDTextTrackNumber::from(track);
+
+        self.parse()
+            .ok()?
+            .into_iter()
+            .filter(|entry| entry.entry_type == pack_type && entry.track_number == track_number)
+            .find_map(|entry| match entry.data {
+                CDTextEntryDataType::String(text) if !text.is_empty() => Some(text),
+                _ => None,
+            })
+    }
+}
@@ -0,0 +1,136 @@
+use alloc::vec::Vec;
+use std::{io::Read, path::Path};
+
+use crate::CDText;
+
+impl<'data> CDText<'data> {
+    /// Reads CD-Text from the reader (a file, a pipe, a socket...) into the buffer,
+    /// and creates a parser over it, detecting the service header like [`CDText::new`].
+    ///
+    /// If the data starts with the service header, reading stops at the length declared by it,
+    /// so the reader doesn't have to end, and bytes past it (e.g. trailing zero byte appended
+    /// by cdrecord) are left unread. Otherwise everything up to the end is read.
+    pub fn from_reader(mut reader: impl Read, buffer: &'data mut Vec<u8>) -> std::io::Result<Self> {
+        buffer.clear();
+
+        // The service header is detected by the first pack following it.
+        (&mut reader).take(4 + 18).read_to_end(buffer)?;
+
+        if buffer.len() == 4 + 18 {
+            if Self::has_service_header(buffer, None) {
+                // Declared length covers two reserved bytes of the header too.
+                let declared = u16::from_be_bytes([buffer[0], buffer[1]]) as usize;
+                let rest = (declared + 2).saturating_sub(buffer.len());
+
+                reader.take(rest as u64).read_to_end(buffer)?;
+            } else {
+                reader.read_to_end(buffer)?;
+            }
+        }
+
+        Ok(Self::new(buffer))
+    }
+
+    /// Reads the file into the buffer and creates a parser over it,
+    /// detecting the service header like [`CDText::new`].
+    pub fn from_file(path: impl AsRef<Path>, buffer: &'data mut Vec<u8>) -> std::io::Result<Self> {
+        let mut file = std::fs::File::open(path)?;
+
+        buffer.clear();
+        file.read_to_end(buffer)?;
+
+        Ok(Self::new(buffer))
+    }
+}
+
+/// A CD-Text file mapped into memory, so it's parsed without reading it first.
+#[cfg(feature = "mmap")]
+pub struct MappedFile(memmap2::Mmap);
+
+#[cfg(feature = "mmap")]
+impl MappedFile {
+    /// Maps the file into memory.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it's mapped,
+    /// see [`memmap2::Mmap::map`].
+    pub unsafe fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+
+        // SAFETY: upheld by the caller.
+        unsafe { memmap2::Mmap::map(&file) }.map(Self)
+    }
+
+    /// Creates a parser over the file, detecting the service header like [`CDText::new`].
+    pub fn cdtext(&self) -> CDText<'_> {
+        CDText::new(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CDTextBuilder, CDTextEntryDataType, CDTextPackType};
+    use alloc::string::String;
+    use std::io::{Cursor, Seek};
+
+    /// Internal function. Returns the data of a disc with the service header.
+    fn data() -> Vec<u8> {
+        CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .with_header(true)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn stops_at_declared_length() {
+        let data = data();
+
+        // cdrecord appends a zero byte to `cdtext.dat`.
+        let mut file = data.clone();
+        file.push(0);
+        let mut reader = Cursor::new(file);
+
+        let mut buffer = Vec::new();
+        let cdtext = CDText::from_reader(&mut reader, &mut buffer).unwrap();
+        assert_eq!(
+            cdtext.parse().unwrap()[0].data,
+            CDTextEntryDataType::String(String::from("Album"))
+        );
+        assert_eq!(buffer, data);
+        assert_eq!(reader.stream_position().unwrap(), data.len() as u64);
+    }
+
+    #[test]
+    fn without_header() {
+        let data = data();
+        let packs = &data[4..];
+
+        let mut buffer = Vec::new();
+        let cdtext = CDText::from_reader(packs, &mut buffer).unwrap();
+        assert_eq!(cdtext.parse().unwrap().len(), 3);
+        assert_eq!(buffer, packs);
+
+        let mut buffer = Vec::new();
+        let cdtext = CDText::from_reader(&packs[..10], &mut buffer).unwrap();
+        assert!(cdtext.parse().is_err());
+        assert_eq!(buffer.len(), 10);
+    }
+
+    #[test]
+    fn from_file() {
+        let data = data();
+        let path = std::env::temp_dir().join(std::format!("cdtext-{}.dat", std::process::id()));
+        std::fs::write(&path, &data).unwrap();
+
+        let mut buffer = Vec::new();
+        let result = CDText::from_file(&path, &mut buffer).map(|cdtext| cdtext.parse());
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(result.unwrap().unwrap().len(), 3);
+        assert_eq!(buffer, data);
+    }
+}
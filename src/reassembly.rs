@@ -0,0 +1,68 @@
+/// Internal trait. A string stream of one pack type being reassembled, fed with the payloads
+/// of its packs by [`feed_strings`]. Implemented by [`CDText::parse`](crate::CDText::parse),
+/// which collects strings into a `Vec`, and [`CDText::parse_into`](crate::CDText::parse_into),
+/// which collects them into a `heapless::Vec`.
+pub(crate) trait StringStream {
+    type Error;
+
+    /// Bytes of the string collected so far.
+    fn buffer(&self) -> &[u8];
+
+    /// Appends the bytes of the pack to the string collected so far.
+    fn push(&mut self, pack_index: usize, bytes: &[u8]) -> Result<(), Self::Error>;
+
+    /// Emits the string collected so far, terminated at `end` in the payload of the pack,
+    /// and moves on to the string of the next track.
+    fn emit(&mut self, pack_index: usize, end: usize) -> Result<(), Self::Error>;
+}
+
+/// Internal function. Splits the payload of the pack into the strings of the stream.
+///
+/// Each string is terminated by a NUL character, and the next string belongs to the next track.
+/// In double-byte packs all the characters take two bytes. The first `prefix_length` bytes
+/// of each string are binary, e.g. the genre code, and are taken as is, even if zero.
+pub(crate) fn feed_strings<S: StringStream>(
+    stream: &mut S,
+    pack_index: usize,
+    payload: &[u8],
+    width: usize,
+    prefix_length: usize,
+) -> Result<(), S::Error> {
+    let mut start = 0;
+
+    while start < payload.len() {
+        let prefix = prefix_length
+            .saturating_sub(stream.buffer().len())
+            .div_ceil(width)
+            * width;
+        let text_start = (start + prefix).min(payload.len());
+
+        // Characters up to the terminator are copied at once.
+        let end = payload[text_start..]
+            .chunks_exact(width)
+            .position(|character| character.iter().all(|&x| x == 0))
+            .map_or(payload.len(), |position| text_start + position * width);
+
+        if end > start {
+            stream.push(pack_index, &payload[start..end])?;
+        }
+
+        if end == payload.len() {
+            break;
+        }
+
+        stream.emit(pack_index, end)?;
+        start = end + width;
+    }
+
+    Ok(())
+}
+
+/// Internal function. Returns the character position a pack continuing the string
+/// collected so far is expected to have.
+///
+/// Character position saturates at 15, meaning "15 or more".
+/// Double-byte characters are counted as one.
+pub(crate) fn expected_position(buffer: &[u8], width: usize) -> u8 {
+    (buffer.len() / width).min(15) as u8
+}
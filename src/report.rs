@@ -0,0 +1,262 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+
+use crate::{CDText, CDTextEntry, CDTextEntryDataType, CDTextError, Disc};
+
+/// Layout of a report rendered by [`render_report`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReportFormat {
+    /// Album fields and a table of the tracks aligned with spaces, for terminals and rip logs.
+    #[default]
+    PlainText,
+    /// A heading for each block, a list of the album fields and a table of the tracks.
+    Markdown,
+}
+
+/// Headers of the columns of the track table.
+const HEADERS: [&str; 8] = [
+    "#",
+    "Title",
+    "Performer",
+    "Songwriter",
+    "Composer",
+    "Arranger",
+    "Message",
+    "ISRC",
+];
+
+/// Renders the entries as a track listing: for each block, its language, the album fields
+/// and a table of the tracks. Fields and columns without any values are left out.
+pub fn render_report(entries: &[CDTextEntry], format: ReportFormat) -> String {
+    let mut output = String::new();
+
+    let mut block_numbers: Vec<u8> = entries.iter().map(|entry| entry.block_number).collect();
+    block_numbers.sort_unstable();
+    block_numbers.dedup();
+
+    for block_number in block_numbers {
+        let disc = Disc::from_block(entries, block_number);
+
+        let language = entries.iter().find_map(|entry| match &entry.data {
+            CDTextEntryDataType::BlockSizeInfo(size_info) if entry.block_number == block_number => {
+                size_info.language(block_number)
+            }
+            _ => None,
+        });
+
+        if !output.is_empty() {
+            output.push('\n');
+        }
+
+        let heading = match language {
+            Some(language) => format!("Block {block_number} ({})", language.name()),
+            None => format!("Block {block_number}"),
+        };
+
+        match format {
+            ReportFormat::PlainText => {
+                let _ = writeln!(output, "{heading}");
+            }
+            ReportFormat::Markdown => {
+                let _ = writeln!(output, "## {heading}\n");
+            }
+        }
+
+        write_album(&mut output, &disc, format);
+        write_tracks(&mut output, &disc, format);
+    }
+
+    output
+}
+
+/// Internal function. Writes the album fields that are set.
+fn write_album(output: &mut String, disc: &Disc, format: ReportFormat) {
+    let upc = disc.upc.map(|upc| upc.to_string());
+    let genre = disc.genre.as_ref().and_then(|genre| genre.tag_name());
+
+    let album = [
+        ("Title", disc.title.as_deref()),
+        ("Performer", disc.performer.as_deref()),
+        ("Songwriter", disc.songwriter.as_deref()),
+        ("Composer", disc.composer.as_deref()),
+        ("Arranger", disc.arranger.as_deref()),
+        ("Message", disc.message.as_deref()),
+        ("Disc ID", disc.disc_id.as_deref()),
+        ("UPC/EAN", upc.as_deref()),
+        ("Genre", genre),
+    ];
+
+    for (name, value) in album {
+        let Some(value) = value else {
+            continue;
+        };
+
+        let _ = match format {
+            ReportFormat::PlainText => writeln!(output, "  {:<12}{value}", format!("{name}:")),
+            ReportFormat::Markdown => writeln!(output, "- **{name}:** {}", escape(value)),
+        };
+    }
+}
+
+/// Internal function. Writes the table of the tracks, with only the columns that have any values.
+fn write_tracks(output: &mut String, disc: &Disc, format: ReportFormat) {
+    let rows: Vec<[String; 8]> = disc
+        .tracks
+        .iter()
+        .map(|track| {
+            let text = |value: &Option<String>| match format {
+                ReportFormat::PlainText => value.clone().unwrap_or_default(),
+                ReportFormat::Markdown => value.as_deref().map(escape).unwrap_or_default(),
+            };
+
+            [
+                track.number.to_string(),
+                text(&track.title),
+                text(&track.performer),
+                text(&track.songwriter),
+                text(&track.composer),
+                text(&track.arranger),
+                text(&track.message),
+                track.isrc.map(|isrc| isrc.to_string()).unwrap_or_default(),
+            ]
+        })
+        .collect();
+
+    if rows.is_empty() {
+        return;
+    }
+
+    let columns: Vec<usize> = (0..HEADERS.len())
+        .filter(|&column| column == 0 || rows.iter().any(|row| !row[column].is_empty()))
+        .collect();
+
+    let width = |column: usize| {
+        rows.iter()
+            .map(|row| row[column].chars().count())
+            .chain([HEADERS[column].len()])
+            .max()
+            .unwrap_or(0)
+    };
+    let widths: Vec<usize> = columns.iter().map(|&column| width(column)).collect();
+
+    let line = |output: &mut String, cells: Vec<&str>| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:<width$}"))
+            .collect();
+
+        let _ = match format {
+            ReportFormat::PlainText => writeln!(output, "  {}", cells.join("  ").trim_end()),
+            ReportFormat::Markdown => writeln!(output, "| {} |", cells.join(" | ")),
+        };
+    };
+
+    output.push('\n');
+    line(
+        output,
+        columns.iter().map(|&column| HEADERS[column]).collect(),
+    );
+
+    if format == ReportFormat::Markdown {
+        let separators: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+        line(output, separators.iter().map(String::as_str).collect());
+    }
+
+    for row in &rows {
+        line(
+            output,
+            columns.iter().map(|&column| row[column].as_str()).collect(),
+        );
+    }
+}
+
+/// Internal function. Escapes characters that would break Markdown lists and tables.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '|' | '*' | '_' | '`' | '\\' | '[' | ']' | '<' | '>' => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            '\r' | '\n' => escaped.push(' '),
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+impl CDText<'_> {
+    /// Parses the data and renders it as a track listing, see [`render_report`].
+    pub fn report(&self, format: ReportFormat) -> Result<String, CDTextError> {
+        Ok(render_report(&self.parse()?, format))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockBuilder, CDTextBuilder, CDTextPackType, Language};
+
+    /// Internal function. Returns the data of a disc with two tracks and two blocks.
+    fn data() -> Vec<u8> {
+        CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Performers, 0, "Band")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .with_text(CDTextPackType::Title, 2, "Second | Live")
+            .with_text(CDTextPackType::Performers, 2, "Guest")
+            .with_block(BlockBuilder::new(Language::German).with_text(
+                CDTextPackType::Title,
+                0,
+                "Titel",
+            ))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn plain_text() {
+        let data = data();
+
+        assert_eq!(
+            CDText::from_data(&data).report(ReportFormat::PlainText),
+            Ok(String::from(
+                "Block 0 (English)\n  Title:      Album\n  Performer:  Band\n\n\
+                 \x20 #  Title          Performer\n  1  First\n  2  Second | Live  Guest\n\n\
+                 Block 1 (German)\n  Title:      Titel\n\n  #\n  1\n  2\n"
+            ))
+        );
+    }
+
+    #[test]
+    fn markdown() {
+        let data = data();
+
+        assert_eq!(
+            CDText::from_data(&data).report(ReportFormat::Markdown),
+            Ok(String::from(
+                "## Block 0 (English)\n\n- **Title:** Album\n- **Performer:** Band\n\n\
+                 | # | Title          | Performer |\n\
+                 | - | -------------- | --------- |\n\
+                 | 1 | First          |           |\n\
+                 | 2 | Second \\| Live | Guest     |\n\n\
+                 ## Block 1 (German)\n\n- **Title:** Titel\n\n| # |\n| - |\n| 1 |\n| 2 |\n"
+            ))
+        );
+    }
+
+    #[test]
+    fn markdown_escapes() {
+        assert_eq!(escape("a|b *c* [d]\ne"), "a\\|b \\*c\\* \\[d\\] e");
+        assert_eq!(render_report(&[], ReportFormat::Markdown), "");
+    }
+}
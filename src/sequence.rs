@@ -0,0 +1,99 @@
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+use crate::CDTextPack;
+
+/// A discontinuity in pack sequence numbers.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SequenceGap {
+    /// Index of the first pack after the discontinuity.
+    pub pack_index: usize,
+    /// Sequence number the pack was expected to have.
+    pub expected: u8,
+    /// Sequence number the pack actually has.
+    pub found: u8,
+}
+
+impl SequenceGap {
+    /// Whether the counter went backwards (restarted or packs are out of order)
+    /// instead of skipping some numbers.
+    ///
+    /// The counter wraps around after 255, so it's told by the distance from the expected number
+    /// to the found one, counted forward: a gap of half the counter range or more is a reset.
+    pub fn is_reset(&self) -> bool {
+        self.distance() >= 128
+    }
+
+    /// Sequence numbers of the packs that are missing, in order. Empty for resets.
+    pub fn missing(&self) -> impl Iterator<Item = u8> + use<> {
+        let expected = self.expected;
+        let count = if self.is_reset() { 0 } else { self.distance() };
+
+        (0..count).map(move |offset| expected.wrapping_add(offset))
+    }
+
+    /// Internal method. Returns the distance from the expected sequence number to the found one.
+    fn distance(&self) -> u8 {
+        self.found.wrapping_sub(self.expected)
+    }
+}
+
+/// Internal structure. Tracks sequence numbers of consecutive packs.
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+#[derive(Default)]
+pub(crate) struct SequenceChecker {
+    /// Expected sequence number and block number of the next pack.
+    next: Option<(u8, u8)>,
+}
+
+#[cfg(any(feature = "alloc", feature = "heapless"))]
+impl SequenceChecker {
+    /// Checks the pack against the previous one. Counter may start over from zero in a new block.
+    pub(crate) fn check(&mut self, pack_index: usize, pack: &CDTextPack) -> Option<SequenceGap> {
+        let gap = match self.next {
+            Some((expected, block_number)) => {
+                let restarted = pack.block_number != block_number && pack.seq_counter == 0;
+
+                (pack.seq_counter != expected && !restarted).then_some(SequenceGap {
+                    pack_index,
+                    expected,
+                    found: pack.seq_counter,
+                })
+            }
+            None => None,
+        };
+
+        // The counter wraps around after 255.
+        self.next = Some((pack.seq_counter.wrapping_add(1), pack.block_number));
+
+        gap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gap_across_wraparound() {
+        let gap = SequenceGap {
+            pack_index: 10,
+            expected: 254,
+            found: 2,
+        };
+
+        assert!(!gap.is_reset());
+        assert!(gap.missing().eq([254, 255, 0, 1]));
+    }
+
+    #[test]
+    fn reset() {
+        let gap = SequenceGap {
+            pack_index: 10,
+            expected: 40,
+            found: 0,
+        };
+
+        assert!(gap.is_reset());
+        assert_eq!(gap.missing().count(), 0);
+    }
+}
@@ -0,0 +1,342 @@
+use alloc::{collections::VecDeque, vec::Vec};
+
+use crate::{
+    BlockSizeInfo, CDText, CDTextError, CDTextPack, CDTextPackType, Diagnostic, ParserOptions,
+    parser::ParseState, visitor::CDTextVisitor,
+};
+
+/// Stands for the service header when the data starts with it.
+/// Only its length is used, to report offsets of packs.
+const SERVICE_HEADER: &[u8] = &[0; 4];
+
+/// Parser for data that arrives in parts, e.g. from a drive or a socket.
+///
+/// Data is given to [`StreamingParser::push`] in chunks of any size, and entries are passed
+/// to the visitor as soon as they are complete. Parsing is the same as by [`CDText::parse_with`],
+/// including detection of the service header and [`ParserOptions`]. The only difference is that
+/// data shorter than the length declared by the header is reported as truncated.
+///
+/// Block Size Info, which declares the charset of a block, follows text of the block.
+/// Unless the charset is forced by the options, packs of a block are held until the charset
+/// is known, i.e. until all the Size Info packs of the block are received. Packs following
+/// the held ones are held too, so entries come in the order of the packs. If Size Info
+/// of the block is incomplete, the packs are held until [`StreamingParser::finish`].
+pub struct StreamingParser {
+    /// Parser without data, used for the options and offsets of packs.
+    cdtext: CDText<'static>,
+    state: ParseState,
+    /// Whether the first bytes have been checked for the service header.
+    started: bool,
+    /// Length declared by the service header, including the two reserved bytes of the header.
+    declared: usize,
+    /// Bytes left until the end of the length declared by the service header.
+    remaining: Option<usize>,
+    /// Received bytes that don't form a complete pack yet.
+    buffer: Vec<u8>,
+    /// Index of the next complete pack.
+    pack_index: usize,
+    /// Packs starting with the first one of a block whose charset isn't known yet.
+    held: VecDeque<(usize, CDTextPack)>,
+    /// Whether charsets of the blocks are known.
+    resolved: [bool; 8],
+}
+
+impl Default for StreamingParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamingParser {
+    /// Creates a parser with the default options.
+    pub fn new() -> Self {
+        Self::with_options(ParserOptions::default())
+    }
+
+    /// Creates a parser with the options.
+    pub fn with_options(options: ParserOptions) -> Self {
+        let cdtext = CDText::from_data(&[]).with_options(options);
+        let state = ParseState::new(&cdtext, cdtext.options.mode());
+        let is_forced = cdtext.options.charset().is_some();
+
+        Self {
+            cdtext,
+            state,
+            started: false,
+            declared: 0,
+            remaining: None,
+            buffer: Vec::new(),
+            pack_index: 0,
+            held: VecDeque::new(),
+            resolved: [is_forced; 8],
+        }
+    }
+
+    /// Parses the next chunk of data, passing completed entries to the visitor.
+    ///
+    /// After an error the rest of the data cannot be parsed.
+    pub fn push(
+        &mut self,
+        data: &[u8],
+        visitor: &mut impl CDTextVisitor,
+    ) -> Result<(), CDTextError> {
+        if self.started {
+            return self.accept(data, visitor);
+        }
+
+        self.buffer.extend_from_slice(data);
+
+        // The service header is detected by the first pack following it.
+        if self.buffer.len() < 4 + 18 {
+            return Ok(());
+        }
+
+        self.start(None, visitor)
+    }
+
+    /// Parses what's left once all the data is received, passing the remaining entries,
+    /// including the ones decoded from binary packs, to the visitor.
+    ///
+    /// Returns all the problems the parser has recovered from, in lenient mode,
+    /// including the ones only found at the end of the data, e.g. truncation.
+    pub fn finish(
+        mut self,
+        visitor: &mut impl CDTextVisitor,
+    ) -> Result<Vec<Diagnostic>, CDTextError> {
+        if !self.started {
+            self.start(Some(self.buffer.len()), visitor)?;
+        }
+
+        // Blocks without complete Size Info are parsed as if it's missing.
+        self.resolved = [true; 8];
+        self.release(visitor)?;
+        self.cdtext.finish_parsing(&mut self.state, visitor)?;
+
+        if !self.buffer.is_empty() {
+            let length = self.buffer.len();
+            self.cdtext
+                .check_trailing(&mut self.state, self.pack_index, length)?;
+        }
+
+        if let Some(remaining) = self.remaining
+            && remaining > 0
+        {
+            let actual = self.declared - remaining;
+            self.cdtext
+                .check_length(&mut self.state, self.declared, actual)?;
+        }
+
+        Ok(self.state.into_diagnostics())
+    }
+
+    /// Returns all the problems the parser has recovered from so far, in lenient mode.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        self.state.diagnostics()
+    }
+
+    /// Internal method. Strips the service header, if the data starts with it,
+    /// and parses the bytes received so far. `total_length` is the length of the data,
+    /// if all of it is received.
+    fn start(
+        &mut self,
+        total_length: Option<usize>,
+        visitor: &mut impl CDTextVisitor,
+    ) -> Result<(), CDTextError> {
+        self.started = true;
+
+        let mut received = core::mem::take(&mut self.buffer);
+
+        if CDText::has_service_header(&received, total_length) {
+            // Declared length covers two reserved bytes of the header too.
+            let declared = u16::from_be_bytes([received[0], received[1]]) as usize;
+
+            self.cdtext.header = SERVICE_HEADER;
+            self.declared = declared;
+            self.remaining = Some(declared.saturating_sub(2));
+            received.drain(..4);
+        }
+
+        self.accept(&received, visitor)
+    }
+
+    /// Internal method. Parses all the complete packs received so far.
+    fn accept(&mut self, data: &[u8], visitor: &mut impl CDTextVisitor) -> Result<(), CDTextError> {
+        // Bytes past the declared length are ignored.
+        let data = match &mut self.remaining {
+            Some(remaining) => {
+                let length = data.len().min(*remaining);
+                *remaining -= length;
+                &data[..length]
+            }
+            None => data,
+        };
+
+        self.buffer.extend_from_slice(data);
+
+        let (packs, _) = self.buffer.as_chunks::<18>();
+        let packs: Vec<CDTextPack> = packs.iter().map(CDText::parse_pack).collect();
+        self.buffer.drain(..packs.len() * 18);
+
+        for pack in packs {
+            let pack_index = self.pack_index;
+            self.pack_index += 1;
+
+            self.feed(pack_index, pack, visitor)?;
+        }
+
+        Ok(())
+    }
+
+    /// Internal method. Parses the pack, or holds it until the charsets of its block
+    /// and of the blocks of the held packs are known.
+    fn feed(
+        &mut self,
+        pack_index: usize,
+        pack: CDTextPack,
+        visitor: &mut impl CDTextVisitor,
+    ) -> Result<(), CDTextError> {
+        let block_number = pack.block_number;
+
+        if self.held.is_empty() && self.resolved[block_number as usize] {
+            return self
+                .cdtext
+                .feed_pack(&mut self.state, pack_index, pack, visitor);
+        }
+
+        let is_size_info = pack.pack_type == CDTextPackType::BlockSizeInfo;
+        self.held.push_back((pack_index, pack));
+
+        if is_size_info && !self.resolved[block_number as usize] {
+            let size_info =
+                BlockSizeInfo::from_packs(block_number, self.held.iter().map(|(_, pack)| pack));
+
+            if let Some(size_info) = size_info {
+                self.state
+                    .set_charset(block_number, Some(size_info.charset()));
+                self.resolved[block_number as usize] = true;
+            }
+        }
+
+        self.release(visitor)
+    }
+
+    /// Internal method. Parses the held packs up to the first one of a block
+    /// whose charset isn't known yet.
+    fn release(&mut self, visitor: &mut impl CDTextVisitor) -> Result<(), CDTextError> {
+        while let Some((_, pack)) = self.held.front()
+            && self.resolved[pack.block_number as usize]
+            && let Some((pack_index, pack)) = self.held.pop_front()
+        {
+            self.cdtext
+                .feed_pack(&mut self.state, pack_index, pack, visitor)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CDTextBuilder, CDTextEntry, CDTextPackType, Recovery};
+
+    /// Internal function. Parses the data in chunks of 7 bytes.
+    fn parse_chunked(
+        data: &[u8],
+        options: ParserOptions,
+    ) -> (Result<Vec<CDTextEntry>, CDTextError>, Vec<Diagnostic>) {
+        let mut parser = StreamingParser::with_options(options);
+        let mut entries = Vec::new();
+
+        for chunk in data.chunks(7) {
+            if let Err(error) = parser.push(chunk, &mut entries) {
+                return (Err(error), parser.diagnostics().to_vec());
+            }
+        }
+
+        match parser.finish(&mut entries) {
+            Ok(diagnostics) => (Ok(entries), diagnostics),
+            Err(error) => (Err(error), Vec::new()),
+        }
+    }
+
+    /// Internal function. Builds a dump with the service header.
+    fn dump() -> Vec<u8> {
+        CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "Track")
+            .with_header(true)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn split_pack() {
+        let data = dump();
+        let truncated = &data[..data.len() - 5];
+        let pack_index = (data.len() - 4) / 18 - 1;
+
+        let short_pack = CDTextError::ShortPack {
+            pack_index,
+            offset: 4 + pack_index * 18,
+            length: 13,
+        };
+        let mismatch = CDTextError::LengthMismatch {
+            declared: data.len() - 2,
+            actual: truncated.len() - 2,
+        };
+
+        let (result, _) = parse_chunked(truncated, ParserOptions::strict());
+        assert_eq!(result, Err(short_pack.clone()));
+
+        let (result, diagnostics) = parse_chunked(truncated, ParserOptions::lenient());
+        assert_eq!(result.unwrap(), CDText::new(&data).parse().unwrap()[..2]);
+        assert_eq!(
+            diagnostics,
+            [
+                Diagnostic {
+                    problem: short_pack,
+                    recovery: Recovery::PackSkipped,
+                },
+                Diagnostic {
+                    problem: mismatch,
+                    recovery: Recovery::DataUsed,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn cut_at_pack_boundary() {
+        let data = dump();
+        let truncated = &data[..data.len() - 18];
+        let mismatch = CDTextError::LengthMismatch {
+            declared: data.len() - 2,
+            actual: truncated.len() - 2,
+        };
+
+        let (result, _) = parse_chunked(truncated, ParserOptions::strict());
+        assert_eq!(result, Err(mismatch.clone()));
+
+        let (result, diagnostics) = parse_chunked(truncated, ParserOptions::lenient());
+        assert_eq!(result.unwrap(), CDText::new(&data).parse().unwrap()[..2]);
+        assert_eq!(
+            diagnostics,
+            [Diagnostic {
+                problem: mismatch,
+                recovery: Recovery::DataUsed,
+            }]
+        );
+    }
+
+    #[test]
+    fn bytes_past_declared_length() {
+        let data = dump();
+        let mut padded = data.clone();
+        padded.extend_from_slice(&[0, 0x80, 0xff, 0x01]);
+
+        let (result, diagnostics) = parse_chunked(&padded, ParserOptions::strict());
+        assert_eq!(result.unwrap(), CDText::new(&data).parse().unwrap());
+        assert!(diagnostics.is_empty());
+    }
+}
@@ -0,0 +1,315 @@
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use crate::pack_crc;
+
+/// Size of a raw subchannel frame: one byte for each of 96 symbols of the P-W subchannels.
+pub const SUBCHANNEL_FRAME_SIZE: usize = 96;
+
+/// Number of 6-bit R-W symbols a pack is spread over.
+const SYMBOLS_PER_PACK: usize = 24;
+
+/// Longest delay, in packs, of a symbol of a pack in raw subchannel data.
+const MAX_DELAY: usize = 7;
+
+/// Layout of the R-W symbols in subchannel data.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SubchannelFormat {
+    /// As recorded on the disc (IEC 60908), e.g. raw P-W data of READ CD (sub-channel selection
+    /// 001b) or a dumping tool. Symbols of a pack are interleaved over the following 7 packs,
+    /// and symbols 1 and 18, 2 and 5, 3 and 23 are swapped.
+    #[default]
+    Raw,
+    /// Deinterleaved by the drive, i.e. packed R-W data of READ CD (sub-channel selection 100b).
+    /// Each pack takes 24 consecutive symbols.
+    Deinterleaved,
+}
+
+/// Internal function. Returns where the symbol of a pack is stored, counting from the start
+/// of the pack in the data.
+fn symbol_offset(format: SubchannelFormat, symbol: usize) -> usize {
+    match format {
+        SubchannelFormat::Raw => {
+            let position = match symbol {
+                1 => 18,
+                18 => 1,
+                2 => 5,
+                5 => 2,
+                3 => 23,
+                23 => 3,
+                n => n,
+            };
+
+            // Each symbol is delayed by its position modulo 8 packs.
+            position % 8 * SYMBOLS_PER_PACK + position
+        }
+        SubchannelFormat::Deinterleaved => symbol,
+    }
+}
+
+/// Internal function. Reassembles a pack from 24 symbols, taking R-W bits from the low
+/// 6 bits of each of them.
+fn pack_from_symbols(symbols: &[u8]) -> [u8; 18] {
+    let mut pack = [0u8; 18];
+
+    for (bytes, symbols) in pack.chunks_exact_mut(3).zip(symbols.chunks_exact(4)) {
+        let bits = symbols
+            .iter()
+            .fold(0u32, |bits, &symbol| bits << 6 | u32::from(symbol & 0x3f));
+
+        bytes.copy_from_slice(&bits.to_be_bytes()[1..]);
+    }
+
+    pack
+}
+
+/// Internal function. Spreads a pack over 24 symbols, storing 6 bits in the R-W bits
+/// of each of them. P and Q bits are left clear.
+fn symbols_from_pack(pack: &[u8], symbols: &mut [u8]) {
+    for (bytes, symbols) in pack.chunks_exact(3).zip(symbols.chunks_exact_mut(4)) {
+        let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        for (index, symbol) in symbols.iter_mut().enumerate() {
+            *symbol = (bits >> (18 - index * 6)) as u8 & 0x3f;
+        }
+    }
+}
+
+/// Internal function. Splits the subchannel data into packs, in the order of the data.
+/// Raw packs whose symbols run past the end of the data are left out.
+fn subchannel_packs(data: &[u8], format: SubchannelFormat) -> impl Iterator<Item = [u8; 18]> + '_ {
+    let symbols = &data[..data.len() / SUBCHANNEL_FRAME_SIZE * SUBCHANNEL_FRAME_SIZE];
+    let count = symbols.len() / SYMBOLS_PER_PACK;
+
+    let complete = match format {
+        SubchannelFormat::Raw => count.saturating_sub(MAX_DELAY),
+        SubchannelFormat::Deinterleaved => count,
+    };
+
+    (0..complete).map(move |index| {
+        let start = index * SYMBOLS_PER_PACK;
+        let mut pack_symbols = [0u8; SYMBOLS_PER_PACK];
+
+        for (symbol, value) in pack_symbols.iter_mut().enumerate() {
+            *value = symbols[start + symbol_offset(format, symbol)];
+        }
+
+        pack_from_symbols(&pack_symbols)
+    })
+}
+
+/// Internal function. Whether the pack is a CD-Text pack with a valid CRC.
+fn is_valid(pack: &[u8; 18]) -> bool {
+    matches!(pack[0], 0x80..=0x8f) && pack_crc(pack) == u16::from_be_bytes([pack[16], pack[17]])
+}
+
+/// Internal function. Returns the block and sequence numbers identifying the pack.
+fn pack_key(pack: &[u8; 18]) -> (u8, u8) {
+    ((pack[3] >> 4) & 0b111, pack[2])
+}
+
+/// Internal function. Builds a pack out of damaged copies, taking the most common value
+/// of each byte. Ties go to the value seen first.
+fn majority_vote(copies: &[[u8; 18]]) -> [u8; 18] {
+    let mut pack = [0u8; 18];
+
+    for (index, byte) in pack.iter_mut().enumerate() {
+        let mut counts = [0usize; 256];
+
+        for copy in copies {
+            counts[usize::from(copy[index])] += 1;
+        }
+
+        // `max_by_key` returns the last maximum, so the copies are looked through in reverse.
+        *byte = copies
+            .iter()
+            .rev()
+            .map(|copy| copy[index])
+            .max_by_key(|&value| counts[usize::from(value)])
+            .unwrap_or(0);
+    }
+
+    pack
+}
+
+/// Reconstructs packs from R-W subchannel data of the lead-in, e.g. read by a drive
+/// along with the lead-in sectors or saved by a dumping tool.
+///
+/// The data is a sequence of 96-byte frames, each carrying 4 packs. Every byte holds
+/// one symbol in its R-W bits (the low 6 bits), laid out as given by `format`.
+/// P and Q bits are ignored, as is an incomplete frame at the end. In raw data, the last
+/// 7 packs are incomplete, as the rest of their symbols follow in the next frames.
+///
+/// The lead-in repeats the text over and over, so only one copy of each pack is kept:
+/// the first one with a valid CRC. If every copy of a pack is damaged, e.g. by a burst error
+/// or a noisy read, the pack is voted byte by byte across all the copies with its block and
+/// sequence numbers and kept only if the result passes its CRC. Packs are ordered by block
+/// and sequence number. The result can be parsed with [`crate::CDText::from_data`].
+pub fn packs_from_subchannel(data: &[u8], format: SubchannelFormat) -> Vec<u8> {
+    let mut packs = BTreeMap::new();
+    let mut damaged = BTreeMap::<(u8, u8), Vec<[u8; 18]>>::new();
+
+    for pack in subchannel_packs(data, format) {
+        if is_valid(&pack) {
+            packs.entry(pack_key(&pack)).or_insert(pack);
+        } else {
+            damaged.entry(pack_key(&pack)).or_default().push(pack);
+        }
+    }
+
+    for (key, copies) in damaged {
+        if packs.contains_key(&key) {
+            continue;
+        }
+
+        let pack = majority_vote(&copies);
+
+        if is_valid(&pack) {
+            packs.entry(pack_key(&pack)).or_insert(pack);
+        }
+    }
+
+    packs.into_values().flatten().collect()
+}
+
+/// Spreads packs over R-W subchannel frames of the lead-in, laid out as given by `format`,
+/// to be handed to a burning backend that writes subchannel data. This is the reverse
+/// of [`packs_from_subchannel`].
+///
+/// The lead-in carries the text over its whole length, so the packs are repeated as many times
+/// as needed to fill `frame_count` frames, typically the length of the lead-in in sectors.
+/// Each frame carries 4 packs; a repetition may start in the middle of a frame. In raw frames,
+/// the first ones also carry the interleaved symbols of the packs preceding the first pack.
+/// Returns no frames if there are no complete packs.
+pub fn subchannel_frames(packs: &[u8], frame_count: usize, format: SubchannelFormat) -> Vec<u8> {
+    let packs: Vec<&[u8]> = packs.chunks_exact(18).collect();
+
+    if packs.is_empty() {
+        return Vec::new();
+    }
+
+    let mut frames = vec![0u8; frame_count * SUBCHANNEL_FRAME_SIZE];
+    let count = frames.len() / SYMBOLS_PER_PACK;
+
+    // Symbols of the packs before the first one are delayed into the first frames.
+    let lead = match format {
+        SubchannelFormat::Raw => MAX_DELAY,
+        SubchannelFormat::Deinterleaved => 0,
+    };
+
+    for index in 0..count + lead {
+        let pack = packs[(index + packs.len() * lead - lead) % packs.len()];
+        let mut symbols = [0u8; SYMBOLS_PER_PACK];
+        symbols_from_pack(pack, &mut symbols);
+
+        for (symbol, &value) in symbols.iter().enumerate() {
+            let position = (index * SYMBOLS_PER_PACK + symbol_offset(format, symbol))
+                .checked_sub(lead * SYMBOLS_PER_PACK);
+
+            if let Some(byte) = position.and_then(|position| frames.get_mut(position)) {
+                *byte = value;
+            }
+        }
+    }
+
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CDTextBuilder, CDTextPackType};
+
+    /// Offsets of the symbols of a pack in raw subchannel data, as tabulated by cdrecord.
+    const RAW_OFFSETS: [usize; SYMBOLS_PER_PACK] = [
+        0, 66, 125, 191, 100, 50, 150, 175, 8, 33, 58, 83, 108, 133, 158, 183, 16, 41, 25, 91, 116,
+        141, 166, 75,
+    ];
+
+    #[test]
+    fn raw_subchannel() {
+        let packs = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "Track")
+            .build()
+            .unwrap();
+        let count = packs.len() / 18;
+
+        let mut data = vec![0u8; 12 * SUBCHANNEL_FRAME_SIZE];
+
+        for index in 0..data.len() / SYMBOLS_PER_PACK {
+            let mut symbols = [0u8; SYMBOLS_PER_PACK];
+            let pack = &packs[index % count * 18..][..18];
+            symbols_from_pack(pack, &mut symbols);
+
+            for (symbol, offset) in symbols.iter().zip(RAW_OFFSETS) {
+                if let Some(byte) = data.get_mut(index * SYMBOLS_PER_PACK + offset) {
+                    // P and Q bits are set to make sure they are ignored.
+                    *byte = symbol | 0xc0;
+                }
+            }
+        }
+
+        assert_eq!(packs_from_subchannel(&data, SubchannelFormat::Raw), packs);
+        assert!(packs_from_subchannel(&data, SubchannelFormat::Deinterleaved).is_empty());
+    }
+
+    #[test]
+    fn damaged_copies() {
+        let packs = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "Track")
+            .build()
+            .unwrap();
+        let mut data = subchannel_frames(&packs, 20, SubchannelFormat::Deinterleaved);
+
+        // Every copy of the first pack is damaged, each in a different symbol of the payload.
+        let count = packs.len() / 18;
+        let copies = (0..data.len() / SYMBOLS_PER_PACK).filter(|index| index % count == 0);
+
+        for (copy, index) in copies.enumerate() {
+            data[index * SYMBOLS_PER_PACK + 6 + copy % 14] ^= 0x3f;
+        }
+
+        // A pack is lost halfway, so the copies after it are shifted.
+        let lost = (count * 10 + 1) * SYMBOLS_PER_PACK;
+        data.drain(lost..lost + SYMBOLS_PER_PACK);
+
+        assert_eq!(
+            packs_from_subchannel(&data, SubchannelFormat::Deinterleaved),
+            packs
+        );
+    }
+
+    #[test]
+    fn frames_round_trip() {
+        let packs = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "Track")
+            .build()
+            .unwrap();
+
+        for format in [SubchannelFormat::Raw, SubchannelFormat::Deinterleaved] {
+            let frames = subchannel_frames(&packs, 10, format);
+
+            assert_eq!(frames.len(), 10 * SUBCHANNEL_FRAME_SIZE);
+            assert_eq!(packs_from_subchannel(&frames, format), packs);
+        }
+
+        assert_ne!(
+            subchannel_frames(&packs, 10, SubchannelFormat::Raw),
+            subchannel_frames(&packs, 10, SubchannelFormat::Deinterleaved)
+        );
+    }
+
+    #[test]
+    fn tie_goes_to_first_copy() {
+        let first = [1u8; 18];
+        let mut second = first;
+        second[4] = 2;
+
+        assert_eq!(majority_vote(&[first, second]), first);
+        assert_eq!(majority_vote(&[second, first]), second);
+        assert_eq!(majority_vote(&[first, second, second]), second);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{BlockSizeInfo, CDText, CDTextPackType, CDTextTrackNumber, Language};
+
+/// Quick statistics about the packs of a dump, see [`CDText::summary`].
+///
+/// Collected in one pass over the packs, without assembling text,
+/// so it's cheap enough for triage and indexing of many dumps.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+    /// Number of complete packs, including ones with mismatching CRC.
+    pub packs: usize,
+    /// Number of packs with mismatching CRC.
+    pub damaged_packs: usize,
+    /// Number of packs of each type 0x80-0x8F, like [`BlockSizeInfo::pack_counts`].
+    pub pack_counts: [usize; 16],
+    /// Number of packs of types not defined by the specification.
+    pub unknown_packs: usize,
+    /// Number of packs in each block.
+    pub block_packs: [usize; 8],
+    /// Number of tracks, as declared by Block Size Info. Without it, the highest
+    /// track number found in the headers of the packs.
+    pub tracks: u8,
+    /// Language of each block that has packs, as declared by Block Size Info.
+    pub languages: [Option<Language>; 8],
+    /// Bytes of text in the text packs, excluding terminators and padding.
+    /// Genre is not counted, as its packs start with the binary genre code.
+    pub text_bytes: usize,
+}
+
+impl Summary {
+    /// Returns the number of packs of the type.
+    pub fn pack_count(&self, pack_type: CDTextPackType) -> usize {
+        match u8::from(pack_type) {
+            n @ 0x80..=0x8f => self.pack_counts[(n - 0x80) as usize],
+            _ => self.unknown_packs,
+        }
+    }
+
+    /// Returns the number of blocks that have packs.
+    pub fn blocks(&self) -> usize {
+        self.block_packs.iter().filter(|&&count| count > 0).count()
+    }
+
+    /// Iterates over the languages of the blocks, skipping blocks without packs or Block Size Info.
+    pub fn iter_languages(&self) -> impl Iterator<Item = Language> + '_ {
+        self.languages.iter().flatten().copied()
+    }
+}
+
+impl CDText<'_> {
+    /// Collects statistics about the packs, without parsing the entries.
+    pub fn summary(&self) -> Summary {
+        let mut summary = Summary::default();
+        let mut highest_track = 0;
+
+        for pack in self.iter_pack_chunks() {
+            summary.packs += 1;
+
+            if !pack.crc_valid {
+                summary.damaged_packs += 1;
+            }
+
+            match u8::from(pack.pack_type) {
+                n @ 0x80..=0x8f => summary.pack_counts[(n - 0x80) as usize] += 1,
+                _ => summary.unknown_packs += 1,
+            }
+
+            summary.block_packs[pack.block_number as usize & 7] += 1;
+
+            let is_text = matches!(
+                pack.pack_type,
+                CDTextPackType::Title
+                    | CDTextPackType::Performers
+                    | CDTextPackType::Songwriters
+                    | CDTextPackType::Composers
+                    | CDTextPackType::Arrangers
+                    | CDTextPackType::Message
+                    | CDTextPackType::DiscID
+                    | CDTextPackType::ClosedInfo
+                    | CDTextPackType::Code
+            );
+
+            if is_text {
+                summary.text_bytes += pack.payload.iter().filter(|&&byte| byte != 0).count();
+
+                if let CDTextTrackNumber::Track(track) = pack.track_number {
+                    highest_track = highest_track.max(track);
+                }
+            }
+        }
+
+        let size_info = (0..8)
+            .filter(|&block_number| summary.block_packs[block_number as usize] > 0)
+            .find_map(|block_number| {
+                BlockSizeInfo::from_packs(block_number, self.iter_pack_chunks())
+            });
+
+        summary.tracks = match size_info {
+            Some(size_info) => size_info.last_track,
+            None => highest_track,
+        };
+
+        if let Some(size_info) = size_info {
+            for (block_number, language) in summary.languages.iter_mut().enumerate() {
+                if summary.block_packs[block_number] > 0 {
+                    *language = size_info.language(block_number as u8);
+                }
+            }
+        }
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finalize_pack;
+    #[cfg(feature = "alloc")]
+    use crate::{BlockBuilder, CDTextBuilder};
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn counts() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .with_text(CDTextPackType::Performers, 0, "Band")
+            .with_block(BlockBuilder::new(Language::German).with_text(
+                CDTextPackType::Title,
+                0,
+                "Titel",
+            ))
+            .build()
+            .unwrap();
+        let summary = CDText::from_data(&data).summary();
+
+        assert_eq!(summary.packs, 9);
+        assert_eq!(summary.damaged_packs, 0);
+        assert_eq!(summary.pack_count(CDTextPackType::Title), 2);
+        assert_eq!(summary.pack_count(CDTextPackType::Performers), 1);
+        assert_eq!(summary.pack_count(CDTextPackType::BlockSizeInfo), 6);
+        assert_eq!(summary.block_packs[..3], [5, 4, 0]);
+        assert_eq!(summary.blocks(), 2);
+        assert_eq!(summary.tracks, 1);
+        assert!(
+            summary
+                .iter_languages()
+                .eq([Language::English, Language::German])
+        );
+        assert_eq!(summary.text_bytes, 19);
+    }
+
+    #[test]
+    fn without_size_info() {
+        let mut data = [0; 3 * 18];
+        let packs = [[0x80, 0, 0, 0], [0x80, 2, 1, 0], [0x70, 0, 2, 0]];
+
+        for ((pack, header), payload) in
+            data.chunks_exact_mut(18)
+                .zip(packs)
+                .zip([&b"Album\0First\0"[..], b"Second\0", b""])
+        {
+            pack[..4].copy_from_slice(&header);
+            pack[4..4 + payload.len()].copy_from_slice(payload);
+            finalize_pack(pack.try_into().unwrap());
+        }
+        data[18 + 17] ^= 0xff;
+
+        let summary = CDText::from_data(&data).summary();
+        assert_eq!(summary.packs, 3);
+        assert_eq!(summary.damaged_packs, 1);
+        assert_eq!(summary.unknown_packs, 1);
+        assert_eq!(summary.pack_count(CDTextPackType::Unknown(0x70)), 1);
+        assert_eq!(summary.tracks, 2);
+        assert_eq!(summary.iter_languages().count(), 0);
+        assert_eq!(summary.text_bytes, 16);
+    }
+}
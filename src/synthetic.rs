@@ -0,0 +1,182 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{BlockBuilder, BuildError, CDTextBuilder, CDTextPackType, Genre, GenreCode, Language};
+
+/// Words the titles are made of. Some are accented, so the text isn't plain ASCII.
+const WORDS: [&str; 16] = [
+    "Morning",
+    "Über",
+    "Café",
+    "River",
+    "Light",
+    "Señor",
+    "Echo",
+    "Winter",
+    "Blue",
+    "Déjà vu",
+    "Garden",
+    "Night",
+    "Fjord",
+    "Song",
+    "Road",
+    "Silence",
+];
+
+/// Performer of the album and all its tracks, so the field is repeated for each track.
+const PERFORMER: &str = "The Synthetic Ensemble";
+
+/// A valid EAN-13 code of the disc.
+const UPC: &str = "4006381333931";
+
+/// Generator of realistic CD-Text, so pipelines can be tested without ripping discs.
+///
+/// The disc has a title, a performer, a message, a genre and, optionally, UPC/EAN,
+/// and each track has a title, a performer, a songwriter, a composer and an ISRC.
+/// Every block describes the same tracks, with different titles and the message naming
+/// the language of the block. The performer is the same for all the tracks and songwriters are shared by
+/// adjacent tracks, so these fields are compressed with TABs.
+///
+/// The text is deterministic: the same settings always give the same data.
+#[derive(Debug, Clone)]
+pub struct SyntheticDisc {
+    tracks: u8,
+    languages: Vec<Language>,
+    title_length: usize,
+    tab_compression: bool,
+    codes: bool,
+    header: bool,
+}
+
+impl SyntheticDisc {
+    /// Creates a generator of a disc with the number of tracks (1-99), in English,
+    /// with short titles, codes and TAB compression.
+    pub fn new(tracks: u8) -> Self {
+        Self {
+            tracks: tracks.clamp(1, 99),
+            languages: Vec::from([Language::English]),
+            title_length: 0,
+            tab_compression: true,
+            codes: true,
+            header: false,
+        }
+    }
+
+    /// Sets languages of the blocks, one block per language, up to 8.
+    pub fn with_languages(mut self, languages: impl IntoIterator<Item = Language>) -> Self {
+        self.languages = languages.into_iter().take(8).collect();
+        self
+    }
+
+    /// Makes titles at least `length` characters long, padding them with words.
+    /// A pack holds 12 characters, so long titles span several packs.
+    pub fn with_title_length(mut self, length: usize) -> Self {
+        self.title_length = length;
+        self
+    }
+
+    /// Sets whether repeated fields are written as TABs,
+    /// see [`CDTextBuilder::with_tab_compression`].
+    pub fn with_tab_compression(mut self, compress_tabs: bool) -> Self {
+        self.tab_compression = compress_tabs;
+        self
+    }
+
+    /// Sets whether the UPC/EAN code of the disc and ISRCs of the tracks are written.
+    pub fn with_codes(mut self, codes: bool) -> Self {
+        self.codes = codes;
+        self
+    }
+
+    /// Sets whether the data starts with the 4-byte service header, like dumps made by cdrecord.
+    pub fn with_header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Returns the builder with all the text, to adjust it before building.
+    pub fn to_builder(&self) -> CDTextBuilder {
+        let blocks = self
+            .languages
+            .iter()
+            .enumerate()
+            .map(|(block_number, &language)| self.block(block_number, language));
+
+        CDTextBuilder::from_blocks(blocks)
+            .with_tab_compression(self.tab_compression)
+            .with_header(self.header)
+    }
+
+    /// Builds the CD-Text. Fails if the text doesn't fit into [`crate::MAX_BLOCK_PACKS`]
+    /// packs of 12 characters each, e.g. with long titles of many tracks.
+    pub fn build(&self) -> Result<Vec<u8>, BuildError> {
+        self.to_builder().build()
+    }
+
+    /// Internal method. Returns the block with the text in the language.
+    fn block(&self, block_number: usize, language: Language) -> BlockBuilder {
+        let mut block = BlockBuilder::new(language)
+            .with_text(CDTextPackType::Title, 0, self.title(block_number, 0))
+            .with_text(CDTextPackType::Performers, 0, PERFORMER)
+            .with_text(
+                CDTextPackType::Message,
+                0,
+                format!("Synthetic disc, {} edition", language.name()),
+            )
+            .with_genre(Genre {
+                code: GenreCode::PopMusic,
+                text: Some(String::from("Synth-pop")),
+            });
+
+        if self.codes {
+            block.set_text(CDTextPackType::Code, 0, UPC);
+        }
+
+        for track in 1..=self.tracks {
+            let writer = format!("Writer {}", track.div_ceil(2));
+
+            block.set_text(
+                CDTextPackType::Title,
+                track,
+                self.title(block_number, track),
+            );
+            block.set_text(CDTextPackType::Performers, track, PERFORMER);
+            block.set_text(CDTextPackType::Songwriters, track, writer);
+            block.set_text(
+                CDTextPackType::Composers,
+                track,
+                format!("Composer {track}"),
+            );
+
+            if self.codes {
+                block.set_text(CDTextPackType::Code, track, format!("XXSYN26{track:05}"));
+            }
+        }
+
+        block
+    }
+
+    /// Internal method. Returns the title of the track in the block, track 0 being the album.
+    fn title(&self, block_number: usize, track: u8) -> String {
+        let index = track as usize * 3 + block_number * 5;
+        let mut title = match track {
+            0 => format!("{} Sessions", WORDS[index % WORDS.len()]),
+            _ => format!(
+                "{} {}",
+                WORDS[index % WORDS.len()],
+                WORDS[(index + 7) % WORDS.len()]
+            ),
+        };
+
+        // Padding continues with the words following the ones of the title.
+        for word in WORDS.iter().cycle().skip(index + 8) {
+            if title.chars().count() >= self.title_length {
+                break;
+            }
+
+            title.push(' ');
+            title.push_str(word);
+        }
+
+        title
+    }
+}
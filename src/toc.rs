@@ -0,0 +1,275 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{CDTextPack, CDTextPackType};
+
+/// Address on the disc in minutes, seconds and frames (1/75 of a second).
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Msf {
+    pub minute: u8,
+    pub second: u8,
+    pub frame: u8,
+}
+
+impl Msf {
+    /// Number of frames from the very beginning of the disc.
+    pub fn to_frames(self) -> u32 {
+        (self.minute as u32 * 60 + self.second as u32) * 75 + self.frame as u32
+    }
+
+    /// Logical block address. The first 150 frames (the pregap of track 1) have negative addresses.
+    pub fn to_lba(self) -> i32 {
+        self.to_frames() as i32 - 150
+    }
+}
+
+/// The address as `mm:ss:ff`.
+impl fmt::Display for Msf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.minute, self.second, self.frame)
+    }
+}
+
+/// Start of a track, as recorded in a TOC pack.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TocTrack {
+    pub number: u8,
+    pub start: Msf,
+}
+
+/// Contents of Table of Contents packs (0x88): a copy of the disc TOC.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Toc {
+    pub first_track: u8,
+    pub last_track: u8,
+    /// Start of the lead-out area.
+    pub lead_out: Msf,
+    /// Starts of the tracks, sorted by track number.
+    pub tracks: Vec<TocTrack>,
+}
+
+impl Toc {
+    /// Collects TOC packs of the block. Returns `None` if the pack describing
+    /// the whole disc (track 0) is missing.
+    pub(crate) fn from_packs<'a>(
+        block_number: u8,
+        packs: impl IntoIterator<Item = &'a CDTextPack>,
+    ) -> Option<Self> {
+        let mut toc: Option<Self> = None;
+        let mut tracks: Vec<TocTrack> = Vec::new();
+
+        for pack in packs {
+            if pack.pack_type != CDTextPackType::TOC || pack.block_number != block_number {
+                continue;
+            }
+
+            let payload = &pack.payload;
+
+            match u8::from(pack.track_number) {
+                // First and last track number, then the lead-out address.
+                0 => {
+                    toc.get_or_insert(Self {
+                        first_track: payload[0],
+                        last_track: payload[1],
+                        lead_out: msf(&payload[3..6]),
+                        tracks: Vec::new(),
+                    });
+                }
+                // Starts of up to four consecutive tracks beginning with the one in the header.
+                first => {
+                    for (index, address) in payload.chunks_exact(3).enumerate() {
+                        let number = first.saturating_add(index as u8);
+
+                        if !tracks.iter().any(|track| track.number == number) {
+                            tracks.push(TocTrack {
+                                number,
+                                start: msf(address),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut toc = toc?;
+
+        tracks.retain(|track| (toc.first_track..=toc.last_track).contains(&track.number));
+        tracks.sort_by_key(|track| track.number);
+        toc.tracks = tracks;
+
+        Some(toc)
+    }
+
+    /// Returns the start address of the track.
+    pub fn track_start(&self, track: u8) -> Option<Msf> {
+        self.tracks
+            .iter()
+            .find(|entry| entry.number == track)
+            .map(|entry| entry.start)
+    }
+}
+
+fn msf(bytes: &[u8]) -> Msf {
+    Msf {
+        minute: bytes[0],
+        second: bytes[1],
+        frame: bytes[2],
+    }
+}
+
+/// An interval described by an Additional TOC pack.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TocInterval {
+    /// Priority number of the interval, starting from 1.
+    pub priority: u8,
+    /// Number of intervals declared by the pack.
+    pub interval_count: u8,
+    pub start: Msf,
+    pub end: Msf,
+}
+
+/// Contents of Additional TOC packs (0x89): intervals of the disc, such as hidden tracks.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdditionalToc {
+    /// Intervals in the order of the packs.
+    pub intervals: Vec<TocInterval>,
+}
+
+impl AdditionalToc {
+    /// Collects Additional TOC packs of the block. Returns `None` if there are none.
+    pub(crate) fn from_packs<'a>(
+        block_number: u8,
+        packs: impl IntoIterator<Item = &'a CDTextPack>,
+    ) -> Option<Self> {
+        let intervals: Vec<TocInterval> = packs
+            .into_iter()
+            .filter(|pack| {
+                pack.pack_type == CDTextPackType::AdditionalTOC && pack.block_number == block_number
+            })
+            // Track number field holds the priority number instead.
+            .map(|pack| TocInterval {
+                priority: u8::from(pack.track_number),
+                interval_count: pack.payload[0],
+                start: msf(&pack.payload[6..9]),
+                end: msf(&pack.payload[9..12]),
+            })
+            .collect();
+
+        (!intervals.is_empty()).then_some(Self { intervals })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    use crate::{CDText, CDTextEntryDataType, finalize_pack};
+
+    /// Internal function. Builds a pack of the type in block 0 with the correct CRC.
+    fn pack(pack_type: u8, track: u8, seq: u8, payload: [u8; 12]) -> [u8; 18] {
+        let mut pack = [0u8; 18];
+
+        pack[..3].copy_from_slice(&[pack_type, track, seq]);
+        pack[4..16].copy_from_slice(&payload);
+        finalize_pack(&mut pack);
+
+        pack
+    }
+
+    #[test]
+    fn toc() {
+        // Tracks 1-5, the lead-out at 40:02:10, then starts of tracks 1-4 and 5.
+        let data = [
+            pack(0x88, 0, 0, [1, 5, 0, 40, 2, 10, 0, 0, 0, 0, 0, 0]),
+            pack(0x88, 1, 1, [0, 2, 0, 4, 30, 12, 9, 0, 74, 15, 20, 1]),
+            pack(0x88, 5, 2, [30, 45, 60, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        ]
+        .concat();
+
+        let entries = CDText::from_data(&data).parse().unwrap();
+        let CDTextEntryDataType::Toc(toc) = &entries[0].data else {
+            panic!("expected TOC, found {:?}", entries[0].data);
+        };
+
+        assert_eq!((toc.first_track, toc.last_track), (1, 5));
+        assert_eq!(toc.lead_out.to_string(), "40:02:10");
+        assert_eq!(toc.tracks.len(), 5);
+        assert_eq!(
+            toc.track_start(2),
+            Some(Msf {
+                minute: 4,
+                second: 30,
+                frame: 12,
+            })
+        );
+        assert_eq!(toc.track_start(1).unwrap().to_lba(), 0);
+        assert_eq!(
+            toc.track_start(5).unwrap().to_frames(),
+            (30 * 60 + 45) * 75 + 60
+        );
+        assert_eq!(toc.track_start(6), None);
+        assert_eq!(entries[0].packs, 0..3);
+    }
+
+    #[test]
+    fn toc_without_disc_pack() {
+        let packs = [CDText::parse_pack(&pack(0x88, 1, 0, [0; 12]))];
+
+        assert_eq!(Toc::from_packs(0, &packs), None);
+    }
+
+    #[test]
+    fn additional_toc() {
+        // Two intervals: 2 of them declared, start and end addresses in the last 6 bytes.
+        let data = [
+            pack(0x89, 1, 0, [2, 0, 0, 0, 0, 0, 0, 2, 0, 3, 10, 0]),
+            pack(0x89, 2, 1, [2, 0, 0, 0, 0, 0, 52, 0, 0, 55, 30, 74]),
+        ]
+        .concat();
+
+        let entries = CDText::from_data(&data).parse().unwrap();
+
+        assert_eq!(
+            entries[0].data,
+            CDTextEntryDataType::AdditionalToc(AdditionalToc {
+                intervals: alloc::vec![
+                    TocInterval {
+                        priority: 1,
+                        interval_count: 2,
+                        start: Msf {
+                            minute: 0,
+                            second: 2,
+                            frame: 0,
+                        },
+                        end: Msf {
+                            minute: 3,
+                            second: 10,
+                            frame: 0,
+                        },
+                    },
+                    TocInterval {
+                        priority: 2,
+                        interval_count: 2,
+                        start: Msf {
+                            minute: 52,
+                            second: 0,
+                            frame: 0,
+                        },
+                        end: Msf {
+                            minute: 55,
+                            second: 30,
+                            frame: 74,
+                        },
+                    },
+                ],
+            })
+        );
+        assert_eq!(AdditionalToc::from_packs(0, &[]), None);
+    }
+}
@@ -0,0 +1,428 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{
+    BlockBuilder, BlockSizeInfo, CDText, CDTextEntry, CDTextEntryDataType, CDTextError,
+    CDTextPackType, CDTextTrackNumber, Charset, Disc, Genre, GenreCode, InputSheetError, Language,
+    Unmappable,
+};
+
+/// Internal structure. Writes `keyword = value` lines of a sheet.
+struct SheetWriter {
+    data: Vec<u8>,
+    charset: Charset,
+}
+
+impl SheetWriter {
+    /// Writes the line with ASCII value.
+    fn line(&mut self, keyword: &str, value: &str) {
+        self.data
+            .extend_from_slice(format!("{keyword:<20} = {value}\n").as_bytes());
+    }
+
+    /// Writes the line with text value, if it's present, encoded in the charset of the block.
+    fn text(&mut self, keyword: &str, value: Option<&str>) {
+        let Some(value) = value else {
+            return;
+        };
+
+        let (value, _) = self
+            .charset
+            .encode(value, Unmappable::Replace)
+            .unwrap_or_default();
+
+        self.data
+            .extend_from_slice(format!("{keyword:<20} = ").as_bytes());
+        self.data.extend(value);
+        self.data.push(b'\n');
+    }
+}
+
+/// Creates Sony CD-Text Input Sheet Version 0.7T of the block, as accepted
+/// by libburn and cdrskin (`input_sheet_v07t=`). Each sheet describes one block.
+///
+/// Text is encoded in the charset of the block, declared by Block Size Info
+/// (ISO 8859-1 if there's none). Characters that cannot be encoded are replaced with `?`.
+pub fn to_v07t(entries: &[CDTextEntry], block_number: u8) -> Vec<u8> {
+    let disc = Disc::from_block(entries, block_number);
+    let size_info = BlockSizeInfo::from_entries(entries, block_number);
+
+    let charset = size_info
+        .map(|size_info| size_info.charset())
+        .filter(|charset| charset.is_encodable())
+        .unwrap_or(Charset::Iso8859_1);
+
+    let text_code = match charset {
+        Charset::Ascii => "ASCII",
+        Charset::MsJis => "MS-JIS",
+        _ => "8859",
+    };
+
+    let language = size_info
+        .and_then(|size_info| size_info.language(block_number))
+        .unwrap_or(Language::English);
+
+    let closed_info = entries.iter().find_map(|entry| match &entry.data {
+        CDTextEntryDataType::String(text)
+            if entry.block_number == block_number
+                && entry.entry_type == CDTextPackType::ClosedInfo
+                && entry.track_number == CDTextTrackNumber::WholeAlbum =>
+        {
+            Some(text.as_str())
+        }
+        _ => None,
+    });
+
+    let mut sheet = SheetWriter {
+        data: Vec::new(),
+        charset,
+    };
+
+    sheet.line("Input Sheet Version", "0.7T");
+    sheet.line("Text Code", text_code);
+    sheet.line("Language Code", language.name());
+
+    sheet.text("Album Title", disc.title.as_deref());
+    sheet.text("Artist Name", disc.performer.as_deref());
+    sheet.text("Songwriter", disc.songwriter.as_deref());
+    sheet.text("Composer", disc.composer.as_deref());
+    sheet.text("Arranger", disc.arranger.as_deref());
+    sheet.text("Album Message", disc.message.as_deref());
+    sheet.text("Catalog Number", disc.disc_id.as_deref());
+
+    if let Some(genre) = &disc.genre {
+        sheet.line("Genre Code", genre.code.name());
+        sheet.text("Genre Information", genre.text.as_deref());
+    }
+
+    sheet.text("Closed Information", closed_info);
+    sheet.text("UPC / EAN", disc.upc.as_ref().map(|upc| upc.as_str()));
+
+    let copy_protection = match size_info {
+        Some(size_info) if size_info.is_copy_protected() => "Y",
+        _ => "N",
+    };
+
+    sheet.line("Copy Protection Flag", copy_protection);
+
+    if let (Some(first), Some(last)) = (disc.tracks.first(), disc.tracks.last()) {
+        sheet.line("First Track Number", &format!("{}", first.number));
+        sheet.line("Last Track Number", &format!("{}", last.number));
+    }
+
+    for track in &disc.tracks {
+        sheet.data.push(b'\n');
+
+        let number = track.number;
+
+        sheet.text(&format!("Track {number:02} Title"), track.title.as_deref());
+        sheet.text(
+            &format!("Track {number:02} Artist"),
+            track.performer.as_deref(),
+        );
+        sheet.text(
+            &format!("Track {number:02} Songwriter"),
+            track.songwriter.as_deref(),
+        );
+        sheet.text(
+            &format!("Track {number:02} Composer"),
+            track.composer.as_deref(),
+        );
+        sheet.text(
+            &format!("Track {number:02} Arranger"),
+            track.arranger.as_deref(),
+        );
+        sheet.text(
+            &format!("Track {number:02} Message"),
+            track.message.as_deref(),
+        );
+        sheet.text(
+            &format!("ISRC {number:02}"),
+            track.isrc.as_ref().map(|isrc| isrc.as_str()),
+        );
+    }
+
+    sheet.data
+}
+
+impl CDText<'_> {
+    /// Parses the data into Input Sheets Version 0.7T, one per block in the order of block numbers,
+    /// see [`to_v07t`].
+    pub fn to_v07t(&self) -> Result<Vec<Vec<u8>>, CDTextError> {
+        let entries = self.parse()?;
+
+        let mut block_numbers: Vec<u8> = entries.iter().map(|e| e.block_number).collect();
+        block_numbers.sort_unstable();
+        block_numbers.dedup();
+
+        Ok(block_numbers
+            .into_iter()
+            .map(|block_number| to_v07t(&entries, block_number))
+            .collect())
+    }
+}
+
+/// Internal function. Yields `keyword = value` lines of the sheet with their numbers.
+/// Keywords are ASCII; lines without `=` are skipped.
+fn sheet_lines(sheet: &[u8]) -> impl Iterator<Item = (usize, &str, &[u8])> {
+    sheet
+        .split(|&byte| byte == b'\n')
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let separator = line.iter().position(|&byte| byte == b'=')?;
+            let keyword = core::str::from_utf8(&line[..separator]).ok()?;
+
+            Some((
+                index + 1,
+                keyword.trim(),
+                line[separator + 1..].trim_ascii(),
+            ))
+        })
+}
+
+impl BlockBuilder {
+    /// Creates a block from Sony CD-Text Input Sheet Version 0.7T, see [`to_v07t`].
+    ///
+    /// The text is decoded according to `Text Code` of the sheet: `8859`, `ASCII` or,
+    /// with the `encoding` feature, `MS-JIS`. Copy protection and track range are
+    /// not taken, since the builder derives them from the content.
+    pub fn from_v07t(sheet: &[u8]) -> Result<Self, InputSheetError> {
+        let mut charset = Charset::Iso8859_1;
+
+        for (line, keyword, value) in sheet_lines(sheet) {
+            if keyword.eq_ignore_ascii_case("Text Code") {
+                charset = match value {
+                    b"8859" | b"ISO-8859-1" => Charset::Iso8859_1,
+                    b"ASCII" => Charset::Ascii,
+                    b"MS-JIS" if Charset::MsJis.is_supported() => Charset::MsJis,
+                    _ => return Err(InputSheetError::UnsupportedTextCode { line }),
+                };
+            }
+        }
+
+        let mut block = Self::new(Language::English).with_charset(charset);
+        let mut genre_code = None;
+        let mut genre_text = None;
+
+        for (line, keyword, value) in sheet_lines(sheet) {
+            let text = || {
+                charset
+                    .decode(value)
+                    .ok_or(InputSheetError::InvalidText { line })
+            };
+
+            let words: Vec<&str> = keyword.split_whitespace().collect();
+
+            let (pack_type, track) = match words.as_slice() {
+                ["Language", "Code"] => {
+                    let name = text()?;
+
+                    block = block.with_language(
+                        Language::from_name(&name)
+                            .ok_or(InputSheetError::UnknownLanguage { line })?,
+                    );
+                    continue;
+                }
+                ["Genre", "Code"] => {
+                    genre_code =
+                        Some(GenreCode::from_name(&text()?).unwrap_or(GenreCode::NotDefined));
+                    continue;
+                }
+                ["Genre", "Information"] => {
+                    genre_text = Some(text()?);
+                    continue;
+                }
+                ["Album", "Title"] => (CDTextPackType::Title, 0),
+                ["Artist", "Name"] => (CDTextPackType::Performers, 0),
+                ["Songwriter"] => (CDTextPackType::Songwriters, 0),
+                ["Composer"] => (CDTextPackType::Composers, 0),
+                ["Arranger"] => (CDTextPackType::Arrangers, 0),
+                ["Album", "Message"] => (CDTextPackType::Message, 0),
+                ["Catalog", "Number"] => (CDTextPackType::DiscID, 0),
+                ["Closed", "Information"] => (CDTextPackType::ClosedInfo, 0),
+                ["UPC", "/", "EAN"] => (CDTextPackType::Code, 0),
+                ["ISRC", number] => (CDTextPackType::Code, track_number(number, line)?),
+                ["Track", number, field] => {
+                    let pack_type = match *field {
+                        "Title" => CDTextPackType::Title,
+                        "Artist" => CDTextPackType::Performers,
+                        "Songwriter" => CDTextPackType::Songwriters,
+                        "Composer" => CDTextPackType::Composers,
+                        "Arranger" => CDTextPackType::Arrangers,
+                        "Message" => CDTextPackType::Message,
+                        _ => continue,
+                    };
+
+                    (pack_type, track_number(number, line)?)
+                }
+                _ => continue,
+            };
+
+            let text = text()?;
+
+            if !text.is_empty() {
+                block.set_text(pack_type, track, text);
+            }
+        }
+
+        if genre_code.is_some() || genre_text.is_some() {
+            block = block.with_genre(Genre {
+                code: genre_code.unwrap_or(GenreCode::NotDefined),
+                text: genre_text.filter(|text: &String| !text.is_empty()),
+            });
+        }
+
+        Ok(block)
+    }
+}
+
+/// Internal function. Parses the track number of a keyword.
+fn track_number(number: &str, line: usize) -> Result<u8, InputSheetError> {
+    match number.parse::<u8>() {
+        Ok(number @ 1..=99) => Ok(number),
+        _ => Err(InputSheetError::InvalidTrackNumber { line }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CDTextBuilder;
+
+    #[test]
+    fn export() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Caf\u{e9}")
+            .with_text(CDTextPackType::Performers, 0, "Band")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .with_text(CDTextPackType::Title, 2, "Second")
+            .with_text(CDTextPackType::Code, 2, "USRC17607839")
+            .with_genre(Genre {
+                code: GenreCode::Jazz,
+                text: Some(String::from("Bebop")),
+            })
+            .build()
+            .unwrap();
+        let sheets = CDText::from_data(&data).to_v07t().unwrap();
+
+        assert_eq!(sheets.len(), 1);
+        assert_eq!(
+            sheets[0],
+            b"Input Sheet Version  = 0.7T\n\
+              Text Code            = 8859\n\
+              Language Code        = English\n\
+              Album Title          = Caf\xe9\n\
+              Artist Name          = Band\n\
+              Genre Code           = Jazz\n\
+              Genre Information    = Bebop\n\
+              Copy Protection Flag = N\n\
+              First Track Number   = 1\n\
+              Last Track Number    = 2\n\
+              \n\
+              Track 01 Title       = First\n\
+              \n\
+              Track 02 Title       = Second\n\
+              ISRC 02              = USRC17607839\n"
+        );
+    }
+
+    #[test]
+    fn import() {
+        let sheet = b"Input Sheet Version = 0.7T\r\n\
+                      Text Code = 8859\n\
+                      Language Code = German\n\
+                      Album Title = Caf\xe9  \n\
+                      Genre Code = Jazz\n\
+                      Genre Information = Bebop\n\
+                      Closed Information = Internal\n\
+                      Copy Protection Flag = Y\n\
+                      Remarks\n\
+                      Track 01 Title = First\n\
+                      Track 02 Artist = Guest\n\
+                      ISRC 02 = USRC17607839\n";
+
+        let block = BlockBuilder::from_v07t(sheet).unwrap();
+
+        assert_eq!(block.language(), Language::German);
+        assert_eq!(block.text(CDTextPackType::Title, 0), Some("Caf\u{e9}"));
+        assert_eq!(block.text(CDTextPackType::ClosedInfo, 0), Some("Internal"));
+        assert_eq!(block.text(CDTextPackType::Title, 1), Some("First"));
+        assert_eq!(block.text(CDTextPackType::Performers, 2), Some("Guest"));
+        assert_eq!(block.text(CDTextPackType::Code, 2), Some("USRC17607839"));
+        assert_eq!(
+            block.genre(),
+            Some(&Genre {
+                code: GenreCode::Jazz,
+                text: Some(String::from("Bebop")),
+            })
+        );
+    }
+
+    #[test]
+    fn import_errors() {
+        assert_eq!(
+            BlockBuilder::from_v07t(b"Text Code = EBCDIC\n").err(),
+            Some(InputSheetError::UnsupportedTextCode { line: 1 })
+        );
+        assert_eq!(
+            BlockBuilder::from_v07t(b"Text Code = ASCII\nAlbum Title = Caf\xe9\n").err(),
+            Some(InputSheetError::InvalidText { line: 2 })
+        );
+        assert_eq!(
+            BlockBuilder::from_v07t(b"Language Code = Klingon\n").err(),
+            Some(InputSheetError::UnknownLanguage { line: 1 })
+        );
+        assert_eq!(
+            BlockBuilder::from_v07t(b"\nTrack 00 Title = Lead-in\n").err(),
+            Some(InputSheetError::InvalidTrackNumber { line: 2 })
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Caf\u{e9}")
+            .with_text(CDTextPackType::Performers, 0, "Band")
+            .with_text(CDTextPackType::DiscID, 0, "XY-1234")
+            .with_text(CDTextPackType::ClosedInfo, 0, "Internal")
+            .with_text(CDTextPackType::Code, 0, "036000291452")
+            .with_text(CDTextPackType::Title, 1, "First")
+            .with_text(CDTextPackType::Composers, 2, "Composer")
+            .with_text(CDTextPackType::Code, 2, "USRC17607839")
+            .with_genre(Genre {
+                code: GenreCode::Jazz,
+                text: Some(String::from("Bebop")),
+            })
+            .with_block(
+                BlockBuilder::new(Language::French)
+                    .with_charset(Charset::Ascii)
+                    .with_text(CDTextPackType::Title, 0, "Titre"),
+            )
+            .build()
+            .unwrap();
+
+        let sheets = CDText::from_data(&data).to_v07t().unwrap();
+        let blocks = sheets
+            .iter()
+            .map(|sheet| BlockBuilder::from_v07t(sheet))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(CDTextBuilder::from_blocks(blocks).build().unwrap(), data);
+    }
+
+    #[cfg(feature = "synthetic")]
+    #[test]
+    fn synthetic_round_trip() {
+        let disc = crate::SyntheticDisc::new(12)
+            .with_languages([Language::English, Language::Spanish])
+            .with_tab_compression(false);
+        let data = disc.build().unwrap();
+
+        let sheets = CDText::from_data(&data).to_v07t().unwrap();
+        let blocks = sheets
+            .iter()
+            .map(|sheet| BlockBuilder::from_v07t(sheet))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(CDTextBuilder::from_blocks(blocks).build().unwrap(), data);
+    }
+}
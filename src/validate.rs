@@ -0,0 +1,449 @@
+use alloc::vec::Vec;
+
+use crate::{
+    BlockSizeInfo, CDText, CDTextError, CDTextPack, CDTextPackType, CDTextTrackNumber,
+    sequence::SequenceChecker,
+};
+
+/// How serious a validation issue is.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    /// The data is usable, but doesn't follow the specification exactly.
+    Warning,
+    /// The data is damaged or inconsistent.
+    Error,
+}
+
+/// What's wrong with the data.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IssueKind {
+    /// A problem with a separate pack or its text.
+    Pack(CDTextError),
+    /// The block has no complete set of Block Size Info packs.
+    MissingBlockSizeInfo { block_number: u8 },
+    /// Block Size Info declares a different number of packs of the type than the block has.
+    PackCountMismatch {
+        block_number: u8,
+        pack_type: CDTextPackType,
+        declared: u8,
+        found: usize,
+    },
+    /// Block Size Info declares a different sequence number of the last pack of the block.
+    LastSequenceNumberMismatch {
+        block_number: u8,
+        declared: u8,
+        found: u8,
+    },
+    /// There's no entry of the type for the track, although the block has it.
+    MissingTrack {
+        block_number: u8,
+        pack_type: CDTextPackType,
+        track_number: CDTextTrackNumber,
+    },
+}
+
+/// A single problem found during validation.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    pub kind: IssueKind,
+}
+
+/// Result of [`CDText::validate`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Whether the data has no errors. Warnings are allowed.
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Returns the highest severity of the issues, if there are any.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.issues.iter().map(|issue| issue.severity).max()
+    }
+
+    /// Returns issues with [`Severity::Error`].
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+    }
+
+    /// Returns issues with [`Severity::Warning`].
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Warning)
+    }
+
+    fn push(&mut self, severity: Severity, kind: IssueKind) {
+        self.issues.push(ValidationIssue { severity, kind });
+    }
+}
+
+impl CDText<'_> {
+    /// Checks the data for conformance to the specification: CRCs, sequence numbers,
+    /// consistency of Block Size Info and presence of text for every track.
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let packs: Vec<CDTextPack> = self.iter_pack_chunks().collect();
+
+        self.validate_packs(&packs, &mut report);
+        let size_infos = validate_blocks(&packs, &mut report);
+        self.validate_text(&size_infos, &mut report);
+
+        report
+    }
+
+    /// Returns a copy of the data with the packs renumbered in order, pack counts
+    /// and last sequence numbers in Block Size Info of each block updated and CRCs recomputed,
+    /// e.g. after editing the packs by hand or a rip with damaged counters.
+    ///
+    /// Text and the order of the packs are left as they are, so damaged text gets a valid CRC
+    /// as well: only repair dumps whose text is known to be intact. The header, the truncated pack
+    /// and the bytes past the declared length are kept, like in [`CDText::to_bytes`].
+    pub fn repair(&self) -> Vec<u8> {
+        let mut packs: Vec<CDTextPack> = self.iter_pack_chunks().collect();
+
+        for (index, pack) in packs.iter_mut().enumerate() {
+            pack.seq_counter = index as u8;
+        }
+
+        let mut pack_counts = [[0u8; 16]; 8];
+        let mut last_sequence_numbers = [0u8; 8];
+
+        for pack in &packs {
+            let block_number = pack.block_number as usize;
+            let type_index = u8::from(pack.pack_type).wrapping_sub(0x80) as usize;
+
+            if let Some(count) = pack_counts[block_number].get_mut(type_index) {
+                *count = count.saturating_add(1);
+            }
+
+            last_sequence_numbers[block_number] = pack.seq_counter;
+        }
+
+        // Pack counts are bytes 4-19 of Block Size Info, last sequence numbers are bytes 20-27.
+        // Each of the three packs holds 12 bytes of it.
+        for pack in &mut packs {
+            if pack.pack_type != CDTextPackType::BlockSizeInfo {
+                continue;
+            }
+
+            let counts = &pack_counts[pack.block_number as usize];
+
+            match u8::from(pack.track_number) {
+                0 => pack.payload[4..].copy_from_slice(&counts[..8]),
+                1 => {
+                    pack.payload[..8].copy_from_slice(&counts[8..]);
+                    pack.payload[8..].copy_from_slice(&last_sequence_numbers[..4]);
+                }
+                2 => pack.payload[..4].copy_from_slice(&last_sequence_numbers[4..]),
+                _ => {}
+            }
+        }
+
+        let mut data = self.to_bytes();
+
+        for (pack_index, pack) in packs.iter_mut().enumerate() {
+            pack.fix_crc();
+            data[self.pack_offset(pack_index)..][..18].copy_from_slice(&pack.to_bytes());
+        }
+
+        data
+    }
+
+    /// Internal method. Checks each pack separately.
+    fn validate_packs(&self, packs: &[CDTextPack], report: &mut ValidationReport) {
+        let mut sequence = SequenceChecker::default();
+
+        for (pack_index, pack) in packs.iter().enumerate() {
+            let offset = self.pack_offset(pack_index);
+
+            if let Some(gap) = sequence.check(pack_index, pack) {
+                let problem = CDTextError::SequenceGap {
+                    pack_index,
+                    offset,
+                    expected: gap.expected,
+                    found: gap.found,
+                };
+
+                report.push(Severity::Error, IssueKind::Pack(problem));
+            }
+
+            if !pack.crc_valid {
+                let problem = CDTextError::CrcMismatch {
+                    pack_index,
+                    offset,
+                    expected: crate::crc::crc16(&self.data[pack_index * 18..][..16]),
+                    found: pack.crc,
+                };
+
+                report.push(Severity::Error, IssueKind::Pack(problem));
+            }
+
+            if let CDTextPackType::Unknown(pack_type) = pack.pack_type {
+                let problem = CDTextError::InvalidPackType {
+                    pack_index,
+                    offset,
+                    pack_type,
+                };
+
+                report.push(Severity::Warning, IssueKind::Pack(problem));
+            }
+        }
+
+        let trailing = self.trailing_bytes();
+
+        if !trailing.is_empty() {
+            let problem = CDTextError::ShortPack {
+                pack_index: packs.len(),
+                offset: self.pack_offset(packs.len()),
+                length: trailing.len(),
+            };
+
+            report.push(Severity::Warning, IssueKind::Pack(problem));
+        }
+
+        if self.length > self.data.len() {
+            let problem = CDTextError::LengthMismatch {
+                declared: self.length + 2,
+                actual: self.data.len() + 2,
+            };
+
+            report.push(Severity::Warning, IssueKind::Pack(problem));
+        }
+    }
+
+    /// Internal method. Checks reassembled text for problems and missing tracks.
+    /// `size_infos` are Block Size Info of each block, if it's complete.
+    fn validate_text(
+        &self,
+        size_infos: &[Option<BlockSizeInfo>; 8],
+        report: &mut ValidationReport,
+    ) {
+        let (entries, diagnostics) = self.parse_with_diagnostics();
+
+        for diagnostic in diagnostics {
+            // Problems with separate packs have been reported already.
+            let severity = match diagnostic.problem {
+                CDTextError::TrackNumberMismatch { .. }
+                | CDTextError::CharacterPositionMismatch { .. } => Severity::Error,
+                CDTextError::UnterminatedText { .. }
+                | CDTextError::InvalidText { .. }
+                | CDTextError::UnsupportedCharset { .. }
+                | CDTextError::InvalidCode { .. } => Severity::Warning,
+                _ => continue,
+            };
+
+            report.push(severity, IssueKind::Pack(diagnostic.problem));
+        }
+
+        // Blocks may cover different tracks, so each one is checked against its own Size Info.
+        for (block_number, size_info) in size_infos.iter().enumerate() {
+            let Some(size_info) = size_info else {
+                continue;
+            };
+
+            let block_entries = || {
+                entries
+                    .iter()
+                    .filter(|entry| entry.block_number as usize == block_number)
+            };
+
+            let mut types: Vec<CDTextPackType> = Vec::new();
+
+            for entry in block_entries() {
+                // Other types only describe the whole album.
+                let is_per_track = matches!(
+                    entry.entry_type,
+                    CDTextPackType::Title
+                        | CDTextPackType::Performers
+                        | CDTextPackType::Songwriters
+                        | CDTextPackType::Composers
+                        | CDTextPackType::Arrangers
+                        | CDTextPackType::Message
+                );
+
+                if is_per_track && !types.contains(&entry.entry_type) {
+                    types.push(entry.entry_type);
+                }
+            }
+
+            // Every text type present must cover the album and each track.
+            let tracks = core::iter::once(0).chain(size_info.first_track..=size_info.last_track);
+
+            for track in tracks {
+                let track_number = CDTextTrackNumber::from(track);
+
+                for &pack_type in &types {
+                    let present = block_entries().any(|entry| {
+                        entry.entry_type == pack_type && entry.track_number == track_number
+                    });
+
+                    if !present {
+                        report.push(
+                            Severity::Warning,
+                            IssueKind::MissingTrack {
+                                block_number: block_number as u8,
+                                pack_type,
+                                track_number,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Internal function. Checks each block against its Block Size Info.
+/// Returns Block Size Info of each block, if it's complete.
+fn validate_blocks(
+    packs: &[CDTextPack],
+    report: &mut ValidationReport,
+) -> [Option<BlockSizeInfo>; 8] {
+    let mut blocks: Vec<u8> = Vec::new();
+
+    for pack in packs {
+        if !matches!(pack.pack_type, CDTextPackType::Unknown(_))
+            && !blocks.contains(&pack.block_number)
+        {
+            blocks.push(pack.block_number);
+        }
+    }
+
+    let mut size_infos = [None; 8];
+
+    for block_number in blocks {
+        let Some(size_info) = BlockSizeInfo::from_packs(block_number, packs) else {
+            report.push(
+                Severity::Error,
+                IssueKind::MissingBlockSizeInfo { block_number },
+            );
+            continue;
+        };
+
+        let block_packs = || {
+            packs
+                .iter()
+                .filter(|pack| pack.block_number == block_number)
+        };
+
+        for (type_index, &declared) in size_info.pack_counts.iter().enumerate() {
+            let pack_type = CDTextPackType::from(0x80 + type_index as u8);
+            let found = block_packs()
+                .filter(|pack| pack.pack_type == pack_type)
+                .count();
+
+            if found != declared as usize {
+                report.push(
+                    Severity::Error,
+                    IssueKind::PackCountMismatch {
+                        block_number,
+                        pack_type,
+                        declared,
+                        found,
+                    },
+                );
+            }
+        }
+
+        let declared = size_info.last_sequence_numbers[block_number as usize];
+
+        if let Some(last) = block_packs().next_back()
+            && last.seq_counter != declared
+        {
+            report.push(
+                Severity::Warning,
+                IssueKind::LastSequenceNumberMismatch {
+                    block_number,
+                    declared,
+                    found: last.seq_counter,
+                },
+            );
+        }
+
+        size_infos[block_number as usize] = Some(size_info);
+    }
+
+    size_infos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockBuilder, CDTextBuilder, Language, finalize_pack};
+
+    #[test]
+    fn repaired_data_is_valid() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "Track")
+            .with_text(CDTextPackType::Performers, 0, "Band")
+            .build()
+            .unwrap();
+
+        // A rip with a damaged sequence counter and a stale pack count in Block Size Info.
+        let mut damaged = data.clone();
+        damaged[18 + 2] = 0x40;
+        let (packs, _) = damaged.as_chunks_mut::<18>();
+        let size_info = packs.len() - 3;
+        packs[size_info][4 + 5] += 1;
+        finalize_pack(&mut packs[size_info]);
+
+        assert!(!CDText::from_data(&damaged).validate().is_valid());
+
+        let repaired = CDText::from_data(&damaged).repair();
+        assert!(CDText::from_data(&repaired).validate().is_valid());
+        assert_eq!(repaired, data);
+    }
+
+    #[test]
+    fn tracks_of_each_block() {
+        let data = CDTextBuilder::new()
+            .with_text(CDTextPackType::Title, 0, "Album")
+            .with_text(CDTextPackType::Title, 1, "One")
+            .with_text(CDTextPackType::Title, 2, "Two")
+            .with_block(
+                BlockBuilder::new(Language::German)
+                    .with_text(CDTextPackType::Title, 0, "Album")
+                    .with_text(CDTextPackType::Title, 1, "Eins"),
+            )
+            .build()
+            .unwrap();
+
+        // The second block has no title of track 2, although its Size Info declares it.
+        assert_eq!(
+            CDText::from_data(&data).validate().issues,
+            [ValidationIssue {
+                severity: Severity::Warning,
+                kind: IssueKind::MissingTrack {
+                    block_number: 1,
+                    pack_type: CDTextPackType::Title,
+                    track_number: CDTextTrackNumber::Track(2),
+                },
+            }]
+        );
+
+        // Once the Size Info of the block declares only track 1, the block is complete.
+        let mut data = data;
+        let (packs, _) = data.as_chunks_mut::<18>();
+        let size_info = packs
+            .iter_mut()
+            .find(|pack| pack[0] == 0x8f && pack[1] == 0 && pack[3] >> 4 == 1)
+            .unwrap();
+        size_info[4 + 2] = 1;
+        finalize_pack(size_info);
+
+        assert!(CDText::from_data(&data).validate().is_valid());
+    }
+}
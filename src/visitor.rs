@@ -0,0 +1,74 @@
+use alloc::vec::Vec;
+
+use crate::{BorrowedEntry, CDText, CDTextEntry, CDTextError, CDTextPack, parser::ParseState};
+
+/// Receives data from [`CDText::parse_with`] as soon as it is assembled.
+///
+/// Entries are passed as [`BorrowedEntry`], with text borrowed from the data where possible,
+/// like by [`CDText::parse_borrowed`], so it's only copied by visitors that keep it.
+///
+/// All methods do nothing by default, so implementors only override the ones they need.
+pub trait CDTextVisitor {
+    /// Called for each entry describing the whole album,
+    /// including entries decoded from binary packs, like TOC and Block Size Info.
+    fn on_album_field(&mut self, _entry: BorrowedEntry<'_>) {}
+
+    /// Called for each entry describing a track.
+    fn on_track_field(&mut self, _track: u8, _entry: BorrowedEntry<'_>) {}
+
+    /// Called for each binary pack (TOC, Block Size Info or reserved) as it is met.
+    ///
+    /// Entries decoded from TOC and Block Size Info packs follow once all packs are read.
+    fn on_binary_pack(&mut self, _pack_index: usize, _pack: &CDTextPack) {}
+}
+
+/// Collects all entries in the order of the packs, like [`CDText::parse`] does.
+impl CDTextVisitor for Vec<CDTextEntry> {
+    fn on_album_field(&mut self, entry: BorrowedEntry<'_>) {
+        self.push(entry.into_owned());
+    }
+
+    fn on_track_field(&mut self, _track: u8, entry: BorrowedEntry<'_>) {
+        self.push(entry.into_owned());
+    }
+}
+
+impl<'data> CDText<'data> {
+    /// Parses CD-Text like [`CDText::parse`], but passes entries to the visitor
+    /// instead of collecting them, so no list of entries is ever built.
+    ///
+    /// On error the visitor has already received the entries preceding the broken pack.
+    pub fn parse_with(&self, visitor: &mut impl CDTextVisitor) -> Result<(), CDTextError> {
+        self.parse_entries(&mut ParseState::new(self, self.options.mode()), visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CDTextBuilder, CDTextPackType, Genre, GenreCode};
+
+    #[test]
+    fn same_as_parse() {
+        let data = CDTextBuilder::new()
+            .with_text(
+                CDTextPackType::Title,
+                0,
+                "An album title longer than one pack",
+            )
+            .with_text(CDTextPackType::Title, 1, "Café")
+            .with_text(CDTextPackType::Performers, 2, "Band")
+            .with_genre(Genre {
+                code: GenreCode::Jazz,
+                text: None,
+            })
+            .build()
+            .unwrap();
+
+        let cdtext = CDText::from_data(&data);
+        let mut entries = Vec::new();
+        cdtext.parse_with(&mut entries).unwrap();
+
+        assert_eq!(entries, cdtext.parse().unwrap());
+    }
+}
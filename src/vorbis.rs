@@ -0,0 +1,148 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::Disc;
+
+impl Disc {
+    /// Maps CD-Text fields to Vorbis comments, as `(key, value)` pairs, to tag a FLAC or Ogg file.
+    ///
+    /// Track 0 describes the whole album, e.g. for an image of the disc with an embedded cue sheet:
+    /// it gets `ALBUM`, `ALBUMARTIST`, `ARTIST`, `LYRICIST`, `COMPOSER`, `ARRANGER`, `DESCRIPTION`,
+    /// `GENRE`, `TRACKTOTAL` and `BARCODE`. Other tracks also get `TITLE`, `TRACKNUMBER` and `ISRC`;
+    /// their performer, songwriter, composer, arranger and message fall back to the album ones.
+    ///
+    /// Missing fields are left out. Genre is written as [`crate::Genre::tag_name`].
+    pub fn vorbis_comments(&self, track: u8) -> Vec<(&'static str, String)> {
+        let info = self.track(track).filter(|_| track != 0);
+        let inherited = |track_field: Option<&String>, album_field: &Option<String>| {
+            track_field.or(album_field.as_ref()).cloned()
+        };
+
+        let fields = [
+            ("TITLE", info.and_then(|info| info.title.clone())),
+            ("ALBUM", self.title.clone()),
+            (
+                "ARTIST",
+                inherited(
+                    info.and_then(|info| info.performer.as_ref()),
+                    &self.performer,
+                ),
+            ),
+            ("ALBUMARTIST", self.performer.clone()),
+            (
+                "LYRICIST",
+                inherited(
+                    info.and_then(|info| info.songwriter.as_ref()),
+                    &self.songwriter,
+                ),
+            ),
+            (
+                "COMPOSER",
+                inherited(info.and_then(|info| info.composer.as_ref()), &self.composer),
+            ),
+            (
+                "ARRANGER",
+                inherited(info.and_then(|info| info.arranger.as_ref()), &self.arranger),
+            ),
+            (
+                "DESCRIPTION",
+                inherited(info.and_then(|info| info.message.as_ref()), &self.message),
+            ),
+            (
+                "GENRE",
+                self.genre
+                    .as_ref()
+                    .and_then(|genre| genre.tag_name())
+                    .map(String::from),
+            ),
+            ("TRACKNUMBER", info.map(|info| format!("{}", info.number))),
+            (
+                "TRACKTOTAL",
+                self.tracks.last().map(|info| format!("{}", info.number)),
+            ),
+            (
+                "ISRC",
+                info.and_then(|info| info.isrc)
+                    .map(|isrc| String::from(isrc.as_str())),
+            ),
+            ("BARCODE", self.upc.map(|upc| String::from(upc.as_str()))),
+        ];
+
+        fields
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, value?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Genre, GenreCode, Isrc, TrackInfo, Upc};
+
+    #[test]
+    fn comments() {
+        let disc = Disc {
+            title: Some(String::from("Album")),
+            performer: Some(String::from("Band")),
+            songwriter: Some(String::from("Songwriter")),
+            upc: Upc::parse("036000291452"),
+            genre: Some(Genre {
+                code: GenreCode::PopMusic,
+                text: Some(String::from("Synth-pop")),
+            }),
+            tracks: Vec::from([
+                TrackInfo {
+                    number: 1,
+                    title: Some(String::from("First")),
+                    songwriter: Some(String::from("Lyricist")),
+                    isrc: Isrc::parse("USRC17607839"),
+                    ..TrackInfo::default()
+                },
+                TrackInfo {
+                    number: 2,
+                    title: Some(String::from("Second")),
+                    ..TrackInfo::default()
+                },
+            ]),
+            ..Disc::default()
+        };
+        let pairs = |pairs: &[(&'static str, &str)]| -> Vec<(&'static str, String)> {
+            pairs
+                .iter()
+                .map(|&(key, value)| (key, String::from(value)))
+                .collect()
+        };
+
+        assert_eq!(
+            disc.vorbis_comments(1),
+            pairs(&[
+                ("TITLE", "First"),
+                ("ALBUM", "Album"),
+                ("ARTIST", "Band"),
+                ("ALBUMARTIST", "Band"),
+                ("LYRICIST", "Lyricist"),
+                ("GENRE", "Synth-pop"),
+                ("TRACKNUMBER", "1"),
+                ("TRACKTOTAL", "2"),
+                ("ISRC", "USRC17607839"),
+                ("BARCODE", "036000291452"),
+            ])
+        );
+        assert_eq!(
+            disc.vorbis_comments(0),
+            pairs(&[
+                ("ALBUM", "Album"),
+                ("ARTIST", "Band"),
+                ("ALBUMARTIST", "Band"),
+                ("LYRICIST", "Songwriter"),
+                ("GENRE", "Synth-pop"),
+                ("TRACKTOTAL", "2"),
+                ("BARCODE", "036000291452"),
+            ])
+        );
+        assert!(
+            disc.vorbis_comments(2)
+                .contains(&("LYRICIST", String::from("Songwriter")))
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use alloc::{string::String, string::ToString, vec::Vec};
+
+use wasm_bindgen::prelude::*;
+
+use crate::{CDText, CDTextBuilder, Disc};
+
+/// Parses CD-Text data, e.g. contents of a `.cdt` file, with or without the header,
+/// into the JSON disc model, see [`Disc::to_json`].
+#[wasm_bindgen(js_name = parseCdText)]
+pub fn parse_cd_text(data: &[u8]) -> Result<String, JsError> {
+    let disc = CDText::new(data)
+        .disc()
+        .map_err(|error| JsError::new(&error.to_string()))?;
+
+    Ok(disc.to_json())
+}
+
+/// Parses CD-Text data into the list of entries as JSON, in the order of the data.
+/// Unlike the disc model, it includes every block, TOC and raw data.
+#[wasm_bindgen(js_name = parseEntries)]
+pub fn parse_entries(data: &[u8]) -> Result<String, JsError> {
+    let entries = CDText::new(data)
+        .parse()
+        .map_err(|error| JsError::new(&error.to_string()))?;
+
+    serde_json::to_string(&entries).map_err(|error| JsError::new(&error.to_string()))
+}
+
+/// Builds CD-Text data from the JSON disc model, see [`Disc::from_json`].
+/// The 4-byte header is prepended if `header` is set.
+#[wasm_bindgen(js_name = buildCdText)]
+pub fn build_cd_text(json: &str, header: bool) -> Result<Vec<u8>, JsError> {
+    let disc = Disc::from_json(json).map_err(|error| JsError::new(&error.to_string()))?;
+
+    CDTextBuilder::from_disc(&disc)
+        .with_header(header)
+        .build()
+        .map_err(|error| JsError::new(&error.to_string()))
+}
@@ -0,0 +1,265 @@
+use alloc::{format, string::String};
+use core::fmt::Write;
+
+use crate::{CDText, CDTextEntryDataType, CDTextError, Disc, Toc};
+
+/// Maximum length of an XMCD line, including the keyword.
+const MAX_LINE_LENGTH: usize = 256;
+
+impl Toc {
+    /// Computes the CDDB/freedb disc ID of the TOC.
+    pub fn freedb_id(&self) -> u32 {
+        let digit_sum = |mut seconds: u32| {
+            let mut sum = 0;
+
+            while seconds > 0 {
+                sum += seconds % 10;
+                seconds /= 10;
+            }
+
+            sum
+        };
+
+        let checksum: u32 = self
+            .tracks
+            .iter()
+            .map(|track| digit_sum(track.start.to_frames() / 75))
+            .sum();
+
+        let first = self
+            .tracks
+            .first()
+            .map_or(0, |track| track.start.to_frames() / 75);
+        let length = (self.lead_out.to_frames() / 75).saturating_sub(first);
+
+        (checksum % 0xff) << 24 | length << 8 | self.tracks.len() as u32
+    }
+}
+
+/// Internal function. Writes the value under the keyword, escaped and split over as many lines
+/// as needed to fit into the maximum line length.
+fn write_value(record: &mut String, keyword: &str, value: &str) {
+    let limit = MAX_LINE_LENGTH - keyword.len() - 1;
+    let mut line = String::new();
+
+    for character in value.chars() {
+        let mut buffer = [0; 4];
+
+        // Neither a character nor an escape sequence is split between lines.
+        let escaped = match character {
+            '\n' => "\\n",
+            '\t' => "\\t",
+            '\\' => "\\\\",
+            _ => character.encode_utf8(&mut buffer),
+        };
+
+        if line.len() + escaped.len() > limit {
+            let _ = writeln!(record, "{keyword}={line}");
+            line.clear();
+        }
+
+        line.push_str(escaped);
+    }
+
+    let _ = writeln!(record, "{keyword}={line}");
+}
+
+impl Disc {
+    /// Creates a CDDB/freedb XMCD record of the disc.
+    ///
+    /// Frame offsets, disc length and `DISCID` are only known with the TOC, e.g. from TOC packs.
+    /// Track titles are numbered from the first track. A track performer that differs from the
+    /// album one is written into the title, as in `TTITLE0=Performer / Title`.
+    pub fn to_xmcd(&self, toc: Option<&Toc>) -> String {
+        let mut record = String::from("# xmcd\n#\n");
+
+        if let Some(toc) = toc {
+            record.push_str("# Track frame offsets:\n");
+
+            for track in &toc.tracks {
+                let _ = writeln!(record, "#\t{}", track.start.to_frames());
+            }
+
+            let _ = writeln!(
+                record,
+                "#\n# Disc length: {} seconds\n#",
+                toc.lead_out.to_frames() / 75
+            );
+        }
+
+        let _ = writeln!(
+            record,
+            "# Revision: 0\n# Submitted via: cdtext {}\n#",
+            env!("CARGO_PKG_VERSION")
+        );
+
+        if let Some(toc) = toc {
+            let _ = writeln!(record, "DISCID={:08x}", toc.freedb_id());
+        }
+
+        let title = match (&self.performer, &self.title) {
+            (Some(performer), Some(title)) => format!("{performer} / {title}"),
+            (None, Some(title)) => title.clone(),
+            (Some(performer), None) => performer.clone(),
+            (None, None) => String::new(),
+        };
+
+        write_value(&mut record, "DTITLE", &title);
+        record.push_str("DYEAR=\n");
+
+        let genre = self.genre.as_ref().map_or(String::new(), |genre| {
+            genre
+                .text
+                .clone()
+                .unwrap_or_else(|| String::from(genre.code.name()))
+        });
+
+        write_value(&mut record, "DGENRE", &genre);
+
+        let first_track = toc.map_or_else(
+            || self.tracks.first().map_or(1, |track| track.number),
+            |toc| toc.first_track,
+        );
+        let last_track = toc.map_or_else(
+            || self.tracks.last().map_or(0, |track| track.number),
+            |toc| toc.last_track,
+        );
+
+        for (index, number) in (first_track..=last_track).enumerate() {
+            let track = self.track(number);
+
+            let title = track.and_then(|track| track.title.as_deref()).unwrap_or("");
+            let performer = track
+                .and_then(|track| track.performer.as_ref())
+                .filter(|&performer| Some(performer) != self.performer.as_ref());
+
+            let title = match performer {
+                Some(performer) => format!("{performer} / {title}"),
+                None => String::from(title),
+            };
+
+            write_value(&mut record, &format!("TTITLE{index}"), &title);
+        }
+
+        write_value(&mut record, "EXTD", self.message.as_deref().unwrap_or(""));
+
+        for (index, number) in (first_track..=last_track).enumerate() {
+            let message = self
+                .track(number)
+                .and_then(|track| track.message.as_deref())
+                .unwrap_or("");
+
+            write_value(&mut record, &format!("EXTT{index}"), message);
+        }
+
+        record.push_str("PLAYORDER=\n");
+        record
+    }
+}
+
+impl CDText<'_> {
+    /// Parses the data into a CDDB/freedb XMCD record, taking the TOC from TOC packs
+    /// if there are any, see [`Disc::to_xmcd`].
+    pub fn to_xmcd(&self) -> Result<String, CDTextError> {
+        let entries = self.parse()?;
+
+        let toc = entries.iter().find_map(|entry| match &entry.data {
+            CDTextEntryDataType::Toc(toc) => Some(toc),
+            _ => None,
+        });
+
+        Ok(Disc::from_entries(&entries).to_xmcd(toc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Genre, GenreCode, Msf, TocTrack, TrackInfo};
+    use alloc::vec::Vec;
+
+    /// Internal function. Returns the MSF of the frame number.
+    fn msf(frames: u32) -> Msf {
+        Msf {
+            minute: (frames / 75 / 60) as u8,
+            second: (frames / 75 % 60) as u8,
+            frame: (frames % 75) as u8,
+        }
+    }
+
+    /// Internal function. Returns the TOC of a disc with 6 tracks.
+    fn toc() -> Toc {
+        let offsets = [150, 15363, 32314, 46592, 63414, 80489];
+
+        Toc {
+            first_track: 1,
+            last_track: 6,
+            lead_out: msf(95462),
+            tracks: (1..)
+                .zip(offsets)
+                .map(|(number, offset)| TocTrack {
+                    number,
+                    start: msf(offset),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn freedb_id() {
+        assert_eq!(toc().freedb_id(), 0x3404f606);
+    }
+
+    #[test]
+    fn record() {
+        let disc = Disc {
+            title: Some(String::from("Album")),
+            performer: Some(String::from("Band")),
+            message: Some(String::from("Liner\nnotes")),
+            genre: Some(Genre {
+                code: GenreCode::Jazz,
+                text: None,
+            }),
+            tracks: Vec::from([
+                TrackInfo {
+                    number: 1,
+                    title: Some(String::from("First")),
+                    performer: Some(String::from("Band")),
+                    ..TrackInfo::default()
+                },
+                TrackInfo {
+                    number: 2,
+                    title: Some(String::from("Second")),
+                    performer: Some(String::from("Guest")),
+                    message: Some(String::from("Bonus")),
+                    ..TrackInfo::default()
+                },
+            ]),
+            ..Disc::default()
+        };
+
+        let record = disc.to_xmcd(Some(&toc()));
+
+        assert!(record.starts_with("# xmcd\n#\n# Track frame offsets:\n#\t150\n#\t15363\n"));
+        assert!(record.contains("#\t80489\n#\n# Disc length: 1272 seconds\n#\n"));
+        assert!(record.ends_with(
+            "DISCID=3404f606\nDTITLE=Band / Album\nDYEAR=\nDGENRE=Jazz\n\
+             TTITLE0=First\nTTITLE1=Guest / Second\nTTITLE2=\nTTITLE3=\nTTITLE4=\nTTITLE5=\n\
+             EXTD=Liner\\nnotes\nEXTT0=\nEXTT1=Bonus\nEXTT2=\nEXTT3=\nEXTT4=\nEXTT5=\n\
+             PLAYORDER=\n"
+        ));
+    }
+
+    #[test]
+    fn long_values() {
+        let mut record = String::new();
+        let value = "\\".repeat(200);
+        write_value(&mut record, "EXTD", &value);
+
+        let lines: Vec<&str> = record.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|line| line.len() <= MAX_LINE_LENGTH));
+        assert_eq!(lines[0].len(), 5 + 250);
+        assert_eq!(lines.concat().replace("EXTD=", ""), "\\\\".repeat(200));
+    }
+}
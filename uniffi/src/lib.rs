@@ -0,0 +1,179 @@
+//! UniFFI bindings of the cdtext crate for Swift and Kotlin.
+//!
+//! The disc model is exposed as records, mirroring [`cdtext::Disc`]; UPC/EAN and ISRC codes
+//! are plain strings.
+
+use std::fmt;
+
+use cdtext::{CDText, CDTextBuilder, GenreCode, Isrc, Upc};
+
+uniffi::setup_scaffolding!();
+
+/// Genre of the album.
+#[derive(uniffi::Record)]
+pub struct Genre {
+    /// Standard genre code.
+    pub code: u16,
+    /// Name of the standard genre, ignored when building.
+    pub name: String,
+    /// Supplementary genre information.
+    pub text: Option<String>,
+}
+
+/// Text information of a single track.
+#[derive(uniffi::Record)]
+pub struct Track {
+    pub number: u8,
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    pub songwriter: Option<String>,
+    pub composer: Option<String>,
+    pub arranger: Option<String>,
+    pub message: Option<String>,
+    pub isrc: Option<String>,
+}
+
+/// Text information of the whole disc.
+#[derive(uniffi::Record)]
+pub struct Disc {
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    pub songwriter: Option<String>,
+    pub composer: Option<String>,
+    pub arranger: Option<String>,
+    pub message: Option<String>,
+    pub disc_id: Option<String>,
+    pub upc: Option<String>,
+    pub genre: Option<Genre>,
+    /// Tracks sorted by number.
+    pub tracks: Vec<Track>,
+}
+
+/// Errors of the bindings.
+#[derive(Debug, uniffi::Error)]
+pub enum CdTextError {
+    /// The data cannot be parsed.
+    Parse { message: String },
+    /// The disc cannot be written, e.g. the text doesn't fit.
+    Build { message: String },
+    /// UPC/EAN or ISRC code is malformed.
+    InvalidCode { code: String },
+}
+
+impl fmt::Display for CdTextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse { message } => write!(f, "cannot parse CD-Text: {message}"),
+            Self::Build { message } => write!(f, "cannot build CD-Text: {message}"),
+            Self::InvalidCode { code } => write!(f, "invalid UPC/EAN or ISRC code {code:?}"),
+        }
+    }
+}
+
+impl std::error::Error for CdTextError {}
+
+impl From<cdtext::Disc> for Disc {
+    fn from(disc: cdtext::Disc) -> Self {
+        Self {
+            title: disc.title,
+            performer: disc.performer,
+            songwriter: disc.songwriter,
+            composer: disc.composer,
+            arranger: disc.arranger,
+            message: disc.message,
+            disc_id: disc.disc_id,
+            upc: disc.upc.map(|upc| upc.as_str().to_owned()),
+            genre: disc.genre.map(|genre| Genre {
+                code: u16::from(genre.code),
+                name: genre.code.name().to_owned(),
+                text: genre.text,
+            }),
+            tracks: disc
+                .tracks
+                .into_iter()
+                .map(|track| Track {
+                    number: track.number,
+                    title: track.title,
+                    performer: track.performer,
+                    songwriter: track.songwriter,
+                    composer: track.composer,
+                    arranger: track.arranger,
+                    message: track.message,
+                    isrc: track.isrc.map(|isrc| isrc.as_str().to_owned()),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<Disc> for cdtext::Disc {
+    type Error = CdTextError;
+
+    fn try_from(disc: Disc) -> Result<Self, Self::Error> {
+        fn parse<T>(
+            code: Option<String>,
+            parse: fn(&str) -> Option<T>,
+        ) -> Result<Option<T>, CdTextError> {
+            code.map(|code| parse(&code).ok_or(CdTextError::InvalidCode { code }))
+                .transpose()
+        }
+
+        let mut tracks = disc
+            .tracks
+            .into_iter()
+            .map(|track| {
+                Ok(cdtext::TrackInfo {
+                    number: track.number,
+                    title: track.title,
+                    performer: track.performer,
+                    songwriter: track.songwriter,
+                    composer: track.composer,
+                    arranger: track.arranger,
+                    message: track.message,
+                    isrc: parse(track.isrc, Isrc::parse)?,
+                })
+            })
+            .collect::<Result<Vec<_>, CdTextError>>()?;
+        tracks.sort_by_key(|track| track.number);
+
+        Ok(Self {
+            title: disc.title,
+            performer: disc.performer,
+            songwriter: disc.songwriter,
+            composer: disc.composer,
+            arranger: disc.arranger,
+            message: disc.message,
+            disc_id: disc.disc_id,
+            upc: parse(disc.upc, Upc::parse)?,
+            genre: disc.genre.map(|genre| cdtext::Genre {
+                code: GenreCode::from(genre.code),
+                text: genre.text,
+            }),
+            tracks,
+        })
+    }
+}
+
+/// Parses CD-Text data, e.g. contents of a `.cdt` file, with or without the header.
+#[uniffi::export]
+pub fn parse_disc(data: Vec<u8>) -> Result<Disc, CdTextError> {
+    CDText::new(&data)
+        .disc()
+        .map(Disc::from)
+        .map_err(|error| CdTextError::Parse {
+            message: error.to_string(),
+        })
+}
+
+/// Builds CD-Text data of the disc, with the 4-byte header prepended if `header` is set.
+#[uniffi::export]
+pub fn build_disc(disc: Disc, header: bool) -> Result<Vec<u8>, CdTextError> {
+    let disc = cdtext::Disc::try_from(disc)?;
+
+    CDTextBuilder::from_disc(&disc)
+        .with_header(header)
+        .build()
+        .map_err(|error| CdTextError::Build {
+            message: error.to_string(),
+        })
+}
@@ -0,0 +1,5 @@
+//! WebAssembly bindings of the cdtext crate, to be built with `wasm-pack`.
+//! Exports `parseCdText`, `parseEntries` and `buildCdText`.
+
+// The bindings live in the crate behind the `wasm` feature, linking it in exports them.
+pub use cdtext;